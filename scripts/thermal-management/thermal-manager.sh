@@ -157,5 +157,3 @@ echo "$TIMESTAMP | CPU:${CPU_TEMP}°C | Teclado:~${KEYBOARD_EST}°C | Max:${MAX_
 if [ $(wc -l < $LOG_FILE 2>/dev/null || echo 0) -gt 2000 ]; then
     tail -1000 $LOG_FILE > ${LOG_FILE}.tmp && mv ${LOG_FILE}.tmp $LOG_FILE
 fi
-
-echo "comfort-$MODE" > /tmp/cpu-mode.current 2>/dev/null
@@ -38,6 +38,7 @@ mkdir -p "$PKG_DIR/DEBIAN"
 mkdir -p "$PKG_DIR/usr/local/bin"
 mkdir -p "$PKG_DIR/usr/share/applications"
 mkdir -p "$PKG_DIR/etc/systemd/system"
+mkdir -p "$PKG_DIR/etc/thermal-monitor/modes"
 
 # Copy control file
 echo "[3/6] Creating package metadata..."
@@ -85,8 +86,13 @@ chmod 755 "$PKG_DIR/usr/local/bin/thermal-manager.sh"
 # Copy systemd files
 cp systemd/thermal-manager.service "$PKG_DIR/etc/systemd/system/"
 cp systemd/thermal-manager.timer "$PKG_DIR/etc/systemd/system/"
+cp systemd/thermal-fan-curve.service "$PKG_DIR/etc/systemd/system/"
 chmod 644 "$PKG_DIR/etc/systemd/system/"*
 
+# Copy example custom modes
+cp modes/*.ini "$PKG_DIR/etc/thermal-monitor/modes/"
+chmod 644 "$PKG_DIR/etc/thermal-monitor/modes/"*
+
 # Copy desktop file
 cp thermal-monitor.desktop "$PKG_DIR/usr/share/applications/"
 chmod 644 "$PKG_DIR/usr/share/applications/thermal-monitor.desktop"
@@ -108,10 +114,6 @@ systemctl daemon-reload 2>/dev/null || true
 # Enable thermal manager timer (but don't start automatically)
 systemctl enable thermal-manager.timer 2>/dev/null || true
 
-# Create initial mode file
-echo "unknown" > /tmp/cpu-mode.current 2>/dev/null || true
-chmod 666 /tmp/cpu-mode.current 2>/dev/null || true
-
 echo ""
 echo "Thermal Monitor installed successfully!"
 echo ""
@@ -137,6 +139,7 @@ if [ "$1" = "remove" ] || [ "$1" = "purge" ]; then
     # Stop and disable services
     systemctl stop thermal-manager.timer 2>/dev/null || true
     systemctl disable thermal-manager.timer 2>/dev/null || true
+    systemctl disable --now thermal-fan-curve.service 2>/dev/null || true
     systemctl daemon-reload 2>/dev/null || true
 
     # Update desktop database
@@ -145,7 +148,11 @@ if [ "$1" = "remove" ] || [ "$1" = "purge" ]; then
     fi
 
     # Clean up
-    rm -f /tmp/cpu-mode.current 2>/dev/null || true
+    rm -rf /run/thermal-monitor 2>/dev/null || true
+fi
+
+if [ "$1" = "purge" ]; then
+    rm -f /etc/thermal-monitor/fan-curve.ini
 fi
 EOF
 chmod 755 "$PKG_DIR/DEBIAN/postrm"
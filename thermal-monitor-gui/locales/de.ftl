@@ -0,0 +1,311 @@
+# Thermal Monitor - Deutsch
+
+app-title = Temperaturmonitor
+app-description = CPU-Temperatur überwachen und steuern
+app-version = Temperaturmonitor v{ $version }
+
+# Section headings
+section-temperatures = Temperaturen
+section-performance = Leistung
+section-mode-control = Modussteuerung
+section-target-temp = Zieltemperatur
+section-fan = Lüfter
+section-history = Verlauf
+section-heat = Top-Prozesse
+section-tuning = CPU-Feinabstimmung
+
+# Reading labels
+label-cpu = CPU
+label-kbd = TAST
+label-zone = Zone
+label-perf = Leist
+label-freq = Takt
+label-power = Leistung
+label-battery = Akku
+battery-charging = Lädt
+battery-discharging = Entlädt
+battery-full = Voll
+battery-not-charging = Lädt nicht
+battery-left = noch { $time }
+battery-until-full = { $time } bis voll
+source-ac = Am Netz
+source-battery = Im Akkubetrieb
+label-source-mode = { $source }:
+hint-source-mode = Modus beim Ein- oder Ausstecken des Netzteils
+label-lid-mode = Deckel zu:
+hint-lid-mode = Modus, solange der Deckel geschlossen ist; beim Öffnen kehrt der vorherige Modus zurück
+label-mode = Modus
+label-epp = Energiepräf.
+label-turbo = Turbo
+label-min-perf = Min. Leistung
+
+# Controls
+target-auto = AUTO
+target-off = AUS
+target-ok = OK
+fan-boost = BOOST
+fan-normal = NORMAL
+fan-hint = Maximale Kühlung
+fan-by-user = Sie
+fan-by-auto = automatische Regelung
+fan-by-mode = aktueller Modus
+fan-by-rule = eine Regel
+fan-curve-edit = Kurve…
+fan-curve-title = Lüfterkurve
+fan-curve-enabled = Lüfter mit dieser Kurve steuern
+fan-curve-hint = Punkte ziehen zum Verschieben, Doppelklick zum Hinzufügen, Rechtsklick zum Entfernen
+fan-curve-axis-temp = CPU ({ $unit })
+fan-curve-axis-duty = Lüfter (%)
+fan-curve-now = Jetzt { $temp } → { $duty } %
+fan-curve-hysteresis = Hysterese
+fan-rpm = { $rpm } U/min
+turbo-on = Ein
+turbo-off = Aus
+
+# Tooltips
+hint-power-restricted = Energiezähler sind auf diesem System nur für root lesbar
+power-package = Paket { $watts } W
+power-core = Kerne { $watts } W
+power-uncore = Uncore { $watts } W
+hint-power-limits = Grenzen: { $limits }
+hint-driver = Treiber: { $driver }
+hint-temp-unit = Temperatureinheit wechseln
+hint-freq-unit = Frequenzeinheit wechseln
+hint-epp = Energie-/Leistungspräferenz (EPP) für die CPU
+heat-show = Anzeigen
+heat-hide = Ausblenden
+hint-heat = Prozesse mit der meisten CPU-Zeit seit der letzten Aktualisierung
+heat-measuring = Messe…
+heat-process = Prozess
+heat-pid = PID
+heat-cpu = CPU
+heat-power = Leistung
+hint-heat-power = Schätzung: die Package-Leistung, aufgeteilt nach dem Anteil jedes Prozesses an der CPU-Zeit
+process-renice = Priorität senken
+process-stop = Anhalten
+process-continue = Fortsetzen
+hint-process-renice = Erhöht den Nice-Wert um 5 (jetzt { $nice })
+hint-process-nice-max = Bereits auf niedrigster Priorität (Nice { $nice })
+hint-process-stop = Hält den Prozess mit SIGSTOP an, bis er fortgesetzt wird
+hint-process-continue = Setzt den Prozess mit SIGCONT fort
+hint-process-other-user = Gehört einem anderen Benutzer
+status-process-reniced = Priorität von { $name } ({ $pid }) gesenkt
+status-process-stopped = { $name } ({ $pid }) angehalten
+status-process-continued = { $name } ({ $pid }) fortgesetzt
+hint-turbo = Turbo-Frequenzen erlauben
+hint-min-perf = Niedrigste Leistung, auf die die CPU fallen darf
+hint-profile = Energie- und Lüfterrichtlinie der Firmware
+hint-mode-new = Eigenen Modus anlegen
+hint-mode-drift = Einstellungen wurden seit dem Anwenden von { $mode } geändert. Klicken Sie auf den Modus, um ihn erneut anzuwenden.
+hint-conflict = Abschalten mit: { $command }
+hint-ppd-cooperate = Profile bei power-profiles-daemon anfordern, statt Plattformprofil und EPP direkt zu schreiben
+hint-fan-curve = Lüfterdrehzahl nach Temperatur (benötigt thermal-fan-curve.service)
+hint-fan-requesters = BOOST angefordert von: { $who }
+
+# History graph
+history-collecting = Daten werden gesammelt...
+history-cpu = CPU
+history-kbd = Tast
+history-target = Ziel
+history-fan = Lüfter
+history-power = Leistung (W)
+history-export = Exportieren
+
+# Status messages
+status-mode-changed = Modus geändert zu { $mode }
+status-error = Fehler: { $error }
+status-auto-on = Auto EIN
+status-auto-off = Auto AUS
+status-fan-boost = Lüfter-Boost
+status-fan-auto = Lüfter automatisch
+status-fan-held = Lüfter-BOOST bleibt an für { $who }
+status-fan-curve-on = Lüfterkurve aktiv
+status-fan-curve-off = Lüfterkurve aus, die Firmware steuert den Lüfter
+status-exported = Exportiert nach { $path }
+status-target = Ziel { $target }
+status-zone-changed = Temperaturzone: { $zone }
+status-epp = Energiepräferenz: { $value }
+status-turbo-on = Turbo aktiviert
+status-turbo-off = Turbo deaktiviert
+status-min-perf = Mindestleistung { $pct } %
+status-profile = Plattformprofil: { $profile }
+status-mode-saved = Modus { $mode } gespeichert
+status-mode-deleted = Modus { $mode } gelöscht
+status-ppd-on = Profile werden über power-profiles-daemon gesetzt
+status-ppd-off = Profile werden direkt geschrieben
+status-source-mode = { $source }: gewechselt zu { $mode }
+status-paused = Sitzung gesperrt oder im Ruhezustand: automatische Regelung pausiert
+status-resumed = Sitzung aktiv: automatische Regelung fortgesetzt
+status-sleep-watch-failed = Ruhezustand wird erst nach dem Aufwachen erkannt ({ $error })
+status-resume-mode = Nach dem Ruhezustand: { $mode } erneut angewendet
+status-lid-mode = Deckel geschlossen: gewechselt zu { $mode }
+status-lid-restored = Deckel geöffnet: zurück zu { $mode }
+status-rule-mode = Regel { $rule }: gewechselt zu { $mode }
+status-app-mode = { $app } läuft: gewechselt zu { $mode }
+status-app-restored = Apps beendet: zurück zu { $mode }
+rules-dry-run = Probelauf: { $action }
+rules-label = Regeln
+rules-label-dry-run = Regeln (Probelauf)
+paused-label = Pausiert
+hint-paused = Die Sitzung ist gesperrt oder im Ruhezustand. Automatische Regelung, Regeln und Moduswechsel warten, bis sie wieder aktiv ist, damit sich keine Passwortabfragen stapeln.
+hint-rules = { $count } Regeln aktiv. Zuletzt:
+
+# Thermal control actions
+control-critical = KRITISCH: Lüfter-Boost + 30%
+control-high = HOCH: Lüfter-Boost + 50%
+control-adjusting = Anpassen auf { $pct }%
+control-increasing = Erhöhen auf { $pct }%
+control-on-target = Im Zielbereich
+control-limit-critical = KRITISCH: Lüfter-Boost + PL1 { $watts } W
+control-limit-high = HOCH: Lüfter-Boost + PL1 { $watts } W
+control-limit-adjusting = Senke PL1 auf { $watts } W
+control-limit-increasing = Erhöhe PL1 auf { $watts } W
+control-cgroup-critical = KRITISCH: Lüfter-Boost + Apps auf { $pct }% begrenzt ({ $count } Gruppen)
+control-cgroup-high = HOCH: Lüfter-Boost + Apps auf { $pct }% begrenzt ({ $count } Gruppen)
+control-cgroup-adjusting = Begrenze Apps auf { $pct }% ({ $count } Gruppen)
+control-cgroup-increasing = Erhöhe die App-Grenze auf { $pct }% ({ $count } Gruppen)
+actuator-performance = Leistung
+actuator-power-limit = Leistungsgrenze
+actuator-cgroups = Apps drosseln
+hint-actuator = Was die automatische Regelung verstellt: die Leistungsobergrenze, die dauerhafte Package-Leistungsgrenze (PL1) oder zuerst das CPU-Kontingent ausgewählter Apps
+throttle-placeholder = firefox, build.slice
+a11y-throttle = Zu drosselnde Apps
+hint-throttle = Apps oder Benutzer-Units zum Drosseln, durch Kommas getrennt, z. B. firefox oder tracker-miner-fs-3. Die Leistungsobergrenze sinkt erst, wenn sie auf 10% begrenzt sind.
+status-throttle-released = App-Drosselung aufgehoben
+status-throttle-refused = Nicht gedrosselt, würde eine ganze Sitzung oder das System treffen: { $groups }
+
+# Modes
+mode-performance = LEISTUNG
+mode-comfort = KOMFORT
+mode-balanced = AUSGEWOGEN
+mode-quiet = LEISE
+mode-auto = AUTO
+mode-unknown = UNBEKANNT
+mode-performance-desc = Videoanrufe
+mode-comfort-desc = Kühle Tastatur
+mode-balanced-desc = Allgemein
+mode-quiet-desc = Lautlos
+mode-auto-desc = Automatisch
+mode-unknown-desc = Unbekannt
+mode-description = { $limits } - { $purpose }
+mode-editor-new = Neuer Modus
+mode-editor-edit = Modus bearbeiten
+mode-field-name = Name
+mode-field-description = Beschreibung
+mode-field-color = Farbe
+mode-field-max-perf = Max. Leistung
+mode-field-profile = Plattformprofil
+mode-field-processes = Apps
+hint-mode-processes = Prozessnamen, durch Kommas getrennt. Der Modus gilt, solange einer davon läuft, danach kehrt der vorherige Modus zurück.
+mode-field-power-limit = Leistungsgrenze (PL1)
+mode-unchanged = Unverändert
+mode-save = Speichern
+mode-cancel = Abbrechen
+mode-edit = Bearbeiten
+mode-delete = Löschen
+mode-name-invalid = Der Name braucht mindestens einen Buchstaben oder eine Ziffer
+mode-name-taken = Ein Modus namens { $name } existiert bereits
+mode-drift = Geändert
+drift-item = { $path }: { $expected } erwartet, jetzt { $actual }
+
+# Thermal zones
+zone-cool = KÜHL
+zone-comfort = KOMFORT
+zone-optimal = OPTIMAL
+zone-warm = WARM
+zone-hot = HEISS
+zone-critical = KRITISCH
+
+# Themes
+theme-system = System
+theme-light = Hell
+theme-dark = Dunkel
+theme-high-contrast = Hoher Kontrast
+
+# Keyboard shortcuts
+shortcut-title = Tastenkürzel
+shortcut-modes = Modus wählen
+shortcut-fan = Lüfter-Boost umschalten
+shortcut-auto = Automatik umschalten
+shortcut-target = Ziel erhöhen / senken
+shortcut-export = Verlauf exportieren
+shortcut-focus = Fokus zwischen Bedienelementen bewegen
+shortcut-help = Diese Hilfe anzeigen
+
+# Screen reader descriptions
+unit-celsius = Grad Celsius
+unit-fahrenheit = Grad Fahrenheit
+unit-gigahertz = Gigahertz
+unit-megahertz = Megahertz
+a11y-quantity = { $value } { $unit }
+a11y-cpu-temp = CPU-Temperatur { $value }
+a11y-kbd-temp = Geschätzte Tastaturtemperatur { $value }
+a11y-zone = Temperaturzone { $zone }
+a11y-perf = Leistungsgrenze { $pct } Prozent
+a11y-freq = CPU-Frequenz { $value }
+a11y-mode = Aktueller Modus { $mode }
+a11y-mode-button = Modus { $mode }
+a11y-auto-control = Automatische Temperaturregelung
+a11y-actuator = Stellgröße der automatischen Regelung
+a11y-fan-boost = Lüfter-Boost
+a11y-turbo = Turbo
+a11y-min-perf = Mindestleistung { $pct } Prozent
+a11y-profile = Plattformprofil { $profile }
+
+# Command line
+cli-cpu = CPU
+cli-keyboard = Tastatur
+cli-ambient = Umgebung
+cli-frequency = Frequenz
+cli-performance = Leistung
+cli-driver = Treiber
+cli-mode = Modus
+cli-drift = Abweichung
+cli-profile = Profil
+cli-fan = Lüfter
+cli-fan-speed = Lüfterdrehzahl
+cli-power = Leistung
+cli-power-limits = Leistungsgrenzen
+cli-power-source = Stromquelle
+cli-battery = Akku
+cli-no-rules = Keine Regeln; in { $path } anlegen
+cli-no-power-limits = Keine RAPL-Leistungsgrenzen gefunden
+cli-epp = Energiepräferenz
+cli-turbo = Turbo
+cli-min-perf = Mindestleistung
+
+# Privileged writes
+write-cpus = CPUs { $cpus }
+write-summary = { $failed } von { $total } Schreibvorgängen fehlgeschlagen
+write-failed = { $path }: { $error }
+write-mismatch = { $path }: { $value } geschrieben, { $actual } zurückgelesen
+
+# Energy preferences
+epp-default = Standard
+epp-performance = Leistung
+epp-balance-performance = Ausgewogen (Leistung)
+epp-balance-power = Ausgewogen (Sparen)
+epp-power = Energiesparen
+
+# Platform profiles
+profile-low-power = Energiesparend
+profile-cool = Kühl
+profile-quiet = Leise
+profile-balanced = Ausgewogen
+profile-balanced-performance = Ausgewogen (Leistung)
+profile-performance = Leistung
+profile-max-power = Maximale Leistung
+profile-custom = Benutzerdefiniert
+
+# Other power managers
+conflict-active = { $manager } läuft und kann { $knobs } überschreiben
+conflict-enabled = { $manager } startet beim Booten und kann { $knobs } überschreiben
+conflict-installed = { $manager } ist installiert und kann { $knobs } überschreiben
+conflict-knobs-ppd = Plattformprofil und Energiepräferenz
+conflict-knobs-tlp = Leistungsgrenzen, Energiepräferenz, Turbo und Plattformprofil
+conflict-knobs-thermald = Leistungsgrenzen
+conflict-knobs-auto-cpufreq = Energiepräferenz und Turbo
+conflict-ppd-cooperate = power-profiles-daemon verwenden
+conflict-ppd-cooperating = Profile werden bei power-profiles-daemon angefordert
+conflict-ppd-stop = Direkt schreiben
+conflict-dismiss = Ausblenden
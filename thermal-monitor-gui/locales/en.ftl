@@ -0,0 +1,311 @@
+# Thermal Monitor - English (reference catalog)
+
+app-title = Thermal Monitor
+app-description = Monitor and control CPU temperature
+app-version = Thermal Monitor v{ $version }
+
+# Section headings
+section-temperatures = Temperatures
+section-performance = Performance
+section-mode-control = Mode Control
+section-target-temp = Target Temp
+section-fan = Fan
+section-history = History
+section-heat = Top Processes
+section-tuning = CPU Tuning
+
+# Reading labels
+label-cpu = CPU
+label-kbd = KBD
+label-zone = Zone
+label-perf = Perf
+label-freq = Freq
+label-power = Power
+label-battery = Battery
+battery-charging = Charging
+battery-discharging = Discharging
+battery-full = Full
+battery-not-charging = Not charging
+battery-left = { $time } left
+battery-until-full = { $time } until full
+source-ac = On AC
+source-battery = On battery
+label-source-mode = { $source }:
+hint-source-mode = Mode to switch to when the charger is plugged in or unplugged
+label-lid-mode = Lid closed:
+hint-lid-mode = Mode to switch to while the lid is closed; the previous mode returns when it is opened
+label-mode = Mode
+label-epp = Energy pref.
+label-turbo = Turbo
+label-min-perf = Min perf
+
+# Controls
+target-auto = AUTO
+target-off = OFF
+target-ok = OK
+fan-boost = BOOST
+fan-normal = NORMAL
+fan-hint = Max cooling
+fan-by-user = you
+fan-by-auto = auto control
+fan-by-mode = current mode
+fan-by-rule = a rule
+fan-curve-edit = Curve…
+fan-curve-title = Fan curve
+fan-curve-enabled = Control the fan with this curve
+fan-curve-hint = Drag points to move them, double-click to add one, right-click to remove one
+fan-curve-axis-temp = CPU ({ $unit })
+fan-curve-axis-duty = Fan (%)
+fan-curve-now = Now { $temp } → { $duty }%
+fan-curve-hysteresis = Hysteresis
+fan-rpm = { $rpm } RPM
+turbo-on = On
+turbo-off = Off
+
+# Tooltips
+hint-power-restricted = Energy counters are readable by root only on this system
+power-package = Package { $watts } W
+power-core = Core { $watts } W
+power-uncore = Uncore { $watts } W
+hint-power-limits = Limits: { $limits }
+hint-driver = Driver: { $driver }
+hint-temp-unit = Switch temperature unit
+hint-freq-unit = Switch frequency unit
+hint-epp = Energy Performance Preference hint for the CPU
+heat-show = Show
+heat-hide = Hide
+hint-heat = Processes that used the most CPU since the last update
+heat-measuring = Measuring…
+heat-process = Process
+heat-pid = PID
+heat-cpu = CPU
+heat-power = Power
+hint-heat-power = Estimate: the package power split by each process's share of CPU time
+process-renice = Lower priority
+process-stop = Pause
+process-continue = Resume
+hint-process-renice = Raise the nice value by 5 (now { $nice })
+hint-process-nice-max = Already at the lowest priority (nice { $nice })
+hint-process-stop = Stop the process with SIGSTOP until resumed
+hint-process-continue = Resume the process with SIGCONT
+hint-process-other-user = Belongs to another user
+status-process-reniced = Lowered the priority of { $name } ({ $pid })
+status-process-stopped = Paused { $name } ({ $pid })
+status-process-continued = Resumed { $name } ({ $pid })
+hint-turbo = Allow turbo boost frequencies
+hint-min-perf = Lowest performance the CPU may drop to
+hint-profile = Firmware power and fan policy
+hint-mode-new = Create a custom mode
+hint-mode-drift = Settings changed since { $mode } was applied. Click the mode to apply it again.
+hint-conflict = Turn it off with: { $command }
+hint-ppd-cooperate = Request profiles from power-profiles-daemon instead of writing platform profile and EPP directly
+hint-fan-curve = Set fan speed by temperature (needs thermal-fan-curve.service)
+hint-fan-requesters = Boost requested by: { $who }
+
+# History graph
+history-collecting = Collecting data...
+history-cpu = CPU
+history-kbd = Kbd
+history-target = Target
+history-fan = Fan
+history-power = Power (W)
+history-export = Export
+
+# Status messages
+status-mode-changed = Mode changed to { $mode }
+status-error = Error: { $error }
+status-auto-on = Auto ON
+status-auto-off = Auto OFF
+status-fan-boost = Fan boost
+status-fan-auto = Fan auto
+status-fan-held = Fan boost stays on for { $who }
+status-fan-curve-on = Fan curve active
+status-fan-curve-off = Fan curve off, firmware controls the fan
+status-exported = Exported to { $path }
+status-target = Target { $target }
+status-zone-changed = Thermal zone: { $zone }
+status-epp = Energy preference: { $value }
+status-turbo-on = Turbo enabled
+status-turbo-off = Turbo disabled
+status-min-perf = Minimum performance { $pct }%
+status-profile = Platform profile: { $profile }
+status-mode-saved = Mode { $mode } saved
+status-mode-deleted = Mode { $mode } deleted
+status-ppd-on = Using power-profiles-daemon for profiles
+status-ppd-off = Writing profiles directly
+status-source-mode = { $source }: switched to { $mode }
+status-paused = Session locked or asleep: automatic control paused
+status-resumed = Session active: automatic control resumed
+status-sleep-watch-failed = Suspend is only noticed after resume ({ $error })
+status-resume-mode = Resumed from sleep: applied { $mode } again
+status-lid-mode = Lid closed: switched to { $mode }
+status-lid-restored = Lid opened: back to { $mode }
+status-rule-mode = Rule { $rule }: switched to { $mode }
+status-app-mode = { $app } is running: switched to { $mode }
+status-app-restored = Apps closed: back to { $mode }
+rules-dry-run = Dry run: { $action }
+rules-label = Rules
+rules-label-dry-run = Rules (dry run)
+paused-label = Paused
+hint-paused = The session is locked or asleep. Automatic control, rules and mode switching wait until it is active again, so no password prompts pile up.
+hint-rules = { $count } rules active. Recent:
+
+# Thermal control actions
+control-critical = CRITICAL: Fan boost + 30%
+control-high = HIGH: Fan boost + 50%
+control-adjusting = Adjusting to { $pct }%
+control-increasing = Increasing to { $pct }%
+control-on-target = On target
+control-limit-critical = CRITICAL: Fan boost + PL1 { $watts } W
+control-limit-high = HIGH: Fan boost + PL1 { $watts } W
+control-limit-adjusting = Lowering PL1 to { $watts } W
+control-limit-increasing = Raising PL1 to { $watts } W
+control-cgroup-critical = CRITICAL: Fan boost + apps capped at { $pct }% ({ $count } groups)
+control-cgroup-high = HIGH: Fan boost + apps capped at { $pct }% ({ $count } groups)
+control-cgroup-adjusting = Capping apps at { $pct }% ({ $count } groups)
+control-cgroup-increasing = Raising the app cap to { $pct }% ({ $count } groups)
+actuator-performance = Performance
+actuator-power-limit = Power limit
+actuator-cgroups = App throttling
+hint-actuator = What automatic control adjusts: the performance ceiling, the sustained package power limit (PL1), or the CPU quota of selected apps first
+throttle-placeholder = firefox, build.slice
+a11y-throttle = Apps to throttle
+hint-throttle = Apps or user units to throttle, separated by commas, e.g. firefox or tracker-miner-fs-3. The performance ceiling is lowered only once they are capped at 10%.
+status-throttle-released = App throttling lifted
+status-throttle-refused = Not throttled, would cover a whole session or the system: { $groups }
+
+# Modes
+mode-performance = PERFORMANCE
+mode-comfort = COMFORT
+mode-balanced = BALANCED
+mode-quiet = QUIET
+mode-auto = AUTO
+mode-unknown = UNKNOWN
+mode-performance-desc = Video calls
+mode-comfort-desc = Cool keyboard
+mode-balanced-desc = General use
+mode-quiet-desc = Silent
+mode-auto-desc = Automatic
+mode-unknown-desc = Unknown
+mode-description = { $limits } - { $purpose }
+mode-editor-new = New mode
+mode-editor-edit = Edit mode
+mode-field-name = Name
+mode-field-description = Description
+mode-field-color = Color
+mode-field-max-perf = Max perf
+mode-field-profile = Platform profile
+mode-field-processes = Apps
+hint-mode-processes = Process names, separated by commas. The mode is used while any of them runs, then the previous mode returns.
+mode-field-power-limit = Power limit (PL1)
+mode-unchanged = Unchanged
+mode-save = Save
+mode-cancel = Cancel
+mode-edit = Edit
+mode-delete = Delete
+mode-name-invalid = The name needs at least one letter or digit
+mode-name-taken = A mode named { $name } already exists
+mode-drift = Modified
+drift-item = { $path }: { $expected } expected, now { $actual }
+
+# Thermal zones
+zone-cool = COOL
+zone-comfort = COMFORT
+zone-optimal = OPTIMAL
+zone-warm = WARM
+zone-hot = HOT
+zone-critical = CRITICAL
+
+# Themes
+theme-system = System
+theme-light = Light
+theme-dark = Dark
+theme-high-contrast = High contrast
+
+# Keyboard shortcuts
+shortcut-title = Keyboard shortcuts
+shortcut-modes = Select mode
+shortcut-fan = Toggle fan boost
+shortcut-auto = Toggle auto control
+shortcut-target = Raise / lower target
+shortcut-export = Export history
+shortcut-focus = Move focus between controls
+shortcut-help = Show this help
+
+# Screen reader descriptions
+unit-celsius = degrees Celsius
+unit-fahrenheit = degrees Fahrenheit
+unit-gigahertz = gigahertz
+unit-megahertz = megahertz
+a11y-quantity = { $value } { $unit }
+a11y-cpu-temp = CPU temperature { $value }
+a11y-kbd-temp = Estimated keyboard temperature { $value }
+a11y-zone = Thermal zone { $zone }
+a11y-perf = Performance limit { $pct } percent
+a11y-freq = CPU frequency { $value }
+a11y-mode = Current mode { $mode }
+a11y-mode-button = { $mode } mode
+a11y-auto-control = Automatic thermal control
+a11y-actuator = Automatic control actuator
+a11y-fan-boost = Fan boost
+a11y-turbo = Turbo boost
+a11y-min-perf = Minimum performance { $pct } percent
+a11y-profile = Platform profile { $profile }
+
+# Command line
+cli-cpu = CPU
+cli-keyboard = Keyboard
+cli-ambient = Ambient
+cli-frequency = Frequency
+cli-performance = Performance
+cli-driver = Driver
+cli-mode = Mode
+cli-drift = Drift
+cli-profile = Profile
+cli-fan = Fan
+cli-fan-speed = Fan speed
+cli-power = Power
+cli-power-limits = Power limits
+cli-power-source = Power source
+cli-battery = Battery
+cli-no-rules = No rules; add them to { $path }
+cli-no-power-limits = No RAPL power limits found
+cli-epp = Energy pref.
+cli-turbo = Turbo
+cli-min-perf = Min performance
+
+# Privileged writes
+write-cpus = CPUs { $cpus }
+write-summary = { $failed } of { $total } writes failed
+write-failed = { $path }: { $error }
+write-mismatch = { $path }: wrote { $value }, reads back { $actual }
+
+# Energy preferences
+epp-default = Default
+epp-performance = Performance
+epp-balance-performance = Balance performance
+epp-balance-power = Balance power
+epp-power = Power saving
+
+# Platform profiles
+profile-low-power = Low power
+profile-cool = Cool
+profile-quiet = Quiet
+profile-balanced = Balanced
+profile-balanced-performance = Balanced performance
+profile-performance = Performance
+profile-max-power = Maximum power
+profile-custom = Custom
+
+# Other power managers
+conflict-active = { $manager } is running and may override { $knobs }
+conflict-enabled = { $manager } is enabled at boot and may override { $knobs }
+conflict-installed = { $manager } is installed and may override { $knobs }
+conflict-knobs-ppd = platform profile and energy preference
+conflict-knobs-tlp = performance limits, energy preference, turbo and platform profile
+conflict-knobs-thermald = performance limits
+conflict-knobs-auto-cpufreq = energy preference and turbo
+conflict-ppd-cooperate = Use power-profiles-daemon
+conflict-ppd-cooperating = Profiles are requested from power-profiles-daemon
+conflict-ppd-stop = Write directly
+conflict-dismiss = Dismiss
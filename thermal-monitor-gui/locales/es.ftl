@@ -0,0 +1,311 @@
+# Thermal Monitor - Español
+
+app-title = Monitor Térmico
+app-description = Monitorea y controla temperatura CPU
+app-version = Monitor Térmico v{ $version }
+
+# Section headings
+section-temperatures = Temperaturas
+section-performance = Rendimiento
+section-mode-control = Control de modo
+section-target-temp = Temp. objetivo
+section-fan = Ventilador
+section-history = Historial
+section-heat = Procesos principales
+section-tuning = Ajuste de CPU
+
+# Reading labels
+label-cpu = CPU
+label-kbd = TECL
+label-zone = Zona
+label-perf = Rend
+label-freq = Frec
+label-power = Potencia
+label-battery = Batería
+battery-charging = Cargando
+battery-discharging = Descargando
+battery-full = Llena
+battery-not-charging = Sin cargar
+battery-left = quedan { $time }
+battery-until-full = { $time } hasta llenar
+source-ac = Con cargador
+source-battery = Con batería
+label-source-mode = { $source }:
+hint-source-mode = Modo al conectar o desconectar el cargador
+label-lid-mode = Tapa cerrada:
+hint-lid-mode = Modo al que cambiar mientras la tapa está cerrada; el modo anterior vuelve al abrirla
+label-mode = Modo
+label-epp = Pref. energía
+label-turbo = Turbo
+label-min-perf = Rend. mín
+
+# Controls
+target-auto = AUTO
+target-off = NO
+target-ok = OK
+fan-boost = MÁXIMO
+fan-normal = NORMAL
+fan-hint = Máxima refrigeración
+fan-by-user = usted
+fan-by-auto = control automático
+fan-by-mode = modo actual
+fan-by-rule = una regla
+fan-curve-edit = Curva…
+fan-curve-title = Curva del ventilador
+fan-curve-enabled = Controlar el ventilador con esta curva
+fan-curve-hint = Arrastre los puntos para moverlos, doble clic para añadir uno, clic derecho para quitarlo
+fan-curve-axis-temp = CPU ({ $unit })
+fan-curve-axis-duty = Ventilador (%)
+fan-curve-now = Ahora { $temp } → { $duty }%
+fan-curve-hysteresis = Histéresis
+fan-rpm = { $rpm } RPM
+turbo-on = Sí
+turbo-off = No
+
+# Tooltips
+hint-power-restricted = En este sistema solo root puede leer los contadores de energía
+power-package = Paquete { $watts } W
+power-core = Núcleos { $watts } W
+power-uncore = Uncore { $watts } W
+hint-power-limits = Límites: { $limits }
+hint-driver = Controlador: { $driver }
+hint-temp-unit = Cambiar unidad de temperatura
+hint-freq-unit = Cambiar unidad de frecuencia
+hint-epp = Preferencia de energía/rendimiento (EPP) para la CPU
+heat-show = Mostrar
+heat-hide = Ocultar
+hint-heat = Procesos que más CPU usaron desde la última actualización
+heat-measuring = Midiendo…
+heat-process = Proceso
+heat-pid = PID
+heat-cpu = CPU
+heat-power = Potencia
+hint-heat-power = Estimación: la potencia del paquete repartida según la parte de tiempo de CPU de cada proceso
+process-renice = Bajar prioridad
+process-stop = Pausar
+process-continue = Reanudar
+hint-process-renice = Sube el valor nice en 5 (ahora { $nice })
+hint-process-nice-max = Ya tiene la prioridad más baja (nice { $nice })
+hint-process-stop = Detiene el proceso con SIGSTOP hasta reanudarlo
+hint-process-continue = Reanuda el proceso con SIGCONT
+hint-process-other-user = Pertenece a otro usuario
+status-process-reniced = Prioridad de { $name } ({ $pid }) reducida
+status-process-stopped = { $name } ({ $pid }) en pausa
+status-process-continued = { $name } ({ $pid }) reanudado
+hint-turbo = Permitir frecuencias turbo
+hint-min-perf = Rendimiento mínimo al que puede bajar la CPU
+hint-profile = Política de energía y ventilador del firmware
+hint-mode-new = Crear un modo personalizado
+hint-mode-drift = Los ajustes cambiaron desde que se aplicó { $mode }. Pulse el modo para aplicarlo de nuevo.
+hint-conflict = Desactívelo con: { $command }
+hint-ppd-cooperate = Pedir los perfiles a power-profiles-daemon en lugar de escribir el perfil de plataforma y EPP directamente
+hint-fan-curve = Velocidad del ventilador según la temperatura (requiere thermal-fan-curve.service)
+hint-fan-requesters = Máximo solicitado por: { $who }
+
+# History graph
+history-collecting = Recopilando datos...
+history-cpu = CPU
+history-kbd = Tecl
+history-target = Objetivo
+history-fan = Ventilador
+history-power = Potencia (W)
+history-export = Exportar
+
+# Status messages
+status-mode-changed = Modo cambiado a { $mode }
+status-error = Error: { $error }
+status-auto-on = Auto activado
+status-auto-off = Auto desactivado
+status-fan-boost = Ventilador al máximo
+status-fan-auto = Ventilador automático
+status-fan-held = El ventilador sigue al máximo por { $who }
+status-fan-curve-on = Curva del ventilador activa
+status-fan-curve-off = Curva desactivada, el firmware controla el ventilador
+status-exported = Exportado a { $path }
+status-target = Objetivo { $target }
+status-zone-changed = Zona térmica: { $zone }
+status-epp = Preferencia de energía: { $value }
+status-turbo-on = Turbo activado
+status-turbo-off = Turbo desactivado
+status-min-perf = Rendimiento mínimo { $pct }%
+status-profile = Perfil de plataforma: { $profile }
+status-mode-saved = Modo { $mode } guardado
+status-mode-deleted = Modo { $mode } eliminado
+status-ppd-on = Usando power-profiles-daemon para los perfiles
+status-ppd-off = Escribiendo los perfiles directamente
+status-source-mode = { $source }: cambiado a { $mode }
+status-paused = Sesión bloqueada o en suspensión: control automático en pausa
+status-resumed = Sesión activa: control automático reanudado
+status-sleep-watch-failed = La suspensión solo se detecta tras reanudar ({ $error })
+status-resume-mode = Tras la suspensión: { $mode } aplicado de nuevo
+status-lid-mode = Tapa cerrada: cambiado a { $mode }
+status-lid-restored = Tapa abierta: de vuelta a { $mode }
+status-rule-mode = Regla { $rule }: cambiado a { $mode }
+status-app-mode = { $app } en ejecución: cambiado a { $mode }
+status-app-restored = Aplicaciones cerradas: de vuelta a { $mode }
+rules-dry-run = Simulación: { $action }
+rules-label = Reglas
+rules-label-dry-run = Reglas (simulación)
+paused-label = En pausa
+hint-paused = La sesión está bloqueada o en suspensión. El control automático, las reglas y los cambios de modo esperan a que vuelva a estar activa, para no acumular peticiones de contraseña.
+hint-rules = { $count } reglas activas. Recientes:
+
+# Thermal control actions
+control-critical = CRÍTICO: Ventilador máximo + 30%
+control-high = ALTO: Ventilador máximo + 50%
+control-adjusting = Ajustando a { $pct }%
+control-increasing = Aumentando a { $pct }%
+control-on-target = En objetivo
+control-limit-critical = CRÍTICO: Ventilador al máximo + PL1 { $watts } W
+control-limit-high = ALTO: Ventilador al máximo + PL1 { $watts } W
+control-limit-adjusting = Bajando PL1 a { $watts } W
+control-limit-increasing = Subiendo PL1 a { $watts } W
+control-cgroup-critical = CRÍTICO: Ventilador máximo + aplicaciones limitadas al { $pct }% ({ $count } grupos)
+control-cgroup-high = ALTO: Ventilador máximo + aplicaciones limitadas al { $pct }% ({ $count } grupos)
+control-cgroup-adjusting = Limitando aplicaciones al { $pct }% ({ $count } grupos)
+control-cgroup-increasing = Subiendo el límite de aplicaciones al { $pct }% ({ $count } grupos)
+actuator-performance = Rendimiento
+actuator-power-limit = Límite de potencia
+actuator-cgroups = Limitar aplicaciones
+hint-actuator = Qué ajusta el control automático: el techo de rendimiento, el límite de potencia sostenida del paquete (PL1) o primero la cuota de CPU de aplicaciones elegidas
+throttle-placeholder = firefox, build.slice
+a11y-throttle = Aplicaciones a limitar
+hint-throttle = Aplicaciones o unidades de usuario a limitar, separadas por comas, p. ej. firefox o tracker-miner-fs-3. El techo de rendimiento solo baja cuando ya están limitadas al 10%.
+status-throttle-released = Límite de aplicaciones retirado
+status-throttle-refused = No se limita, abarcaría toda la sesión o el sistema: { $groups }
+
+# Modes
+mode-performance = RENDIMIENTO
+mode-comfort = CONFORT
+mode-balanced = EQUILIBRADO
+mode-quiet = SILENCIO
+mode-auto = AUTO
+mode-unknown = DESCONOCIDO
+mode-performance-desc = Videollamadas
+mode-comfort-desc = Teclado fresco
+mode-balanced-desc = Uso general
+mode-quiet-desc = Silencioso
+mode-auto-desc = Automático
+mode-unknown-desc = Desconocido
+mode-description = { $limits } - { $purpose }
+mode-editor-new = Nuevo modo
+mode-editor-edit = Editar modo
+mode-field-name = Nombre
+mode-field-description = Descripción
+mode-field-color = Color
+mode-field-max-perf = Rend. máx.
+mode-field-profile = Perfil de plataforma
+mode-field-processes = Aplicaciones
+hint-mode-processes = Nombres de procesos separados por comas. El modo se usa mientras alguno se ejecuta y luego vuelve el modo anterior.
+mode-field-power-limit = Límite de potencia (PL1)
+mode-unchanged = Sin cambios
+mode-save = Guardar
+mode-cancel = Cancelar
+mode-edit = Editar
+mode-delete = Eliminar
+mode-name-invalid = El nombre necesita al menos una letra o un dígito
+mode-name-taken = Ya existe un modo llamado { $name }
+mode-drift = Modificado
+drift-item = { $path }: se esperaba { $expected }, ahora { $actual }
+
+# Thermal zones
+zone-cool = FRÍO
+zone-comfort = CONFORT
+zone-optimal = ÓPTIMO
+zone-warm = TEMPLADO
+zone-hot = CALIENTE
+zone-critical = CRÍTICO
+
+# Themes
+theme-system = Sistema
+theme-light = Claro
+theme-dark = Oscuro
+theme-high-contrast = Alto contraste
+
+# Keyboard shortcuts
+shortcut-title = Atajos de teclado
+shortcut-modes = Seleccionar modo
+shortcut-fan = Alternar ventilador al máximo
+shortcut-auto = Alternar control automático
+shortcut-target = Subir / bajar objetivo
+shortcut-export = Exportar historial
+shortcut-focus = Mover el foco entre controles
+shortcut-help = Mostrar esta ayuda
+
+# Screen reader descriptions
+unit-celsius = grados Celsius
+unit-fahrenheit = grados Fahrenheit
+unit-gigahertz = gigahercios
+unit-megahertz = megahercios
+a11y-quantity = { $value } { $unit }
+a11y-cpu-temp = Temperatura de CPU { $value }
+a11y-kbd-temp = Temperatura estimada del teclado { $value }
+a11y-zone = Zona térmica { $zone }
+a11y-perf = Límite de rendimiento { $pct } por ciento
+a11y-freq = Frecuencia de CPU { $value }
+a11y-mode = Modo actual { $mode }
+a11y-mode-button = Modo { $mode }
+a11y-auto-control = Control térmico automático
+a11y-actuator = Actuador del control automático
+a11y-fan-boost = Ventilador al máximo
+a11y-turbo = Turbo
+a11y-min-perf = Rendimiento mínimo { $pct } por ciento
+a11y-profile = Perfil de plataforma { $profile }
+
+# Command line
+cli-cpu = CPU
+cli-keyboard = Teclado
+cli-ambient = Ambiente
+cli-frequency = Frecuencia
+cli-performance = Rendimiento
+cli-driver = Controlador
+cli-mode = Modo
+cli-drift = Desvío
+cli-profile = Perfil
+cli-fan = Ventilador
+cli-fan-speed = Vel. ventilador
+cli-power = Potencia
+cli-power-limits = Límites de potencia
+cli-power-source = Alimentación
+cli-battery = Batería
+cli-no-rules = No hay reglas; agréguelas en { $path }
+cli-no-power-limits = No se encontraron límites de potencia RAPL
+cli-epp = Pref. energía
+cli-turbo = Turbo
+cli-min-perf = Rendimiento mín.
+
+# Privileged writes
+write-cpus = CPU { $cpus }
+write-summary = fallaron { $failed } de { $total } escrituras
+write-failed = { $path }: { $error }
+write-mismatch = { $path }: se escribió { $value }, se lee { $actual }
+
+# Energy preferences
+epp-default = Predeterminado
+epp-performance = Rendimiento
+epp-balance-performance = Equilibrio rendimiento
+epp-balance-power = Equilibrio ahorro
+epp-power = Ahorro de energía
+
+# Platform profiles
+profile-low-power = Bajo consumo
+profile-cool = Fresco
+profile-quiet = Silencioso
+profile-balanced = Equilibrado
+profile-balanced-performance = Equilibrado rendimiento
+profile-performance = Rendimiento
+profile-max-power = Potencia máxima
+profile-custom = Personalizado
+
+# Other power managers
+conflict-active = { $manager } está en ejecución y puede sobrescribir { $knobs }
+conflict-enabled = { $manager } se inicia con el sistema y puede sobrescribir { $knobs }
+conflict-installed = { $manager } está instalado y puede sobrescribir { $knobs }
+conflict-knobs-ppd = el perfil de plataforma y la preferencia energética
+conflict-knobs-tlp = los límites de rendimiento, la preferencia energética, el turbo y el perfil de plataforma
+conflict-knobs-thermald = los límites de rendimiento
+conflict-knobs-auto-cpufreq = la preferencia energética y el turbo
+conflict-ppd-cooperate = Usar power-profiles-daemon
+conflict-ppd-cooperating = Los perfiles se piden a power-profiles-daemon
+conflict-ppd-stop = Escribir directamente
+conflict-dismiss = Descartar
@@ -0,0 +1,311 @@
+# Thermal Monitor - Português
+
+app-title = Monitor Térmico
+app-description = Monitore e controle temperatura CPU
+app-version = Monitor Térmico v{ $version }
+
+# Section headings
+section-temperatures = Temperaturas
+section-performance = Desempenho
+section-mode-control = Controle de modo
+section-target-temp = Temp. alvo
+section-fan = Ventoinha
+section-history = Histórico
+section-heat = Principais processos
+section-tuning = Ajuste da CPU
+
+# Reading labels
+label-cpu = CPU
+label-kbd = TECL
+label-zone = Zona
+label-perf = Desemp
+label-freq = Freq
+label-power = Potência
+label-battery = Bateria
+battery-charging = Carregando
+battery-discharging = Descarregando
+battery-full = Cheia
+battery-not-charging = Sem carregar
+battery-left = restam { $time }
+battery-until-full = { $time } até encher
+source-ac = Na tomada
+source-battery = Na bateria
+label-source-mode = { $source }:
+hint-source-mode = Modo ao conectar ou desconectar o carregador
+label-lid-mode = Tampa fechada:
+hint-lid-mode = Modo para usar enquanto a tampa estiver fechada; o modo anterior volta ao abri-la
+label-mode = Modo
+label-epp = Pref. energia
+label-turbo = Turbo
+label-min-perf = Desemp. mín
+
+# Controls
+target-auto = AUTO
+target-off = DESL
+target-ok = OK
+fan-boost = MÁXIMO
+fan-normal = NORMAL
+fan-hint = Resfriamento máximo
+fan-by-user = você
+fan-by-auto = controle automático
+fan-by-mode = modo atual
+fan-by-rule = uma regra
+fan-curve-edit = Curva…
+fan-curve-title = Curva da ventoinha
+fan-curve-enabled = Controlar a ventoinha com esta curva
+fan-curve-hint = Arraste os pontos para movê-los, clique duplo para adicionar, clique direito para remover
+fan-curve-axis-temp = CPU ({ $unit })
+fan-curve-axis-duty = Ventoinha (%)
+fan-curve-now = Agora { $temp } → { $duty }%
+fan-curve-hysteresis = Histerese
+fan-rpm = { $rpm } RPM
+turbo-on = Sim
+turbo-off = Não
+
+# Tooltips
+hint-power-restricted = Neste sistema só o root pode ler os contadores de energia
+power-package = Pacote { $watts } W
+power-core = Núcleos { $watts } W
+power-uncore = Uncore { $watts } W
+hint-power-limits = Limites: { $limits }
+hint-driver = Driver: { $driver }
+hint-temp-unit = Alternar unidade de temperatura
+hint-freq-unit = Alternar unidade de frequência
+hint-epp = Preferência de energia/desempenho (EPP) para a CPU
+heat-show = Mostrar
+heat-hide = Ocultar
+hint-heat = Processos que mais usaram CPU desde a última atualização
+heat-measuring = Medindo…
+heat-process = Processo
+heat-pid = PID
+heat-cpu = CPU
+heat-power = Potência
+hint-heat-power = Estimativa: a potência do pacote dividida pela parcela de tempo de CPU de cada processo
+process-renice = Baixar prioridade
+process-stop = Pausar
+process-continue = Retomar
+hint-process-renice = Aumenta o valor nice em 5 (agora { $nice })
+hint-process-nice-max = Já está na prioridade mais baixa (nice { $nice })
+hint-process-stop = Para o processo com SIGSTOP até ser retomado
+hint-process-continue = Retoma o processo com SIGCONT
+hint-process-other-user = Pertence a outro usuário
+status-process-reniced = Prioridade de { $name } ({ $pid }) reduzida
+status-process-stopped = { $name } ({ $pid }) pausado
+status-process-continued = { $name } ({ $pid }) retomado
+hint-turbo = Permitir frequências turbo
+hint-min-perf = Desempenho mínimo a que a CPU pode descer
+hint-profile = Política de energia e ventoinha do firmware
+hint-mode-new = Criar um modo personalizado
+hint-mode-drift = As configurações mudaram desde que { $mode } foi aplicado. Clique no modo para aplicá-lo novamente.
+hint-conflict = Desative com: { $command }
+hint-ppd-cooperate = Solicitar perfis ao power-profiles-daemon em vez de escrever o perfil de plataforma e o EPP diretamente
+hint-fan-curve = Velocidade da ventoinha conforme a temperatura (requer thermal-fan-curve.service)
+hint-fan-requesters = Máximo solicitado por: { $who }
+
+# History graph
+history-collecting = Coletando dados...
+history-cpu = CPU
+history-kbd = Tecl
+history-target = Alvo
+history-fan = Ventoinha
+history-power = Potência (W)
+history-export = Exportar
+
+# Status messages
+status-mode-changed = Modo alterado para { $mode }
+status-error = Erro: { $error }
+status-auto-on = Auto ligado
+status-auto-off = Auto desligado
+status-fan-boost = Ventoinha no máximo
+status-fan-auto = Ventoinha automática
+status-fan-held = A ventoinha continua no máximo por { $who }
+status-fan-curve-on = Curva da ventoinha ativa
+status-fan-curve-off = Curva desativada, o firmware controla a ventoinha
+status-exported = Exportado para { $path }
+status-target = Alvo { $target }
+status-zone-changed = Zona térmica: { $zone }
+status-epp = Preferência de energia: { $value }
+status-turbo-on = Turbo ativado
+status-turbo-off = Turbo desativado
+status-min-perf = Desempenho mínimo { $pct }%
+status-profile = Perfil da plataforma: { $profile }
+status-mode-saved = Modo { $mode } salvo
+status-mode-deleted = Modo { $mode } excluído
+status-ppd-on = Usando power-profiles-daemon para os perfis
+status-ppd-off = Gravando os perfis diretamente
+status-source-mode = { $source }: alterado para { $mode }
+status-paused = Sessão bloqueada ou em suspensão: controle automático pausado
+status-resumed = Sessão ativa: controle automático retomado
+status-sleep-watch-failed = A suspensão só é detectada após retomar ({ $error })
+status-resume-mode = Após a suspensão: { $mode } aplicado novamente
+status-lid-mode = Tampa fechada: alterado para { $mode }
+status-lid-restored = Tampa aberta: de volta a { $mode }
+status-rule-mode = Regra { $rule }: alterado para { $mode }
+status-app-mode = { $app } em execução: alterado para { $mode }
+status-app-restored = Aplicativos fechados: de volta a { $mode }
+rules-dry-run = Simulação: { $action }
+rules-label = Regras
+rules-label-dry-run = Regras (simulação)
+paused-label = Pausado
+hint-paused = A sessão está bloqueada ou em suspensão. O controle automático, as regras e as trocas de modo aguardam até ela ficar ativa de novo, para não acumular pedidos de senha.
+hint-rules = { $count } regras ativas. Recentes:
+
+# Thermal control actions
+control-critical = CRÍTICO: Ventoinha máxima + 30%
+control-high = ALTO: Ventoinha máxima + 50%
+control-adjusting = Ajustando para { $pct }%
+control-increasing = Aumentando para { $pct }%
+control-on-target = No alvo
+control-limit-critical = CRÍTICO: Ventoinha no máximo + PL1 { $watts } W
+control-limit-high = ALTO: Ventoinha no máximo + PL1 { $watts } W
+control-limit-adjusting = Reduzindo PL1 para { $watts } W
+control-limit-increasing = Aumentando PL1 para { $watts } W
+control-cgroup-critical = CRÍTICO: Ventoinha máxima + aplicativos limitados a { $pct }% ({ $count } grupos)
+control-cgroup-high = ALTO: Ventoinha máxima + aplicativos limitados a { $pct }% ({ $count } grupos)
+control-cgroup-adjusting = Limitando aplicativos a { $pct }% ({ $count } grupos)
+control-cgroup-increasing = Aumentando o limite dos aplicativos para { $pct }% ({ $count } grupos)
+actuator-performance = Desempenho
+actuator-power-limit = Limite de potência
+actuator-cgroups = Limitar aplicativos
+hint-actuator = O que o controle automático ajusta: o teto de desempenho, o limite de potência sustentada do pacote (PL1) ou primeiro a cota de CPU de aplicativos escolhidos
+throttle-placeholder = firefox, build.slice
+a11y-throttle = Aplicativos a limitar
+hint-throttle = Aplicativos ou unidades de usuário a limitar, separados por vírgulas, p. ex. firefox ou tracker-miner-fs-3. O teto de desempenho só baixa quando já estiverem limitados a 10%.
+status-throttle-released = Limite dos aplicativos removido
+status-throttle-refused = Não limitado, abrangeria a sessão inteira ou o sistema: { $groups }
+
+# Modes
+mode-performance = DESEMPENHO
+mode-comfort = CONFORTO
+mode-balanced = EQUILIBRADO
+mode-quiet = SILENCIOSO
+mode-auto = AUTO
+mode-unknown = DESCONHECIDO
+mode-performance-desc = Videochamadas
+mode-comfort-desc = Teclado fresco
+mode-balanced-desc = Uso geral
+mode-quiet-desc = Silencioso
+mode-auto-desc = Automático
+mode-unknown-desc = Desconhecido
+mode-description = { $limits } - { $purpose }
+mode-editor-new = Novo modo
+mode-editor-edit = Editar modo
+mode-field-name = Nome
+mode-field-description = Descrição
+mode-field-color = Cor
+mode-field-max-perf = Desemp. máx.
+mode-field-profile = Perfil de plataforma
+mode-field-processes = Aplicativos
+hint-mode-processes = Nomes de processos separados por vírgulas. O modo é usado enquanto algum estiver em execução e depois o modo anterior volta.
+mode-field-power-limit = Limite de potência (PL1)
+mode-unchanged = Sem alteração
+mode-save = Salvar
+mode-cancel = Cancelar
+mode-edit = Editar
+mode-delete = Excluir
+mode-name-invalid = O nome precisa de pelo menos uma letra ou dígito
+mode-name-taken = Já existe um modo chamado { $name }
+mode-drift = Modificado
+drift-item = { $path }: esperado { $expected }, agora { $actual }
+
+# Thermal zones
+zone-cool = FRIO
+zone-comfort = CONFORTO
+zone-optimal = IDEAL
+zone-warm = MORNO
+zone-hot = QUENTE
+zone-critical = CRÍTICO
+
+# Themes
+theme-system = Sistema
+theme-light = Claro
+theme-dark = Escuro
+theme-high-contrast = Alto contraste
+
+# Keyboard shortcuts
+shortcut-title = Atalhos de teclado
+shortcut-modes = Selecionar modo
+shortcut-fan = Alternar ventoinha no máximo
+shortcut-auto = Alternar controle automático
+shortcut-target = Subir / baixar alvo
+shortcut-export = Exportar histórico
+shortcut-focus = Mover o foco entre controles
+shortcut-help = Mostrar esta ajuda
+
+# Screen reader descriptions
+unit-celsius = graus Celsius
+unit-fahrenheit = graus Fahrenheit
+unit-gigahertz = gigahertz
+unit-megahertz = megahertz
+a11y-quantity = { $value } { $unit }
+a11y-cpu-temp = Temperatura da CPU { $value }
+a11y-kbd-temp = Temperatura estimada do teclado { $value }
+a11y-zone = Zona térmica { $zone }
+a11y-perf = Limite de desempenho { $pct } por cento
+a11y-freq = Frequência da CPU { $value }
+a11y-mode = Modo atual { $mode }
+a11y-mode-button = Modo { $mode }
+a11y-auto-control = Controle térmico automático
+a11y-actuator = Atuador do controle automático
+a11y-fan-boost = Ventoinha no máximo
+a11y-turbo = Turbo
+a11y-min-perf = Desempenho mínimo { $pct } por cento
+a11y-profile = Perfil da plataforma { $profile }
+
+# Command line
+cli-cpu = CPU
+cli-keyboard = Teclado
+cli-ambient = Ambiente
+cli-frequency = Frequência
+cli-performance = Desempenho
+cli-driver = Driver
+cli-mode = Modo
+cli-drift = Desvio
+cli-profile = Perfil
+cli-fan = Ventoinha
+cli-fan-speed = Vel. ventoinha
+cli-power = Potência
+cli-power-limits = Limites de potência
+cli-power-source = Alimentação
+cli-battery = Bateria
+cli-no-rules = Nenhuma regra; adicione-as em { $path }
+cli-no-power-limits = Nenhum limite de potência RAPL encontrado
+cli-epp = Pref. energia
+cli-turbo = Turbo
+cli-min-perf = Desempenho mín.
+
+# Privileged writes
+write-cpus = CPUs { $cpus }
+write-summary = falharam { $failed } de { $total } gravações
+write-failed = { $path }: { $error }
+write-mismatch = { $path }: gravado { $value }, lido { $actual }
+
+# Energy preferences
+epp-default = Padrão
+epp-performance = Desempenho
+epp-balance-performance = Equilíbrio desempenho
+epp-balance-power = Equilíbrio economia
+epp-power = Economia de energia
+
+# Platform profiles
+profile-low-power = Baixo consumo
+profile-cool = Fresco
+profile-quiet = Silencioso
+profile-balanced = Equilibrado
+profile-balanced-performance = Equilibrado desempenho
+profile-performance = Desempenho
+profile-max-power = Potência máxima
+profile-custom = Personalizado
+
+# Other power managers
+conflict-active = { $manager } está em execução e pode sobrescrever { $knobs }
+conflict-enabled = { $manager } inicia com o sistema e pode sobrescrever { $knobs }
+conflict-installed = { $manager } está instalado e pode sobrescrever { $knobs }
+conflict-knobs-ppd = o perfil de plataforma e a preferência de energia
+conflict-knobs-tlp = os limites de desempenho, a preferência de energia, o turbo e o perfil de plataforma
+conflict-knobs-thermald = os limites de desempenho
+conflict-knobs-auto-cpufreq = a preferência de energia e o turbo
+conflict-ppd-cooperate = Usar power-profiles-daemon
+conflict-ppd-cooperating = Os perfis são solicitados ao power-profiles-daemon
+conflict-ppd-stop = Gravar diretamente
+conflict-dismiss = Dispensar
@@ -0,0 +1,311 @@
+# Thermal Monitor - 中文
+
+app-title = 温度监控
+app-description = 监控和控制CPU温度
+app-version = 温度监控 v{ $version }
+
+# Section headings
+section-temperatures = 温度
+section-performance = 性能
+section-mode-control = 模式控制
+section-target-temp = 目标温度
+section-fan = 风扇
+section-history = 历史
+section-heat = 主要进程
+section-tuning = CPU 调节
+
+# Reading labels
+label-cpu = CPU
+label-kbd = 键盘
+label-zone = 区间
+label-perf = 性能
+label-freq = 频率
+label-power = 功耗
+label-battery = 电池
+battery-charging = 充电中
+battery-discharging = 放电中
+battery-full = 已充满
+battery-not-charging = 未充电
+battery-left = 剩余 { $time }
+battery-until-full = { $time } 后充满
+source-ac = 使用电源
+source-battery = 使用电池
+label-source-mode = { $source }：
+hint-source-mode = 插入或拔出电源时切换到的模式
+label-lid-mode = 合盖时：
+hint-lid-mode = 合上盖子时切换到的模式；打开后恢复之前的模式
+label-mode = 模式
+label-epp = 能耗偏好
+label-turbo = 睿频
+label-min-perf = 最低性能
+
+# Controls
+target-auto = 自动
+target-off = 关闭
+target-ok = 正常
+fan-boost = 强劲
+fan-normal = 普通
+fan-hint = 最大散热
+fan-by-user = 您
+fan-by-auto = 自动控制
+fan-by-mode = 当前模式
+fan-by-rule = 规则
+fan-curve-edit = 曲线…
+fan-curve-title = 风扇曲线
+fan-curve-enabled = 使用此曲线控制风扇
+fan-curve-hint = 拖动点以移动，双击添加，右键删除
+fan-curve-axis-temp = CPU（{ $unit }）
+fan-curve-axis-duty = 风扇（%）
+fan-curve-now = 当前 { $temp } → { $duty }%
+fan-curve-hysteresis = 回差
+fan-rpm = { $rpm } 转/分
+turbo-on = 开
+turbo-off = 关
+
+# Tooltips
+hint-power-restricted = 此系统上只有 root 能读取能耗计数器
+power-package = 封装 { $watts } W
+power-core = 核心 { $watts } W
+power-uncore = 非核心 { $watts } W
+hint-power-limits = 限制：{ $limits }
+hint-driver = 驱动：{ $driver }
+hint-temp-unit = 切换温度单位
+hint-freq-unit = 切换频率单位
+hint-epp = CPU 的能耗/性能偏好 (EPP)
+heat-show = 显示
+heat-hide = 隐藏
+hint-heat = 自上次更新以来占用 CPU 最多的进程
+heat-measuring = 正在测量…
+heat-process = 进程
+heat-pid = PID
+heat-cpu = CPU
+heat-power = 功率
+hint-heat-power = 估算值：按各进程所占 CPU 时间比例分摊的封装功率
+process-renice = 降低优先级
+process-stop = 暂停
+process-continue = 继续
+hint-process-renice = 将 nice 值提高 5（当前 { $nice }）
+hint-process-nice-max = 已是最低优先级（nice { $nice }）
+hint-process-stop = 使用 SIGSTOP 暂停进程，直到继续
+hint-process-continue = 使用 SIGCONT 继续进程
+hint-process-other-user = 属于其他用户
+status-process-reniced = 已降低 { $name }（{ $pid }）的优先级
+status-process-stopped = 已暂停 { $name }（{ $pid }）
+status-process-continued = 已继续 { $name }（{ $pid }）
+hint-turbo = 允许睿频频率
+hint-min-perf = CPU 可降到的最低性能
+hint-profile = 固件的电源与风扇策略
+hint-mode-new = 创建自定义模式
+hint-mode-drift = 应用 { $mode } 后设置已被更改。点击该模式重新应用。
+hint-conflict = 关闭命令：{ $command }
+hint-ppd-cooperate = 通过 power-profiles-daemon 请求配置，而不是直接写入平台配置和 EPP
+hint-fan-curve = 按温度设定风扇转速（需要 thermal-fan-curve.service）
+hint-fan-requesters = 请求强劲模式：{ $who }
+
+# History graph
+history-collecting = 正在收集数据...
+history-cpu = CPU
+history-kbd = 键盘
+history-target = 目标
+history-fan = 风扇
+history-power = 功耗（W）
+history-export = 导出
+
+# Status messages
+status-mode-changed = 模式已切换为 { $mode }
+status-error = 错误：{ $error }
+status-auto-on = 自动已开启
+status-auto-off = 自动已关闭
+status-fan-boost = 风扇强劲模式
+status-fan-auto = 风扇自动模式
+status-fan-held = 风扇强劲模式因{ $who }保持开启
+status-fan-curve-on = 风扇曲线已启用
+status-fan-curve-off = 风扇曲线已关闭，由固件控制风扇
+status-exported = 已导出到 { $path }
+status-target = 目标 { $target }
+status-zone-changed = 温度区间：{ $zone }
+status-epp = 能耗偏好：{ $value }
+status-turbo-on = 睿频已启用
+status-turbo-off = 睿频已禁用
+status-min-perf = 最低性能 { $pct }%
+status-profile = 平台配置文件：{ $profile }
+status-mode-saved = 模式 { $mode } 已保存
+status-mode-deleted = 模式 { $mode } 已删除
+status-ppd-on = 通过 power-profiles-daemon 设置配置
+status-ppd-off = 直接写入配置
+status-source-mode = { $source }：已切换到 { $mode }
+status-paused = 会话已锁定或休眠：自动控制已暂停
+status-resumed = 会话已激活：自动控制已恢复
+status-sleep-watch-failed = 仅能在唤醒后察觉挂起（{ $error }）
+status-resume-mode = 从休眠恢复：已重新应用 { $mode }
+status-lid-mode = 已合盖：已切换到 { $mode }
+status-lid-restored = 已开盖：已恢复为 { $mode }
+status-rule-mode = 规则 { $rule }：已切换到 { $mode }
+status-app-mode = { $app } 正在运行：已切换到 { $mode }
+status-app-restored = 应用已关闭：已恢复为 { $mode }
+rules-dry-run = 试运行：{ $action }
+rules-label = 规则
+rules-label-dry-run = 规则（试运行）
+paused-label = 已暂停
+hint-paused = 会话已锁定或处于休眠状态。自动控制、规则和模式切换会等到会话重新激活，以免堆积密码提示。
+hint-rules = { $count } 条规则生效。最近：
+
+# Thermal control actions
+control-critical = 危急：风扇强劲 + 30%
+control-high = 偏高：风扇强劲 + 50%
+control-adjusting = 调整至 { $pct }%
+control-increasing = 提升至 { $pct }%
+control-on-target = 已达目标
+control-limit-critical = 严重：风扇加速 + PL1 { $watts } W
+control-limit-high = 偏高：风扇加速 + PL1 { $watts } W
+control-limit-adjusting = 将 PL1 降至 { $watts } W
+control-limit-increasing = 将 PL1 升至 { $watts } W
+control-cgroup-critical = 危急：风扇强劲 + 应用限制为 { $pct }%（{ $count } 个组）
+control-cgroup-high = 高：风扇强劲 + 应用限制为 { $pct }%（{ $count } 个组）
+control-cgroup-adjusting = 将应用限制为 { $pct }%（{ $count } 个组）
+control-cgroup-increasing = 将应用限制提高到 { $pct }%（{ $count } 个组）
+actuator-performance = 性能
+actuator-power-limit = 功耗限制
+actuator-cgroups = 限制应用
+hint-actuator = 自动控制调节的对象：性能上限、封装持续功耗限制（PL1），或优先调节所选应用的 CPU 配额
+throttle-placeholder = firefox, build.slice
+a11y-throttle = 要限制的应用
+hint-throttle = 要限制的应用或用户单元，用逗号分隔，例如 firefox 或 tracker-miner-fs-3。只有当它们已被限制到 10% 时才会降低性能上限。
+status-throttle-released = 已解除应用限制
+status-throttle-refused = 未限制，会涵盖整个会话或系统：{ $groups }
+
+# Modes
+mode-performance = 性能
+mode-comfort = 舒适
+mode-balanced = 均衡
+mode-quiet = 安静
+mode-auto = 自动
+mode-unknown = 未知
+mode-performance-desc = 视频通话
+mode-comfort-desc = 键盘凉爽
+mode-balanced-desc = 日常使用
+mode-quiet-desc = 静音
+mode-auto-desc = 自动
+mode-unknown-desc = 未知
+mode-description = { $limits } - { $purpose }
+mode-editor-new = 新建模式
+mode-editor-edit = 编辑模式
+mode-field-name = 名称
+mode-field-description = 描述
+mode-field-color = 颜色
+mode-field-max-perf = 最高性能
+mode-field-profile = 平台配置
+mode-field-processes = 应用
+hint-mode-processes = 进程名，用逗号分隔。任一进程运行时使用此模式，结束后恢复之前的模式。
+mode-field-power-limit = 功耗限制（PL1）
+mode-unchanged = 不变
+mode-save = 保存
+mode-cancel = 取消
+mode-edit = 编辑
+mode-delete = 删除
+mode-name-invalid = 名称至少需要一个字母或数字
+mode-name-taken = 已存在名为 { $name } 的模式
+mode-drift = 已修改
+drift-item = { $path }：应为 { $expected }，当前 { $actual }
+
+# Thermal zones
+zone-cool = 凉爽
+zone-comfort = 舒适
+zone-optimal = 最佳
+zone-warm = 温暖
+zone-hot = 炎热
+zone-critical = 危急
+
+# Themes
+theme-system = 跟随系统
+theme-light = 浅色
+theme-dark = 深色
+theme-high-contrast = 高对比度
+
+# Keyboard shortcuts
+shortcut-title = 键盘快捷键
+shortcut-modes = 选择模式
+shortcut-fan = 切换风扇强劲模式
+shortcut-auto = 切换自动控制
+shortcut-target = 提高 / 降低目标
+shortcut-export = 导出历史
+shortcut-focus = 在控件间移动焦点
+shortcut-help = 显示此帮助
+
+# Screen reader descriptions
+unit-celsius = 摄氏度
+unit-fahrenheit = 华氏度
+unit-gigahertz = 吉赫
+unit-megahertz = 兆赫
+a11y-quantity = { $value } { $unit }
+a11y-cpu-temp = CPU 温度 { $value }
+a11y-kbd-temp = 键盘估计温度 { $value }
+a11y-zone = 温度区间 { $zone }
+a11y-perf = 性能上限 百分之 { $pct }
+a11y-freq = CPU 频率 { $value }
+a11y-mode = 当前模式 { $mode }
+a11y-mode-button = { $mode } 模式
+a11y-auto-control = 自动温度控制
+a11y-actuator = 自动控制执行方式
+a11y-fan-boost = 风扇强劲模式
+a11y-turbo = 睿频
+a11y-min-perf = 最低性能百分之 { $pct }
+a11y-profile = 平台配置文件 { $profile }
+
+# Command line
+cli-cpu = CPU
+cli-keyboard = 键盘
+cli-ambient = 环境
+cli-frequency = 频率
+cli-performance = 性能
+cli-driver = 驱动
+cli-mode = 模式
+cli-drift = 偏离
+cli-profile = 配置
+cli-fan = 风扇
+cli-fan-speed = 风扇转速
+cli-power = 功耗
+cli-power-limits = 功耗限制
+cli-power-source = 电源
+cli-battery = 电池
+cli-no-rules = 没有规则；请在 { $path } 中添加
+cli-no-power-limits = 未找到 RAPL 功耗限制
+cli-epp = 能耗偏好
+cli-turbo = 睿频
+cli-min-perf = 最低性能
+
+# Privileged writes
+write-cpus = CPU { $cpus }
+write-summary = { $total } 项写入中有 { $failed } 项失败
+write-failed = { $path }：{ $error }
+write-mismatch = { $path }：写入 { $value }，读回 { $actual }
+
+# Energy preferences
+epp-default = 默认
+epp-performance = 性能
+epp-balance-performance = 均衡偏性能
+epp-balance-power = 均衡偏节能
+epp-power = 节能
+
+# Platform profiles
+profile-low-power = 低功耗
+profile-cool = 凉爽
+profile-quiet = 安静
+profile-balanced = 均衡
+profile-balanced-performance = 均衡偏性能
+profile-performance = 性能
+profile-max-power = 最大功率
+profile-custom = 自定义
+
+# Other power managers
+conflict-active = { $manager } 正在运行，可能会覆盖{ $knobs }
+conflict-enabled = { $manager } 已设为开机启动，可能会覆盖{ $knobs }
+conflict-installed = 已安装 { $manager }，可能会覆盖{ $knobs }
+conflict-knobs-ppd = 平台配置和能耗偏好
+conflict-knobs-tlp = 性能限制、能耗偏好、睿频和平台配置
+conflict-knobs-thermald = 性能限制
+conflict-knobs-auto-cpufreq = 能耗偏好和睿频
+conflict-ppd-cooperate = 使用 power-profiles-daemon
+conflict-ppd-cooperating = 通过 power-profiles-daemon 请求配置
+conflict-ppd-stop = 直接写入
+conflict-dismiss = 忽略
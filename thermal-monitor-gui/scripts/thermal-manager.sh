@@ -77,5 +77,3 @@ echo "$TIMESTAMP | CPU:${CPU_TEMP}C | Kbd:~${KEYBOARD_EST}C | ${MAX_PERF}% | $MO
 
 # Keep log small
 [ $(wc -l < $LOG_FILE 2>/dev/null || echo 0) -gt 2000 ] && tail -1000 $LOG_FILE > ${LOG_FILE}.tmp && mv ${LOG_FILE}.tmp $LOG_FILE
-
-echo "comfort-$MODE" > /tmp/cpu-mode.current 2>/dev/null
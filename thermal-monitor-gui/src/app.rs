@@ -3,44 +3,69 @@
 //! Implements eframe::App trait for egui integration.
 
 use std::collections::VecDeque;
-use std::time::{Duration, Instant};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use eframe::egui;
-use egui_plot::{Line, Plot, PlotPoints};
-
-use crate::system::{Mode, ThermalState, ThermalZone, set_mode, set_fan_boost, apply_thermal_control};
+use egui_plot::{Line, Plot, PlotPoints, Points, VLine};
+
+use crate::cgroup;
+use crate::config::Config;
+use crate::conflicts::{self, Conflict, Manager};
+use crate::cpufreq::MIN_PERF_PCT;
+use crate::i18n::{tr, tr_args};
+use crate::shortcuts::{help_entries, shortcut_for, Shortcut, TARGET_STEP};
+use crate::helper::{drift_text, WriteReport};
+use crate::fan::{self, BoostController, BoostRequester};
+use crate::fan_curve::{self, CurvePoint, FanCurve};
+use crate::modes::{self, ModeProfile, ProcessSwitch};
+use crate::system::{
+    Actuator, Mode, ThermalState, ThermalZone, set_mode, set_fan_boost, apply_thermal_control, control_wants_fan_boost,
+    energy_preference_label, set_energy_preference, set_min_perf_pct, set_platform_profile, set_turbo,
+};
+use crate::platform_profile::profile_label;
+use crate::power::MIN_POWER_LIMIT_W;
+use crate::power_supply::PowerSource;
+use crate::processes::{self, Contributor, HeatTracker, ProcessAction, ProcessStat};
+use crate::rules::{self, Action, Fired, RuleEngine};
+use crate::session::SleepWatcher;
+use crate::theme::{read_system_theme, Theme, ThemePreference};
+use crate::units::{FreqUnit, TempUnit, Units};
 
 /// Update interval in seconds
 const UPDATE_INTERVAL_SECS: f32 = 2.0;
+/// Processes listed in the heat panel
+const TOP_PROCESSES: usize = 8;
 
 /// History capacity (2 minutes at 2-second intervals)
 const HISTORY_CAPACITY: usize = 60;
 
-/// Get localized app description (max 8 words)
-/// Supports: English, Spanish, Chinese, Portuguese, German
-fn get_localized_description() -> &'static str {
-    let lang = std::env::var("LANG")
-        .or_else(|_| std::env::var("LC_ALL"))
-        .or_else(|_| std::env::var("LC_MESSAGES"))
-        .unwrap_or_default();
+/// Upper end of the PL1 slider where the firmware states no maximum
+const POWER_LIMIT_SLIDER_MAX: u32 = 65;
 
-    let lang_code = lang.split('.').next().unwrap_or("en");
-    let lang_prefix = lang_code.split('_').next().unwrap_or("en");
+/// How often to re-read the desktop color scheme when following the system
+const THEME_POLL_SECS: u64 = 30;
 
-    match lang_prefix {
-        "es" => "Monitorea y controla temperatura CPU",
-        "zh" => "监控和控制CPU温度",
-        "pt" => "Monitore e controle temperatura CPU",
-        "de" => "CPU-Temperatur überwachen und steuern",
-        _ => "Monitor and control CPU temperature",
-    }
-}
+/// How often to look for other power managers
+const CONFLICT_POLL_SECS: u64 = 30;
+
+/// How often to look for new cgroups to throttle
+const THROTTLE_SCAN_SECS: u64 = 30;
 
-/// Temperature history buffer
+/// Target temperature slider range (Celsius)
+const TARGET_MIN: f32 = 40.0;
+const TARGET_MAX: f32 = 80.0;
+
+/// Temperature (plus fan speed and power) history buffer
 #[derive(Debug)]
 pub struct TemperatureHistory {
     cpu_temps: VecDeque<f32>,
     kbd_temps: VecDeque<f32>,
+    fan_rpms: VecDeque<Option<u32>>,
+    power_watts: VecDeque<Option<f32>>,
     capacity: usize,
 }
 
@@ -55,41 +80,122 @@ impl TemperatureHistory {
         Self {
             cpu_temps: VecDeque::with_capacity(capacity),
             kbd_temps: VecDeque::with_capacity(capacity),
+            fan_rpms: VecDeque::with_capacity(capacity),
+            power_watts: VecDeque::with_capacity(capacity),
             capacity,
         }
     }
 
-    pub fn push(&mut self, cpu: f32, kbd: f32) {
-        if self.cpu_temps.len() >= self.capacity {
+    /// Record temperatures together with fan speed and power, if known
+    pub fn push(&mut self, cpu: f32, kbd: f32, fan_rpm: Option<u32>, power_w: Option<f32>) {
+        if self.len() >= self.capacity {
             self.cpu_temps.pop_front();
             self.kbd_temps.pop_front();
+            self.fan_rpms.pop_front();
+            self.power_watts.pop_front();
         }
         self.cpu_temps.push_back(cpu);
         self.kbd_temps.push_back(kbd);
+        self.fan_rpms.push_back(fan_rpm);
+        self.power_watts.push_back(power_w);
+    }
+
+    /// Whether any sample has a power reading
+    pub fn has_power(&self) -> bool {
+        self.power_watts.iter().any(Option::is_some)
+    }
+
+    /// Get power points for plotting, skipping samples without a reading
+    pub fn power_points(&self) -> PlotPoints {
+        PlotPoints::new(
+            self.power_watts
+                .iter()
+                .enumerate()
+                .filter_map(|(i, watts)| Some([i as f64, (*watts)? as f64]))
+                .collect(),
+        )
+    }
+
+    /// Whether any sample has a fan speed
+    pub fn has_fan(&self) -> bool {
+        self.fan_rpms.iter().any(Option::is_some)
+    }
+
+    /// Get fan speed points for plotting, skipping samples without a reading
+    pub fn fan_points(&self) -> PlotPoints {
+        PlotPoints::new(
+            self.fan_rpms
+                .iter()
+                .enumerate()
+                .filter_map(|(i, rpm)| Some([i as f64, (*rpm)? as f64]))
+                .collect(),
+        )
     }
 
     /// Get CPU temperature points for plotting
-    pub fn cpu_points(&self) -> PlotPoints {
+    pub fn cpu_points(&self, unit: TempUnit) -> PlotPoints {
         PlotPoints::new(
             self.cpu_temps
                 .iter()
                 .enumerate()
-                .map(|(i, &t)| [i as f64, t as f64])
+                .map(|(i, &t)| [i as f64, unit.convert(t) as f64])
                 .collect(),
         )
     }
 
     /// Get keyboard temperature points for plotting
-    pub fn kbd_points(&self) -> PlotPoints {
+    pub fn kbd_points(&self, unit: TempUnit) -> PlotPoints {
         PlotPoints::new(
             self.kbd_temps
                 .iter()
                 .enumerate()
-                .map(|(i, &t)| [i as f64, t as f64])
+                .map(|(i, &t)| [i as f64, unit.convert(t) as f64])
                 .collect(),
         )
     }
 
+    /// Export history as CSV, oldest sample first
+    /// Time column is seconds relative to the newest sample; fan_rpm and
+    /// power_w columns are added when such readings were recorded
+    pub fn to_csv(&self, unit: TempUnit) -> String {
+        let symbol = unit.symbol().trim_start_matches('°');
+        let (fan, power) = (self.has_fan(), self.has_power());
+        let mut csv = format!("seconds,cpu_{},keyboard_{}", symbol, symbol);
+        if fan {
+            csv.push_str(",fan_rpm");
+        }
+        if power {
+            csv.push_str(",power_w");
+        }
+        csv.push('\n');
+        let newest = self.len().saturating_sub(1);
+        let samples = self.cpu_temps.iter().zip(&self.kbd_temps).zip(&self.fan_rpms).zip(&self.power_watts);
+        for (i, (((cpu, kbd), rpm), watts)) in samples.enumerate() {
+            let seconds = -(((newest - i) as f32 * UPDATE_INTERVAL_SECS) as i64);
+            let _ = write!(
+                csv,
+                "{},{:.1},{:.1}",
+                seconds,
+                unit.convert(*cpu),
+                unit.convert(*kbd)
+            );
+            if fan {
+                csv.push(',');
+                if let Some(rpm) = rpm {
+                    let _ = write!(csv, "{}", rpm);
+                }
+            }
+            if power {
+                csv.push(',');
+                if let Some(watts) = watts {
+                    let _ = write!(csv, "{:.1}", watts);
+                }
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
     pub fn len(&self) -> usize {
         self.cpu_temps.len()
     }
@@ -99,6 +205,125 @@ impl TemperatureHistory {
     }
 }
 
+/// Color preset for new custom modes
+const CUSTOM_MODE_COLOR: [u8; 3] = [100, 220, 200];
+
+/// Custom mode being created or edited
+struct ModeEditor {
+    profile: ModeProfile,
+    /// Name of the mode being edited, `None` when creating one
+    original: Option<String>,
+    label: String,
+    purpose: String,
+    /// Comma-separated process names that switch to the mode
+    apps: String,
+    error: Option<String>,
+}
+
+impl ModeEditor {
+    /// New mode preset from the current limits
+    fn create(state: &ThermalState) -> Self {
+        Self {
+            profile: ModeProfile {
+                max_perf_pct: Some(state.perf_pct),
+                color: Some(CUSTOM_MODE_COLOR),
+                ..ModeProfile::default()
+            },
+            original: None,
+            label: String::new(),
+            purpose: String::new(),
+            apps: String::new(),
+            error: None,
+        }
+    }
+
+    fn edit(profile: ModeProfile) -> Self {
+        Self {
+            original: Some(profile.name.clone()),
+            label: profile.label.clone().unwrap_or_else(|| profile.name.clone()),
+            purpose: profile.purpose.clone().unwrap_or_default(),
+            apps: profile.processes.join(", "),
+            profile,
+            error: None,
+        }
+    }
+
+    /// Profile to save; new modes are named after their label
+    fn finish(&self) -> Result<ModeProfile, String> {
+        let label = self.label.trim();
+        let name = self.original.clone().unwrap_or_else(|| modes::slug(label));
+        if label.is_empty() || !modes::valid_name(&name) {
+            return Err(tr("mode-name-invalid").to_string());
+        }
+        if self.original.is_none() && modes::find(&name).is_some() {
+            return Err(tr_args("mode-name-taken", &[("name", &name)]));
+        }
+        let purpose = self.purpose.trim();
+        Ok(ModeProfile {
+            name,
+            label: Some(label.to_string()),
+            purpose: (!purpose.is_empty()).then(|| purpose.to_string()),
+            processes: self.apps.split(',').map(str::trim).filter(|app| !app.is_empty()).map(String::from).collect(),
+            ..self.profile.clone()
+        })
+    }
+}
+
+/// Screen distance (points) within which a curve point can be grabbed
+const CURVE_GRAB_RADIUS: f32 = 12.0;
+
+/// Fan curve being edited
+struct FanCurveEditor {
+    curve: FanCurve,
+    /// Index of the point being dragged
+    dragging: Option<usize>,
+    error: Option<String>,
+}
+
+impl FanCurveEditor {
+    fn new(curve: FanCurve) -> Self {
+        Self { curve, dragging: None, error: None }
+    }
+
+    /// Move a point, keeping temperatures rising and duty never falling
+    fn drag_to(&mut self, index: usize, temp: f32, duty: f32) {
+        let points = &mut self.curve.points;
+        let (lo_temp, lo_duty) = match index.checked_sub(1).map(|i| points[i]) {
+            Some(prev) => (prev.temp + 1.0, prev.duty),
+            None => (*fan_curve::TEMP_RANGE.start(), 0),
+        };
+        let (hi_temp, hi_duty) = match points.get(index + 1) {
+            Some(next) => (next.temp - 1.0, next.duty),
+            None => (*fan_curve::TEMP_RANGE.end(), 100),
+        };
+        points[index] = CurvePoint::new(
+            temp.round().clamp(lo_temp, hi_temp),
+            (duty.round().clamp(0.0, 100.0) as u8).clamp(lo_duty, hi_duty),
+        );
+    }
+
+    /// Add a point where the curve currently is at `temp`
+    fn insert(&mut self, temp: f32) {
+        let temp = temp.round();
+        let points = &mut self.curve.points;
+        if points.len() >= fan_curve::MAX_POINTS
+            || !fan_curve::TEMP_RANGE.contains(&temp)
+            || points.iter().any(|p| p.temp == temp)
+        {
+            return;
+        }
+        let duty = self.curve.duty_at(temp);
+        let index = self.curve.points.partition_point(|p| p.temp < temp);
+        self.curve.points.insert(index, CurvePoint::new(temp, duty));
+    }
+
+    fn remove(&mut self, index: usize) {
+        if self.curve.points.len() > 2 {
+            self.curve.points.remove(index);
+        }
+    }
+}
+
 /// Main application state
 pub struct ThermalApp {
     state: ThermalState,
@@ -107,24 +332,100 @@ pub struct ThermalApp {
     status_message: Option<(String, Instant)>,
     target_temp: f32,
     auto_control: bool,
-    fan_boost_manual: bool,
+    /// Fan boost requests from the user, auto control and the mode
+    fan_boost: BoostController,
+    config: Config,
+    system_theme: Option<Theme>,
+    last_theme_check: Instant,
+    show_shortcut_help: bool,
+    last_zone: ThermalZone,
+    /// Minimum performance being dragged, applied when released
+    min_perf_edit: Option<u8>,
+    mode_editor: Option<ModeEditor>,
+    fan_curve_editor: Option<FanCurveEditor>,
+    /// Power managers that may override our writes
+    conflicts: Vec<Conflict>,
+    last_conflict_check: Instant,
+    conflicts_dismissed: bool,
+    /// Power source at the last update, to notice plugging and unplugging
+    power_source: Option<PowerSource>,
+    /// Rules evaluated on every update
+    rules: RuleEngine,
+    /// Mode switched to while configured apps run
+    process_switch: ProcessSwitch,
+    /// Top CPU users, sampled only while the panel is shown
+    show_heat: bool,
+    heat: HeatTracker,
+    contributors: Vec<Contributor>,
+    /// Processes stopped from the panel, listed until continued or gone
+    stopped: Vec<ProcessStat>,
+    /// Cgroups to throttle as typed, saved when the field loses focus
+    throttle_text: String,
+    /// Cgroups matching the configured patterns at the last scan
+    throttle_groups: Vec<PathBuf>,
+    last_throttle_scan: Option<Instant>,
+    sleep: SleepWatcher,
+    /// Locked or asleep: no control and nothing that asks for a password
+    paused: bool,
+    /// Mode to apply again once resumed and unlocked
+    resume_mode: Option<Mode>,
+    has_lid: bool,
+    /// A hwmon PWM channel the fan curve can drive
+    has_pwm_channel: bool,
+    lid_switch: ProcessSwitch,
 }
 
 impl Default for ThermalApp {
     fn default() -> Self {
         let state = ThermalState::read();
+        let last_zone = state.thermal_zone();
+        let power_source = state.power_supply.source();
+        let config = Config::load();
         let mut history = TemperatureHistory::default();
-        history.push(state.cpu_temp, state.keyboard_temp);
+        history.push(state.cpu_temp, state.keyboard_temp, state.fan_rpm, state.power.total());
+        let mut sleep = SleepWatcher::start();
+        let has_lid = sleep.lid_closed().is_some();
 
-        Self {
+        let mut app = Self {
             state,
             history,
             last_update: Instant::now(),
             status_message: None,
             target_temp: 55.0,
             auto_control: false,
-            fan_boost_manual: false,
+            fan_boost: BoostController::default(),
+            throttle_text: config.throttle_groups.join(", "),
+            throttle_groups: Vec::new(),
+            last_throttle_scan: None,
+            config,
+            system_theme: read_system_theme(),
+            last_theme_check: Instant::now(),
+            show_shortcut_help: false,
+            last_zone,
+            min_perf_edit: None,
+            mode_editor: None,
+            fan_curve_editor: None,
+            conflicts: conflicts::detect(),
+            last_conflict_check: Instant::now(),
+            conflicts_dismissed: false,
+            power_source,
+            rules: RuleEngine::from_files(rules::rule_files()),
+            process_switch: ProcessSwitch::default(),
+            show_heat: false,
+            heat: HeatTracker::default(),
+            contributors: Vec::new(),
+            stopped: Vec::new(),
+            paused: false,
+            resume_mode: None,
+            has_lid,
+            has_pwm_channel: fan::pwm().is_some(),
+            lid_switch: ProcessSwitch::default(),
+            sleep,
+        };
+        if let Some(error) = app.sleep.error().map(String::from) {
+            app.set_status(tr_args("status-sleep-watch-failed", &[("error", &error)]));
         }
+        app
     }
 }
 
@@ -135,88 +436,822 @@ impl ThermalApp {
 
     /// Update state from system
     fn update_state(&mut self) {
+        // Some firmware resets the platform profile on resume, so the mode
+        // from before is applied again; held back, like everything that
+        // writes, until the session is unlocked. Nothing is read while
+        // paused, so the state still shows the mode from before
+        if self.sleep.take_resumed() && self.state.mode != Mode::Unknown {
+            self.resume_mode.get_or_insert(self.state.mode.clone());
+        }
+        let paused = self.sleep.sleeping() || self.sleep.locked() == Some(true);
+        if paused != self.paused {
+            self.paused = paused;
+            self.set_status(if paused { tr("status-paused").into() } else { tr("status-resumed").into() });
+        }
+        if paused {
+            // CPU shares start over once sampling does
+            self.heat.reset();
+            return;
+        }
+
         self.state = ThermalState::read();
-        self.history.push(self.state.cpu_temp, self.state.keyboard_temp);
+        if self.via_ppd() {
+            self.state.mode_drift.retain(|result| !conflicts::ppd_owns(&result.write.path));
+        }
+        self.history.push(self.state.cpu_temp, self.state.keyboard_temp, self.state.fan_rpm, self.state.power.total());
+        if self.show_heat {
+            if let Some(snapshot) = processes::current_snapshot() {
+                self.contributors = self.heat.update(snapshot, self.state.power.total(), TOP_PROCESSES);
+            }
+            self.stopped.retain(processes::exists);
+        }
+
+        // Zone changes go through the status line, which is a live region
+        let zone = self.state.thermal_zone();
+        if zone != self.last_zone {
+            self.last_zone = zone;
+            self.set_status(tr_args("status-zone-changed", &[("zone", zone.label())]));
+        }
+
+        // Follow desktop color scheme changes
+        if self.config.theme == ThemePreference::System
+            && self.last_theme_check.elapsed() >= Duration::from_secs(THEME_POLL_SECS)
+        {
+            self.system_theme = read_system_theme();
+            self.last_theme_check = Instant::now();
+        }
+
+        if self.last_conflict_check.elapsed() >= Duration::from_secs(CONFLICT_POLL_SECS) {
+            self.conflicts = conflicts::detect();
+            self.last_conflict_check = Instant::now();
+        }
+
+        if let Some(mode) = self.resume_mode.take() {
+            if mode != self.state.mode || !self.state.mode_drift.is_empty() {
+                let message = tr_args("status-resume-mode", &[("mode", &mode.label())]);
+                self.apply_mode_quietly(&mode, message);
+            }
+        }
+
+        // Lid mode while closed, the mode from before once opened
+        let lid_closed = self.has_lid && self.sleep.lid_closed() == Some(true);
+        let wanted = self.config.lid_mode.as_deref().filter(|_| lid_closed);
+        if let Some(mode) = self.lid_switch.step(wanted, &self.state.mode) {
+            let key = if lid_closed { "status-lid-mode" } else { "status-lid-restored" };
+            let applied = self.apply_mode_quietly(&mode, tr_args(key, &[("mode", &mode.label())]));
+            self.lid_switch.commit(applied);
+        }
+
+        // Mode rules for plugging in and unplugging; recorded first, since
+        // applying a mode updates the state again
+        let source = self.state.power_supply.source();
+        if source != self.power_source {
+            let previous = std::mem::replace(&mut self.power_source, source);
+            if let (Some(_), Some(source)) = (previous, source) {
+                self.apply_source_rule(source);
+            }
+        }
+
+        self.rules.reload_if_changed();
+        if !self.rules.rules.is_empty() {
+            let sample = self.rules.rules.sample(&self.state);
+            for fired in self.rules.evaluate(&sample, Instant::now()) {
+                self.perform_rule_action(&fired);
+            }
+        }
+        self.follow_running_apps();
 
         // Apply automatic thermal control if enabled
+        let now = Instant::now();
         if self.auto_control {
-            if let Ok(msg) = apply_thermal_control(self.state.cpu_temp, self.target_temp) {
-                if msg != "On target" {
+            if control_wants_fan_boost(self.state.cpu_temp, self.target_temp) {
+                self.fan_boost.request(BoostRequester::AutoControl);
+            } else {
+                self.fan_boost.release(BoostRequester::AutoControl, now);
+            }
+            let actuator = self.actuator();
+            let groups = if actuator == Actuator::Cgroups { self.throttle_groups() } else { Vec::new() };
+            if let Ok(msg) = apply_thermal_control(self.state.cpu_temp, self.target_temp, actuator, &groups) {
+                if msg != tr("control-on-target") {
                     self.status_message = Some((msg, Instant::now()));
                 }
             }
         }
+        let mode_boost = modes::find(self.state.mode.command()).and_then(|profile| profile.fan_boost);
+        if mode_boost == Some(true) {
+            self.fan_boost.request(BoostRequester::Daemon);
+        } else {
+            self.fan_boost.release(BoostRequester::Daemon, now);
+        }
+        self.reconcile_fan_boost();
+    }
+
+    /// Write fan boost when the combined requests changed, then show the
+    /// state read back from the driver. Returns the write result, if any
+    fn reconcile_fan_boost(&mut self) -> Option<io::Result<()>> {
+        let now = Instant::now();
+        let enable = self.fan_boost.pending(self.state.fan_boost, now)?;
+        let result = set_fan_boost(enable);
+        self.fan_boost.record(&result, now);
+        self.state.fan_boost = fan::backend().boost_enabled();
+        Some(result)
     }
 
     /// Change CPU mode
-    fn change_mode(&mut self, mode: Mode) {
-        match set_mode(mode) {
-            Ok(()) => {
-                self.status_message = Some((
-                    format!("Mode changed to {}", mode.label()),
-                    Instant::now(),
-                ));
-                self.update_state();
+    fn change_mode(&mut self, mode: &Mode) {
+        let success = tr_args("status-mode-changed", &[("mode", &mode.label())]);
+        self.report_write(set_mode(mode, self.via_ppd()), success);
+    }
+
+    /// Switch to the mode configured for a new power source, if any
+    fn apply_source_rule(&mut self, source: PowerSource) {
+        let Some(mode) = self.config.mode_for_source(source).map(Mode::from_name) else {
+            return;
+        };
+        if mode == Mode::Unknown || mode == self.state.mode {
+            return;
+        }
+        let success = tr_args("status-source-mode", &[("source", source.label()), ("mode", &mode.label())]);
+        self.report_write(set_mode(&mode, self.via_ppd()), success);
+    }
+
+    /// Switch to the mode of a configured app while it runs and back
+    /// afterwards. Applied without re-reading the state, like rule modes
+    fn follow_running_apps(&mut self) {
+        let profiles = modes::profiles();
+        if !profiles.iter().any(ModeProfile::has_triggers) && self.process_switch.active().is_none() {
+            return;
+        }
+        let (names, cgroups) = (processes::running(), processes::running_cgroups());
+        let triggered = modes::triggered(&profiles, &names, &cgroups);
+        let Some(mode) = self.process_switch.step(triggered.as_ref().map(|(profile, _)| profile.name.as_str()), &self.state.mode) else {
+            return;
+        };
+        let message = match &triggered {
+            Some((_, app)) => tr_args("status-app-mode", &[("app", app), ("mode", &mode.label())]),
+            None => tr_args("status-app-restored", &[("mode", &mode.label())]),
+        };
+        let applied = self.apply_mode_quietly(&mode, message);
+        self.process_switch.commit(applied);
+    }
+
+    /// Apply a mode from within [`Self::update_state`], which
+    /// [`Self::report_write`] would re-enter; the next update reads it back.
+    /// Returns whether the mode was applied
+    fn apply_mode_quietly(&mut self, mode: &Mode, success: String) -> bool {
+        match set_mode(mode, self.via_ppd()) {
+            Ok(_) => {
+                self.set_status(success);
+                true
             }
             Err(e) => {
-                self.status_message = Some((
-                    format!("Error: {}", e),
-                    Instant::now(),
-                ));
+                self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+                false
             }
         }
     }
 
+    /// Renice, stop or continue a process from the heat panel
+    fn process_action(&mut self, process: &ProcessStat, action: ProcessAction) {
+        let key = match action {
+            ProcessAction::Renice => "status-process-reniced",
+            ProcessAction::Stop => "status-process-stopped",
+            ProcessAction::Continue => "status-process-continued",
+        };
+        match processes::perform(process, action) {
+            Ok(()) => {
+                match action {
+                    ProcessAction::Stop => self.stopped.push(ProcessStat { stopped: true, ..process.clone() }),
+                    ProcessAction::Continue => self.stopped.retain(|p| p.pid != process.pid),
+                    ProcessAction::Renice => {}
+                }
+                self.set_status(tr_args(key, &[("name", &process.name), ("pid", &process.pid.to_string())]));
+            }
+            Err(e) => self.set_status(tr_args("status-error", &[("error", &e.to_string())])),
+        }
+    }
+
+    /// Carry out a fired rule action, or only report it in dry run.
+    /// Modes are applied without re-reading the state, which the next
+    /// update does
+    fn perform_rule_action(&mut self, fired: &Fired) {
+        if self.rules.rules.dry_run {
+            self.set_status(tr_args("rules-dry-run", &[("action", &fired.describe())]));
+            return;
+        }
+        match &fired.action {
+            Action::Mode(name) => {
+                let mode = Mode::from_name(name);
+                self.apply_mode_quietly(&mode, tr_args("status-rule-mode", &[("rule", &fired.rule), ("mode", &mode.label())]));
+            }
+            Action::Target(celsius) => self.target_temp = celsius.clamp(TARGET_MIN, TARGET_MAX),
+            Action::FanBoost(true) => self.fan_boost.request(BoostRequester::Rule),
+            Action::FanBoost(false) => self.fan_boost.cancel(BoostRequester::Rule),
+            Action::Notify(text) => {
+                rules::notify(text);
+                self.set_status(text.clone());
+            }
+        }
+    }
+
+    /// Mode for a closed lid, persisted
+    fn set_lid_mode(&mut self, mode: Option<String>) {
+        self.config.lid_mode = mode;
+        if let Err(e) = self.config.save() {
+            self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+        }
+    }
+
+    /// Mode rule for one power source, persisted
+    fn set_source_mode(&mut self, source: PowerSource, mode: Option<String>) {
+        match source {
+            PowerSource::Battery => self.config.battery_mode = mode,
+            PowerSource::Ac => self.config.ac_mode = mode,
+        }
+        if let Err(e) = self.config.save() {
+            self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+        }
+    }
+
+    /// Whether profile changes go through power-profiles-daemon
+    fn via_ppd(&self) -> bool {
+        self.config.cooperate_ppd && conflicts::ppd_active(&self.conflicts)
+    }
+
+    /// Switch power-profiles-daemon cooperation and persist it
+    fn set_cooperate_ppd(&mut self, cooperate: bool) {
+        self.config.cooperate_ppd = cooperate;
+        match self.config.save() {
+            Ok(()) => self.set_status(tr(if cooperate { "status-ppd-on" } else { "status-ppd-off" }).to_string()),
+            Err(e) => self.set_status(tr_args("status-error", &[("error", &e.to_string())])),
+        }
+    }
+
+    /// Remove a user-defined mode file
+    fn delete_mode(&mut self, mode: &Mode) {
+        match modes::delete_user(mode.command()) {
+            Ok(()) => self.set_status(tr_args("status-mode-deleted", &[("mode", &mode.label())])),
+            Err(e) => self.set_status(tr_args("status-error", &[("error", &e.to_string())])),
+        }
+    }
+
+    /// Show the outcome of a privileged sysfs write and refresh readings
+    fn report_write(&mut self, result: io::Result<WriteReport>, success: String) {
+        match result {
+            Ok(_) => {
+                self.set_status(success);
+                self.update_state();
+            }
+            Err(e) => self.set_status(tr_args("status-error", &[("error", &e.to_string())])),
+        }
+    }
+
+    fn change_energy_preference(&mut self, value: &str) {
+        let success = tr_args("status-epp", &[("value", energy_preference_label(value))]);
+        self.report_write(set_energy_preference(value), success);
+    }
+
+    fn change_turbo(&mut self, enabled: bool) {
+        let success = if enabled { tr("status-turbo-on") } else { tr("status-turbo-off") };
+        self.report_write(set_turbo(enabled), success.into());
+    }
+
+    fn change_min_perf(&mut self, pct: u8) {
+        let success = tr_args("status-min-perf", &[("pct", &pct.to_string())]);
+        self.report_write(set_min_perf_pct(pct), success);
+    }
+
     /// Set status message
     fn set_status(&mut self, msg: String) {
         self.status_message = Some((msg, Instant::now()));
     }
 
-    /// Get zone color as egui Color32
-    fn zone_color(zone: ThermalZone) -> egui::Color32 {
-        let (r, g, b) = zone.color_rgb();
-        egui::Color32::from_rgb(r, g, b)
+    fn units(&self) -> Units {
+        self.config.units
+    }
+
+    /// Apply a new units preference and persist it
+    fn set_units(&mut self, units: Units) {
+        self.config.units = units;
+        if let Err(e) = self.config.save() {
+            self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+        }
     }
 
-    /// Get mode color
-    fn mode_color(mode: Mode) -> egui::Color32 {
-        match mode {
-            Mode::Performance => egui::Color32::from_rgb(255, 100, 100),
-            Mode::Comfort => egui::Color32::from_rgb(100, 200, 255),
-            Mode::Balanced => egui::Color32::from_rgb(150, 220, 100),
-            Mode::Quiet => egui::Color32::from_rgb(180, 180, 220),
-            Mode::Auto => egui::Color32::from_rgb(255, 200, 100),
-            Mode::Unknown => egui::Color32::GRAY,
+    /// Compact frequency label for the performance panel ("2.5G", "2500M")
+    fn freq_label(&self, mhz: u32, ghz: f32) -> String {
+        match self.units().freq {
+            FreqUnit::GHz => format!("{:.1}G", ghz),
+            FreqUnit::MHz => format!("{}M", mhz),
         }
     }
 
+    /// Configured actuator, or the performance ceiling where it cannot work
+    fn actuator(&self) -> Actuator {
+        match self.config.actuator {
+            Actuator::PowerLimit if self.state.power_limits.long_term_pct().is_none() => Actuator::Performance,
+            Actuator::Cgroups if self.config.throttle_groups.is_empty() => Actuator::Performance,
+            actuator => actuator,
+        }
+    }
+
+    fn actuator_available(&self, actuator: Actuator) -> bool {
+        match actuator {
+            Actuator::Performance => true,
+            Actuator::PowerLimit => self.state.power_limits.long_term_pct().is_some(),
+            Actuator::Cgroups => Path::new(cgroup::CGROUP_ROOT).join("cgroup.controllers").exists(),
+        }
+    }
+
+    fn set_actuator(&mut self, actuator: Actuator) {
+        if self.actuator() == Actuator::Cgroups {
+            self.release_throttle();
+        }
+        self.config.actuator = actuator;
+        if let Err(e) = self.config.save() {
+            self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+        }
+    }
+
+    /// Save the cgroups to throttle, releasing those no longer listed
+    fn set_throttle_groups(&mut self, groups: Vec<String>) {
+        if groups == self.config.throttle_groups {
+            return;
+        }
+        self.release_throttle();
+        let refused: Vec<&str> = groups.iter().map(String::as_str).filter(|g| !cgroup::valid_pattern(g)).collect();
+        if !refused.is_empty() {
+            self.set_status(tr_args("status-throttle-refused", &[("groups", &refused.join(", "))]));
+        }
+        self.config.throttle_groups = groups;
+        self.last_throttle_scan = None;
+        if let Err(e) = self.config.save() {
+            self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+        }
+    }
+
+    /// Cgroups to throttle, looked up again every [`THROTTLE_SCAN_SECS`]
+    /// or when one of them is gone
+    fn throttle_groups(&mut self) -> Vec<PathBuf> {
+        let stale = self.last_throttle_scan.is_none_or(|at| at.elapsed() >= Duration::from_secs(THROTTLE_SCAN_SECS));
+        if stale || !self.throttle_groups.iter().all(|group| group.exists()) {
+            self.throttle_groups = cgroup::groups(&self.config.throttle_groups);
+            self.last_throttle_scan = Some(Instant::now());
+        }
+        self.throttle_groups.clone()
+    }
+
+    /// Lift the caps the cgroup actuator put on the configured groups
+    fn release_throttle(&mut self) {
+        let groups = self.throttle_groups();
+        if cgroup::current_pct(&groups, cgroup::cpu_count()).is_some_and(|pct| pct < 100) {
+            self.report_write(cgroup::set_throttle(&groups, 100), tr("status-throttle-released").into());
+        }
+    }
+
+    fn toggle_auto_control(&mut self) {
+        self.auto_control = !self.auto_control;
+        if !self.auto_control {
+            self.fan_boost.cancel(BoostRequester::AutoControl);
+            self.reconcile_fan_boost();
+            if self.actuator() == Actuator::Cgroups {
+                self.release_throttle();
+            }
+        }
+        self.set_status(if self.auto_control { tr("status-auto-on").into() } else { tr("status-auto-off").into() });
+    }
+
+    fn toggle_fan_boost(&mut self) {
+        if self.state.fan_boost.is_none() {
+            return;
+        }
+        if self.fan_boost.is_requested(BoostRequester::User) {
+            self.fan_boost.release(BoostRequester::User, Instant::now());
+        } else {
+            self.fan_boost.request(BoostRequester::User);
+        }
+        match self.reconcile_fan_boost() {
+            Some(Err(e)) => {
+                // Nothing changed, so the button must not stay pressed
+                self.fan_boost.cancel(BoostRequester::User);
+                self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+            }
+            _ if self.state.fan_boost == Some(true) => {
+                let others = self.fan_boost.requesters().iter().any(|&r| r != BoostRequester::User);
+                if self.fan_boost.is_requested(BoostRequester::User) || !others {
+                    self.set_status(tr("status-fan-boost").into());
+                } else {
+                    self.set_status(tr_args("status-fan-held", &[("who", &self.boost_requesters_text())]));
+                }
+            }
+            _ => self.set_status(tr("status-fan-auto").into()),
+        }
+    }
+
+    /// Move the target temperature by `delta` Celsius, within slider range
+    fn adjust_target(&mut self, delta: f32) {
+        self.target_temp = (self.target_temp + delta).clamp(TARGET_MIN, TARGET_MAX);
+        self.set_status(tr_args("status-target", &[("target", &self.units().temp(self.target_temp))]));
+    }
+
+    /// Dispatch keyboard shortcuts pressed this frame
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Leave keys alone while a text field is being edited
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let shortcuts: Vec<Shortcut> = ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Key { key, pressed: true, repeat: false, modifiers, .. } => {
+                        shortcut_for(*key, *modifiers)
+                    }
+                    _ => None,
+                })
+                .collect()
+        });
+
+        for shortcut in shortcuts {
+            match shortcut {
+                Shortcut::SetMode(index) => {
+                    if let Some(mode) = Mode::available().get(index) {
+                        if self.state.mode != *mode {
+                            self.change_mode(mode);
+                        }
+                    }
+                }
+                Shortcut::ToggleFanBoost => self.toggle_fan_boost(),
+                Shortcut::ToggleAutoControl => self.toggle_auto_control(),
+                Shortcut::TargetUp => self.adjust_target(TARGET_STEP),
+                Shortcut::TargetDown => self.adjust_target(-TARGET_STEP),
+                Shortcut::Export => self.export_history(),
+                Shortcut::ToggleHelp => self.show_shortcut_help = !self.show_shortcut_help,
+                Shortcut::CloseHelp => self.show_shortcut_help = false,
+            }
+        }
+    }
+
+    /// Shortcut help overlay
+    fn render_shortcut_help(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_shortcut_help;
+        egui::Window::new(tr("shortcut-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcut_help").num_columns(2).spacing([16.0, 6.0]).show(ui, |ui| {
+                    for (keys, description) in help_entries() {
+                        ui.label(egui::RichText::new(keys).monospace().strong());
+                        ui.label(description);
+                        ui.end_row();
+                    }
+                });
+            });
+        self.show_shortcut_help = open;
+    }
+
+    /// Window for creating or editing a custom mode
+    fn render_mode_editor(&mut self, ctx: &egui::Context) {
+        let Some(mut editor) = self.mode_editor.take() else {
+            return;
+        };
+        let title = if editor.original.is_some() { tr("mode-editor-edit") } else { tr("mode-editor-new") };
+        let mut open = true;
+        let (mut save, mut cancel) = (false, false);
+        egui::Window::new(title)
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                egui::Grid::new("mode_editor").num_columns(2).spacing([12.0, 6.0]).show(ui, |ui| {
+                    ui.label(tr("mode-field-name"));
+                    let name = ui.add_enabled(editor.original.is_none(), egui::TextEdit::singleline(&mut editor.label));
+                    describe(&name, tr("mode-field-name").to_string());
+                    ui.end_row();
+
+                    ui.label(tr("mode-field-description"));
+                    let purpose = ui.text_edit_singleline(&mut editor.purpose);
+                    describe(&purpose, tr("mode-field-description").to_string());
+                    ui.end_row();
+
+                    ui.label(tr("mode-field-color"));
+                    let mut color = editor.profile.color.unwrap_or(CUSTOM_MODE_COLOR);
+                    if ui.color_edit_button_srgb(&mut color).changed() {
+                        editor.profile.color = Some(color);
+                    }
+                    ui.end_row();
+
+                    ui.label(tr("mode-field-max-perf"));
+                    let mut pct = editor.profile.max_perf_pct.unwrap_or(100);
+                    if ui.add(egui::Slider::new(&mut pct, MIN_PERF_PCT..=100).suffix("%")).changed() {
+                        editor.profile.max_perf_pct = Some(pct);
+                    }
+                    ui.end_row();
+
+                    if !self.state.energy_preferences.is_empty() {
+                        ui.label(tr("label-epp"));
+                        let selected = editor.profile.energy_preference.as_deref().map_or(tr("mode-unchanged"), energy_preference_label);
+                        egui::ComboBox::from_id_salt("mode_editor_epp").selected_text(selected).show_ui(ui, |ui| {
+                            ui.selectable_value(&mut editor.profile.energy_preference, None, tr("mode-unchanged"));
+                            for choice in &self.state.energy_preferences {
+                                ui.selectable_value(&mut editor.profile.energy_preference, Some(choice.clone()), energy_preference_label(choice));
+                            }
+                        });
+                        ui.end_row();
+                    }
+
+                    ui.label(tr("label-turbo"));
+                    tri_state(ui, "mode_editor_turbo", &mut editor.profile.turbo, tr("turbo-on"), tr("turbo-off"));
+                    ui.end_row();
+
+                    ui.label(tr("section-fan"));
+                    tri_state(ui, "mode_editor_fan", &mut editor.profile.fan_boost, tr("fan-boost"), tr("fan-normal"));
+                    ui.end_row();
+
+                    if let Some(limit) = &self.state.power_limits.long_term {
+                        ui.label(tr("mode-field-power-limit"));
+                        ui.horizontal(|ui| {
+                            let mut enabled = editor.profile.power_limit_w.is_some();
+                            let mut watts = editor.profile.power_limit_w.unwrap_or(limit.watts().round() as u32);
+                            let max = limit.max_watts().map_or(POWER_LIMIT_SLIDER_MAX, |w| w.round() as u32);
+                            let check = ui.checkbox(&mut enabled, "");
+                            describe(&check, tr("mode-field-power-limit").to_string());
+                            ui.add_enabled(enabled, egui::Slider::new(&mut watts, MIN_POWER_LIMIT_W..=max.max(MIN_POWER_LIMIT_W)).suffix(" W"));
+                            editor.profile.power_limit_w = enabled.then_some(watts);
+                        });
+                        ui.end_row();
+                    }
+
+                    if !self.state.platform_profile_choices.is_empty() {
+                        ui.label(tr("mode-field-profile"));
+                        let mut selected = editor.profile.platform_profiles.first().cloned();
+                        let text = selected.as_deref().map_or(tr("mode-unchanged"), profile_label);
+                        egui::ComboBox::from_id_salt("mode_editor_profile").selected_text(text).show_ui(ui, |ui| {
+                            ui.selectable_value(&mut selected, None, tr("mode-unchanged"));
+                            for choice in &self.state.platform_profile_choices {
+                                ui.selectable_value(&mut selected, Some(choice.clone()), profile_label(choice));
+                            }
+                        });
+                        editor.profile.platform_profiles = selected.into_iter().collect();
+                        ui.end_row();
+                    }
+
+                    ui.label(tr("mode-field-processes"));
+                    let apps = ui.text_edit_singleline(&mut editor.apps).on_hover_text(tr("hint-mode-processes"));
+                    describe(&apps, tr("mode-field-processes").to_string());
+                    ui.end_row();
+                });
+
+                if let Some(error) = &editor.error {
+                    ui.colored_label(self.theme().zone_color(ThermalZone::Critical), error);
+                }
+                ui.horizontal(|ui| {
+                    save = focus_ring(ui.button(tr("mode-save"))).clicked();
+                    cancel = focus_ring(ui.button(tr("mode-cancel"))).clicked();
+                });
+            });
+
+        if save {
+            match editor.finish() {
+                Ok(profile) => match modes::save_user(&profile) {
+                    Ok(_) => {
+                        let mode = Mode::Custom(profile.name);
+                        self.set_status(tr_args("status-mode-saved", &[("mode", &mode.label())]));
+                        return;
+                    }
+                    Err(e) => editor.error = Some(tr_args("status-error", &[("error", &e.to_string())])),
+                },
+                Err(e) => editor.error = Some(e),
+            }
+        }
+        if open && !cancel {
+            self.mode_editor = Some(editor);
+        }
+    }
+
+    /// Window for editing the hwmon fan curve; points are dragged on the plot,
+    /// added with a double click and removed with a right click
+    fn render_fan_curve_editor(&mut self, ctx: &egui::Context) {
+        let Some(mut editor) = self.fan_curve_editor.take() else {
+            return;
+        };
+        let theme = self.theme();
+        let units = self.units();
+        let cpu_temp = self.state.cpu_temp;
+        let mut open = true;
+        let (mut save, mut cancel) = (false, false);
+        egui::Window::new(tr("fan-curve-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.checkbox(&mut editor.curve.enabled, tr("fan-curve-enabled"));
+                ui.label(egui::RichText::new(tr("fan-curve-hint")).size(10.0).color(theme.faint()));
+
+                let line = Line::new(PlotPoints::new(
+                    editor.curve.points.iter().map(|p| [p.temp as f64, p.duty as f64]).collect(),
+                ))
+                .color(theme.warn())
+                .width(2.0);
+                let markers = Points::new(PlotPoints::new(
+                    editor.curve.points.iter().map(|p| [p.temp as f64, p.duty as f64]).collect(),
+                ))
+                .radius(5.0)
+                .color(theme.warn());
+                let now = VLine::new(cpu_temp as f64).color(theme.zone_color(ThermalZone::from_cpu_temp(cpu_temp)));
+
+                // Axes stay in Celsius, the unit the curve is stored in
+                Plot::new("fan_curve")
+                    .width(360.0)
+                    .height(220.0)
+                    .include_x(*fan_curve::TEMP_RANGE.start() as f64)
+                    .include_x(*fan_curve::TEMP_RANGE.end() as f64)
+                    .include_y(0.0)
+                    .include_y(100.0)
+                    .x_axis_label(tr_args("fan-curve-axis-temp", &[("unit", TempUnit::Celsius.symbol())]))
+                    .y_axis_label(tr("fan-curve-axis-duty"))
+                    .allow_zoom(false)
+                    .allow_drag(false)
+                    .allow_scroll(false)
+                    .allow_boxed_zoom(false)
+                    .allow_double_click_reset(false)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(line);
+                        plot_ui.points(markers);
+                        plot_ui.vline(now);
+
+                        let response = plot_ui.response().clone();
+                        let pointer = plot_ui.pointer_coordinate();
+                        let nearest = response.hover_pos().and_then(|pos| {
+                            editor
+                                .curve
+                                .points
+                                .iter()
+                                .map(|p| plot_ui.screen_from_plot([p.temp as f64, p.duty as f64].into()).distance(pos))
+                                .enumerate()
+                                .filter(|&(_, distance)| distance <= CURVE_GRAB_RADIUS)
+                                .min_by(|a, b| a.1.total_cmp(&b.1))
+                                .map(|(index, _)| index)
+                        });
+
+                        if response.drag_started() {
+                            editor.dragging = nearest;
+                        }
+                        if let (Some(index), Some(pointer), true) = (editor.dragging, pointer, response.dragged()) {
+                            editor.drag_to(index, pointer.x as f32, pointer.y as f32);
+                        }
+                        if response.drag_stopped() {
+                            editor.dragging = None;
+                        }
+                        if response.double_clicked() {
+                            if let (None, Some(pointer)) = (nearest, pointer) {
+                                editor.insert(pointer.x as f32);
+                            }
+                        }
+                        if let (true, Some(index)) = (response.secondary_clicked(), nearest) {
+                            editor.remove(index);
+                        }
+                    });
+
+                ui.label(
+                    egui::RichText::new(tr_args("fan-curve-now", &[
+                        ("temp", &units.temp(cpu_temp)),
+                        ("duty", &editor.curve.duty_at(cpu_temp).to_string()),
+                    ]))
+                    .size(11.0),
+                );
+                ui.horizontal(|ui| {
+                    ui.label(tr("fan-curve-hysteresis"));
+                    ui.add(egui::Slider::new(&mut editor.curve.hysteresis, 0.0..=fan_curve::MAX_HYSTERESIS).step_by(1.0).suffix(" °C"));
+                });
+
+                if let Some(error) = &editor.error {
+                    ui.colored_label(theme.zone_color(ThermalZone::Critical), error);
+                }
+                ui.horizontal(|ui| {
+                    save = focus_ring(ui.button(tr("mode-save"))).clicked();
+                    cancel = focus_ring(ui.button(tr("mode-cancel"))).clicked();
+                });
+            });
+
+        if save {
+            match fan_curve::save_privileged(&editor.curve) {
+                Ok(()) => {
+                    // Boost leaves the channel to an enabled curve
+                    fan::redetect();
+                    let key = if editor.curve.enabled { "status-fan-curve-on" } else { "status-fan-curve-off" };
+                    self.set_status(tr(key).to_string());
+                    return;
+                }
+                Err(e) => editor.error = Some(tr_args("status-error", &[("error", &e.to_string())])),
+            }
+        }
+        if open && !cancel {
+            self.fan_curve_editor = Some(editor);
+        }
+    }
+
+    /// Write history to a CSV file in the user's home directory
+    fn export_history(&mut self) {
+        match write_history_csv(&self.history, self.units().temp) {
+            Ok(path) => self.set_status(tr_args("status-exported", &[("path", &path.display().to_string())])),
+            Err(e) => self.set_status(tr_args("status-error", &[("error", &e.to_string())])),
+        }
+    }
+
+    /// Theme currently in effect
+    fn theme(&self) -> Theme {
+        self.config.theme.resolve(self.system_theme)
+    }
+
+    /// Apply a new theme preference and persist it
+    fn set_theme_preference(&mut self, pref: ThemePreference) {
+        self.config.theme = pref;
+        if pref == ThemePreference::System {
+            self.system_theme = read_system_theme();
+            self.last_theme_check = Instant::now();
+        }
+        if let Err(e) = self.config.save() {
+            self.set_status(tr_args("status-error", &[("error", &e.to_string())]));
+        }
+    }
+
+    /// Warning banner for power managers that fight over the same knobs
+    fn render_conflicts(&mut self, ui: &mut egui::Ui, font_size: f32) {
+        let via_ppd = self.via_ppd();
+        let shown: Vec<Conflict> = self
+            .conflicts
+            .iter()
+            .filter(|c| !(via_ppd && c.manager == Manager::PowerProfilesDaemon))
+            .cloned()
+            .collect();
+        if self.conflicts_dismissed || (shown.is_empty() && !via_ppd) {
+            return;
+        }
+
+        let warning = self.theme().zone_color(ThermalZone::Warm);
+        egui::Frame::group(ui.style()).stroke(egui::Stroke::new(1.0, warning)).show(ui, |ui| {
+            for conflict in &shown {
+                let text = ui.label(egui::RichText::new(format!("⚠ {}", conflict.message())).size(font_size).color(warning));
+                live_region(&text);
+                text.on_hover_text(tr_args("hint-conflict", &[("command", &conflict.remedy())]));
+            }
+            ui.horizontal_wrapped(|ui| {
+                if via_ppd {
+                    ui.label(egui::RichText::new(tr("conflict-ppd-cooperating")).size(font_size).color(self.theme().muted()));
+                    if focus_ring(ui.small_button(tr("conflict-ppd-stop"))).clicked() {
+                        self.set_cooperate_ppd(false);
+                    }
+                } else if conflicts::ppd_active(&self.conflicts) {
+                    let button = focus_ring(ui.small_button(tr("conflict-ppd-cooperate")));
+                    if button.on_hover_text(tr("hint-ppd-cooperate")).clicked() {
+                        self.set_cooperate_ppd(true);
+                    }
+                }
+                if !shown.is_empty() && focus_ring(ui.small_button(tr("conflict-dismiss"))).clicked() {
+                    self.conflicts_dismissed = true;
+                }
+            });
+        });
+    }
 
     /// Render temperatures - adaptive version
     fn render_temperatures_adaptive(&self, ui: &mut egui::Ui, is_medium: bool) {
         let zone = self.state.thermal_zone();
-        let color = Self::zone_color(zone);
+        let theme = self.theme();
+        let color = theme.zone_color(zone);
+        let muted = theme.muted();
         let font_size = if is_medium { 24.0 } else { 18.0 };
         let label_size = if is_medium { 11.0 } else { 9.0 };
+        let units = self.units();
 
         ui.horizontal_wrapped(|ui| {
             // CPU
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("CPU").size(label_size).color(egui::Color32::GRAY));
-                ui.label(egui::RichText::new(format!("{:.0}°", self.state.cpu_temp))
+                ui.label(egui::RichText::new(tr("label-cpu")).size(label_size).color(muted));
+                let reading = ui.label(egui::RichText::new(units.temp(self.state.cpu_temp))
                     .size(font_size).color(color).strong());
+                describe(&reading, tr_args("a11y-cpu-temp", &[("value", &units.temp_spoken(self.state.cpu_temp))]));
             });
             ui.add_space(10.0);
             // Keyboard
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("KBD").size(label_size).color(egui::Color32::GRAY));
-                ui.label(egui::RichText::new(format!("{:.0}°", self.state.keyboard_temp))
+                ui.label(egui::RichText::new(tr("label-kbd")).size(label_size).color(muted));
+                let reading = ui.label(egui::RichText::new(units.temp(self.state.keyboard_temp))
                     .size(font_size).color(color).strong());
+                describe(&reading, tr_args("a11y-kbd-temp", &[("value", &units.temp_spoken(self.state.keyboard_temp))]));
             });
             ui.add_space(10.0);
             // Zone label
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("Zone").size(label_size).color(egui::Color32::GRAY));
-                ui.label(egui::RichText::new(zone.label()).size(label_size + 2.0).color(color));
+                ui.label(egui::RichText::new(tr("label-zone")).size(label_size).color(muted));
+                let reading = ui.label(egui::RichText::new(zone.label()).size(label_size + 2.0).color(color));
+                describe(&reading, tr_args("a11y-zone", &[("zone", zone.label())]));
             });
         });
     }
@@ -225,25 +1260,75 @@ impl ThermalApp {
     fn render_performance_adaptive(&self, ui: &mut egui::Ui, is_medium: bool) {
         let font_size = if is_medium { 20.0 } else { 16.0 };
         let label_size = if is_medium { 11.0 } else { 9.0 };
-        let mode_color = Self::mode_color(self.state.mode);
+        let theme = self.theme();
+        let muted = theme.muted();
+        let mode_color = theme.mode_color(&self.state.mode);
 
         ui.horizontal_wrapped(|ui| {
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("Perf").size(label_size).color(egui::Color32::GRAY));
-                ui.label(egui::RichText::new(format!("{}%", self.state.perf_pct))
+                ui.label(egui::RichText::new(tr("label-perf")).size(label_size).color(muted));
+                let reading = ui.label(egui::RichText::new(format!("{}%", self.state.perf_pct))
                     .size(font_size).strong());
+                describe(&reading, tr_args("a11y-perf", &[("pct", &self.state.perf_pct.to_string())]));
+                reading.on_hover_text(tr_args("hint-driver", &[("driver", &self.state.cpufreq_driver)]));
             });
             ui.add_space(10.0);
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("Freq").size(label_size).color(egui::Color32::GRAY));
-                ui.label(egui::RichText::new(format!("{:.1}G", self.state.current_freq_ghz()))
+                ui.label(egui::RichText::new(tr("label-freq")).size(label_size).color(muted));
+                let reading = ui.label(egui::RichText::new(self.freq_label(self.state.current_freq_mhz, self.state.current_freq_ghz()))
                     .size(font_size).strong());
+                describe(&reading, tr_args("a11y-freq", &[("value", &self.units().freq_spoken(self.state.current_freq_mhz))]));
             });
+            let power = self.state.power;
+            if power.total().is_some() || power.restricted {
+                ui.add_space(10.0);
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new(tr("label-power")).size(label_size).color(muted));
+                    let text = power.total().map_or("—".to_string(), |watts| format!("{:.1} W", watts));
+                    let reading = ui.label(egui::RichText::new(text).size(font_size).strong());
+                    let mut hint = match power.total() {
+                        Some(_) => power.breakdown(),
+                        None => tr("hint-power-restricted").to_string(),
+                    };
+                    if !self.state.power_limits.is_empty() {
+                        hint.push('\n');
+                        hint.push_str(&tr_args("hint-power-limits", &[("limits", &self.state.power_limits.summary())]));
+                    }
+                    describe(&reading, format!("{}: {}", tr("label-power"), hint));
+                    reading.on_hover_text(hint);
+                });
+            }
+            let supply = &self.state.power_supply;
+            if let Some(pct) = supply.battery_pct {
+                ui.add_space(10.0);
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new(tr("label-battery")).size(label_size).color(muted));
+                    let reading = ui.label(egui::RichText::new(format!("{}%", pct)).size(font_size).strong());
+                    let hint = match supply.source() {
+                        Some(source) => format!("{}: {}", source.label(), supply.summary()),
+                        None => supply.summary(),
+                    };
+                    describe(&reading, format!("{}: {}", tr("label-battery"), hint));
+                    reading.on_hover_text(hint);
+                });
+            }
             ui.add_space(10.0);
             ui.vertical(|ui| {
-                ui.label(egui::RichText::new("Mode").size(label_size).color(egui::Color32::GRAY));
-                ui.label(egui::RichText::new(self.state.mode.label())
+                ui.label(egui::RichText::new(tr("label-mode")).size(label_size).color(muted));
+                let reading = ui.label(egui::RichText::new(self.state.mode.label())
                     .size(label_size + 2.0).color(mode_color).strong());
+                describe(&reading, tr_args("a11y-mode", &[("mode", &self.state.mode.label())]));
+                if !self.state.mode_drift.is_empty() {
+                    let mut hint = tr_args("hint-mode-drift", &[("mode", &self.state.mode.label())]);
+                    for result in &self.state.mode_drift {
+                        hint.push('\n');
+                        hint.push_str(&drift_text(result));
+                    }
+                    let warning = ui.label(egui::RichText::new(tr("mode-drift"))
+                        .size(label_size).color(theme.zone_color(ThermalZone::Warm)));
+                    describe(&warning, hint.clone());
+                    warning.on_hover_text(hint);
+                }
             });
         });
     }
@@ -253,26 +1338,244 @@ impl ThermalApp {
         let button_width = if available_width > 600.0 { 90.0 } else { 70.0 };
         let button_height = if available_width > 600.0 { 28.0 } else { 24.0 };
         let font_size = if available_width > 600.0 { 11.0 } else { 9.0 };
+        let theme = self.theme();
 
         ui.horizontal_wrapped(|ui| {
-            for mode in Mode::all() {
+            for (index, mode) in Mode::available().iter().enumerate() {
                 let is_current = self.state.mode == *mode;
-                let color = Self::mode_color(*mode);
+                let color = theme.mode_color(mode);
 
                 let button = egui::Button::new(
                     egui::RichText::new(mode.label())
                         .size(font_size)
-                        .color(if is_current { egui::Color32::BLACK } else { color }),
+                        .color(if is_current { theme.on_accent() } else { color }),
                 )
                 .fill(if is_current { color } else { egui::Color32::TRANSPARENT })
                 .stroke(egui::Stroke::new(1.0, color))
                 .min_size(egui::vec2(button_width, button_height));
 
-                if ui.add(button).clicked() && !is_current {
-                    self.change_mode(*mode);
+                let response = focus_ring(ui.add(button));
+                toggle_state(&response, is_current, tr_args("a11y-mode-button", &[("mode", &mode.label())]));
+                let hint = match index {
+                    0..=8 => format!("{} [{}]", mode.description(), index + 1),
+                    _ => mode.description(),
+                };
+                let response = response.on_hover_text(hint);
+                // Clicking the current mode re-applies it after drift
+                if response.clicked() && (!is_current || !self.state.mode_drift.is_empty()) {
+                    self.change_mode(mode);
+                }
+                if let Mode::Custom(name) = mode {
+                    response.context_menu(|ui| {
+                        if ui.button(tr("mode-edit")).clicked() {
+                            self.mode_editor = modes::find(name).map(ModeEditor::edit);
+                            ui.close_menu();
+                        }
+                        if ui.button(tr("mode-delete")).clicked() {
+                            self.delete_mode(mode);
+                            ui.close_menu();
+                        }
+                    });
+                }
+            }
+
+            let add = egui::Button::new(egui::RichText::new("+").size(font_size))
+                .min_size(egui::vec2(button_height, button_height));
+            let response = focus_ring(ui.add(add));
+            describe(&response, tr("hint-mode-new").to_string());
+            if response.on_hover_text(tr("hint-mode-new")).clicked() {
+                self.mode_editor = Some(ModeEditor::create(&self.state));
+            }
+        });
+
+        // Modes to switch to on plugging in and unplugging
+        if self.state.power_supply.has_battery() {
+            ui.horizontal_wrapped(|ui| {
+                for source in [PowerSource::Battery, PowerSource::Ac] {
+                    let current = self.config.mode_for_source(source).map(String::from);
+                    let mut selected = current.clone();
+                    let text = selected.as_deref().map_or(tr("mode-unchanged").to_string(), |name| Mode::from_name(name).label());
+                    ui.label(egui::RichText::new(tr_args("label-source-mode", &[("source", source.label())])).size(font_size));
+                    let combo = egui::ComboBox::from_id_salt(("source_mode", source))
+                        .selected_text(egui::RichText::new(text).size(font_size))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut selected, None, tr("mode-unchanged"));
+                            for mode in Mode::available() {
+                                ui.selectable_value(&mut selected, Some(mode.command().to_string()), mode.label());
+                            }
+                        });
+                    describe(&combo.response, tr_args("label-source-mode", &[("source", source.label())]));
+                    combo.response.on_hover_text(tr("hint-source-mode"));
+                    if selected != current {
+                        self.set_source_mode(source, selected);
+                    }
+                }
+            });
+        }
+
+        // Mode while the lid is closed
+        if self.has_lid {
+            ui.horizontal_wrapped(|ui| {
+                let current = self.config.lid_mode.clone();
+                let mut selected = current.clone();
+                let text = selected.as_deref().map_or(tr("mode-unchanged").to_string(), |name| Mode::from_name(name).label());
+                ui.label(egui::RichText::new(tr("label-lid-mode")).size(font_size));
+                let combo = egui::ComboBox::from_id_salt("lid_mode")
+                    .selected_text(egui::RichText::new(text).size(font_size))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut selected, None, tr("mode-unchanged"));
+                        for mode in Mode::available() {
+                            ui.selectable_value(&mut selected, Some(mode.command().to_string()), mode.label());
+                        }
+                    });
+                describe(&combo.response, tr("label-lid-mode").to_string());
+                combo.response.on_hover_text(tr("hint-lid-mode"));
+                if selected != current {
+                    self.set_lid_mode(selected);
+                }
+            });
+        }
+    }
+
+    /// Platform profile selector, or a plain label when the firmware
+    /// advertises no choices
+    fn render_platform_profile(&mut self, ui: &mut egui::Ui, font_size: f32) {
+        let current = self.state.platform_profile.clone();
+        let text = egui::RichText::new(profile_label(&current)).size(font_size).color(self.theme().muted());
+        if self.state.platform_profile_choices.is_empty() {
+            ui.label(text);
+            return;
+        }
+
+        let mut selected = current.clone();
+        let combo = egui::ComboBox::from_id_salt("platform_profile")
+            .selected_text(text)
+            .show_ui(ui, |ui| {
+                for choice in &self.state.platform_profile_choices {
+                    ui.selectable_value(&mut selected, choice.clone(), profile_label(choice));
+                }
+            });
+        describe(&combo.response, tr_args("a11y-profile", &[("profile", profile_label(&current))]));
+        focus_ring(combo.response).on_hover_text(tr("hint-profile"));
+        if selected != current {
+            let success = tr_args("status-profile", &[("profile", profile_label(&selected))]);
+            self.report_write(set_platform_profile(&selected, self.via_ppd()), success);
+        }
+    }
+
+    /// Render EPP, turbo and minimum performance controls
+    fn render_tuning_adaptive(&mut self, ui: &mut egui::Ui, is_wide: bool) {
+        let font_size = if is_wide { 11.0 } else { 9.0 };
+        let slider_width = if is_wide { 120.0 } else { 80.0 };
+        let muted = self.theme().muted();
+
+        ui.horizontal_wrapped(|ui| {
+            // Energy Performance Preference, only where the driver exposes it
+            if let Some(current) = self.state.energy_preference.clone() {
+                ui.label(egui::RichText::new(tr("label-epp")).size(font_size).color(muted));
+                let mut selected = current.clone();
+                let combo = egui::ComboBox::from_id_salt("epp")
+                    .selected_text(energy_preference_label(&current))
+                    .show_ui(ui, |ui| {
+                        for choice in &self.state.energy_preferences {
+                            ui.selectable_value(&mut selected, choice.clone(), energy_preference_label(choice));
+                        }
+                    });
+                describe(&combo.response, format!("{}: {}", tr("label-epp"), energy_preference_label(&current)));
+                focus_ring(combo.response).on_hover_text(tr("hint-epp"));
+                if selected != current {
+                    self.change_energy_preference(&selected);
+                }
+                ui.add_space(10.0);
+            }
+
+            // Turbo switch
+            if let Some(turbo) = self.state.turbo {
+                let mut enabled = turbo;
+                let label = egui::RichText::new(tr("label-turbo")).size(font_size);
+                let response = ui.checkbox(&mut enabled, label);
+                toggle_state(&response, turbo, tr("a11y-turbo").to_string());
+                if focus_ring(response).on_hover_text(tr("hint-turbo")).changed() {
+                    self.change_turbo(enabled);
                 }
+                ui.add_space(10.0);
+            }
+
+            // Minimum performance, applied when the slider is released
+            ui.label(egui::RichText::new(tr("label-min-perf")).size(font_size).color(muted));
+            let mut pct = self.min_perf_edit.unwrap_or(self.state.min_perf_pct);
+            let slider = egui::Slider::new(&mut pct, 0..=self.state.perf_pct.max(1)).suffix("%").text("");
+            let response = focus_ring(ui.add_sized([slider_width, 20.0], slider)).on_hover_text(tr("hint-min-perf"));
+            describe(&response, tr_args("a11y-min-perf", &[("pct", &pct.to_string())]));
+            if response.changed() {
+                self.min_perf_edit = Some(pct);
+            }
+            if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                if let Some(pct) = self.min_perf_edit.take() {
+                    self.change_min_perf(pct);
+                }
+            }
+        });
+    }
+
+    /// Processes using the most CPU since the last update, with actions
+    fn render_heat(&mut self, ui: &mut egui::Ui, is_wide: bool) {
+        let font_size = if is_wide { 11.0 } else { 9.0 };
+        let muted = self.theme().muted();
+        // Stopped processes use no CPU, so keep them listed to continue them
+        let mut rows: Vec<(ProcessStat, Option<f32>, Option<f32>)> =
+            self.contributors.iter().map(|c| (c.process.clone(), Some(c.cpu_pct), c.watts)).collect();
+        for process in &self.stopped {
+            if !rows.iter().any(|(p, _, _)| p.pid == process.pid) {
+                rows.push((process.clone(), None, None));
+            }
+        }
+        if rows.is_empty() {
+            ui.label(egui::RichText::new(tr("heat-measuring")).size(font_size).color(muted));
+            return;
+        }
+
+        let mut action = None;
+        egui::Grid::new("heat").num_columns(5).spacing([12.0, 4.0]).striped(true).show(ui, |ui| {
+            for heading in ["heat-process", "heat-pid", "heat-cpu", "heat-power"] {
+                ui.label(egui::RichText::new(tr(heading)).size(font_size).color(muted));
+            }
+            ui.end_row();
+            for (process, cpu_pct, watts) in &rows {
+                ui.label(egui::RichText::new(&process.name).size(font_size));
+                ui.label(egui::RichText::new(process.pid.to_string()).size(font_size).color(muted));
+                let cpu = cpu_pct.map_or_else(|| "-".to_string(), |pct| format!("{:.0}%", pct));
+                ui.label(egui::RichText::new(cpu).size(font_size));
+                let power = watts.map_or_else(|| "-".to_string(), |w| format!("≈ {:.1} W", w));
+                ui.label(egui::RichText::new(power).size(font_size)).on_hover_text(tr("hint-heat-power"));
+                ui.horizontal(|ui| {
+                    let allowed = processes::can_control(process.pid);
+                    let hint = |text: String| if allowed { text } else { tr("hint-process-other-user").to_string() };
+                    let can_renice = processes::reniced(process.nice).is_some();
+                    let renice = ui.add_enabled(allowed && can_renice, egui::Button::new(tr("process-renice")).small());
+                    describe(&renice, format!("{}: {}", tr("process-renice"), process.name));
+                    let renice_key = if can_renice { "hint-process-renice" } else { "hint-process-nice-max" };
+                    let renice_hint = tr_args(renice_key, &[("nice", &process.nice.to_string())]);
+                    if focus_ring(renice).on_hover_text(hint(renice_hint)).clicked() {
+                        action = Some((process.clone(), ProcessAction::Renice));
+                    }
+                    let (label, hint_key, next) = if process.stopped {
+                        (tr("process-continue"), "hint-process-continue", ProcessAction::Continue)
+                    } else {
+                        (tr("process-stop"), "hint-process-stop", ProcessAction::Stop)
+                    };
+                    let toggle = ui.add_enabled(allowed, egui::Button::new(label).small());
+                    describe(&toggle, format!("{}: {}", label, process.name));
+                    if focus_ring(toggle).on_hover_text(hint(tr(hint_key).to_string())).clicked() {
+                        action = Some((process.clone(), next));
+                    }
+                });
+                ui.end_row();
             }
         });
+        if let Some((process, action)) = action {
+            self.process_action(&process, action);
+        }
     }
 
     /// Render target temperature - adaptive version
@@ -280,34 +1583,71 @@ impl ThermalApp {
         let slider_width = if is_wide { 120.0 } else { 80.0 };
         let font_size = if is_wide { 11.0 } else { 9.0 };
 
+        let units = self.units();
+        let theme = self.theme();
+
         ui.horizontal_wrapped(|ui| {
-            let slider = egui::Slider::new(&mut self.target_temp, 40.0..=80.0)
-                .suffix("°")
+            // Slider works in the display unit; target stays in Celsius
+            let mut shown = units.temp_value(self.target_temp).round();
+            let range = units.temp_value(TARGET_MIN).round()..=units.temp_value(TARGET_MAX).round();
+            let slider = egui::Slider::new(&mut shown, range)
+                .suffix(units.temp.symbol())
                 .step_by(1.0)
                 .text("");
-            ui.add_sized([slider_width, 20.0], slider);
+            let response = focus_ring(ui.add_sized([slider_width, 20.0], slider)).on_hover_text("[+ / −]");
+            if response.changed() {
+                self.target_temp = units.temp.to_celsius(shown);
+            }
 
             // Auto button
-            let auto_color = if self.auto_control {
-                egui::Color32::from_rgb(100, 220, 100)
-            } else {
-                egui::Color32::GRAY
-            };
-            if ui.add(egui::Button::new(
-                egui::RichText::new(if self.auto_control { "AUTO" } else { "OFF" })
+            let auto_color = if self.auto_control { theme.good() } else { theme.muted() };
+            let auto_button = ui.add(egui::Button::new(
+                egui::RichText::new(if self.auto_control { tr("target-auto") } else { tr("target-off") })
                     .size(font_size).color(auto_color)
-            ).min_size(egui::vec2(40.0, 20.0))).clicked() {
-                self.auto_control = !self.auto_control;
-                self.set_status(if self.auto_control { "Auto ON".into() } else { "Auto OFF".into() });
+            ).min_size(egui::vec2(40.0, 20.0)));
+            toggle_state(&auto_button, self.auto_control, tr("a11y-auto-control").to_string());
+            if focus_ring(auto_button).on_hover_text("[A]").clicked() {
+                self.toggle_auto_control();
+            }
+
+            // Actuator choice only where there is more than one
+            let choices: Vec<Actuator> = Actuator::all().iter().copied().filter(|a| self.actuator_available(*a)).collect();
+            if choices.len() > 1 {
+                let mut actuator = self.config.actuator;
+                let combo = egui::ComboBox::from_id_salt("control_actuator")
+                    .selected_text(egui::RichText::new(actuator.label()).size(font_size))
+                    .show_ui(ui, |ui| {
+                        for choice in &choices {
+                            ui.selectable_value(&mut actuator, *choice, choice.label());
+                        }
+                    });
+                describe(&combo.response, tr("a11y-actuator").to_string());
+                combo.response.on_hover_text(tr("hint-actuator"));
+                if actuator != self.config.actuator {
+                    self.set_actuator(actuator);
+                }
+            }
+
+            // Cgroups the cgroup actuator throttles
+            if self.config.actuator == Actuator::Cgroups && choices.contains(&Actuator::Cgroups) {
+                let field = egui::TextEdit::singleline(&mut self.throttle_text)
+                    .hint_text(tr("throttle-placeholder"))
+                    .desired_width(140.0);
+                let response = ui.add(field);
+                describe(&response, tr("a11y-throttle").to_string());
+                if focus_ring(response).on_hover_text(tr("hint-throttle")).lost_focus() {
+                    let groups = self.throttle_text.split(',').map(str::trim).filter(|g| !g.is_empty()).map(String::from).collect();
+                    self.set_throttle_groups(groups);
+                }
             }
 
             // Status
             if self.state.cpu_temp > self.target_temp {
-                ui.label(egui::RichText::new(format!("+{:.0}°", self.state.cpu_temp - self.target_temp))
-                    .size(font_size).color(egui::Color32::from_rgb(255, 150, 100)));
+                ui.label(egui::RichText::new(units.temp_delta(self.state.cpu_temp - self.target_temp))
+                    .size(font_size).color(theme.warn()));
             } else {
-                ui.label(egui::RichText::new("OK").size(font_size)
-                    .color(egui::Color32::from_rgb(100, 220, 100)));
+                ui.label(egui::RichText::new(tr("target-ok")).size(font_size)
+                    .color(theme.good()));
             }
         });
     }
@@ -315,56 +1655,102 @@ impl ThermalApp {
     /// Render fan control - adaptive version
     fn render_fan_control_adaptive(&mut self, ui: &mut egui::Ui, is_wide: bool) {
         let font_size = if is_wide { 11.0 } else { 9.0 };
-        let fan_active = self.state.fan_boost || self.fan_boost_manual;
-        let fan_color = if fan_active {
-            egui::Color32::from_rgb(255, 150, 100)
-        } else {
-            egui::Color32::GRAY
-        };
+        let theme = self.theme();
 
         ui.horizontal_wrapped(|ui| {
-            if ui.add(egui::Button::new(
-                egui::RichText::new(if fan_active { "BOOST" } else { "NORMAL" })
-                    .size(font_size)
-                    .color(if fan_active { egui::Color32::BLACK } else { fan_color })
-            )
-            .fill(if fan_active { fan_color } else { egui::Color32::TRANSPARENT })
-            .stroke(egui::Stroke::new(1.0, fan_color))
-            .min_size(egui::vec2(60.0, 20.0))).clicked() {
-                self.fan_boost_manual = !self.fan_boost_manual;
-                let _ = set_fan_boost(self.fan_boost_manual);
-                self.set_status(if self.fan_boost_manual { "Fan boost".into() } else { "Fan auto".into() });
-            }
-
-            if is_wide {
-                ui.label(egui::RichText::new("Max cooling").size(9.0).color(egui::Color32::DARK_GRAY));
+            if self.state.fan_boost.is_some() {
+                self.render_fan_boost(ui, font_size);
+            }
+            self.render_fan_rpm(ui, font_size);
+
+            // Curves need a PWM channel the service can drive; an enabled
+            // curve takes it from boost, so the editor stays to switch it off
+            if self.has_pwm_channel
+                && focus_ring(ui.small_button(tr("fan-curve-edit"))).on_hover_text(tr("hint-fan-curve")).clicked()
+            {
+                self.fan_curve_editor = Some(FanCurveEditor::new(fan_curve::load()));
+            }
+
+            if is_wide && self.state.fan_boost.is_some() {
+                ui.label(egui::RichText::new(tr("fan-hint")).size(9.0).color(theme.faint()));
             }
         });
     }
 
+    /// Fan boost toggle
+    fn render_fan_boost(&mut self, ui: &mut egui::Ui, font_size: f32) {
+        // Always the state read back from the driver, never what was requested
+        let fan_active = self.state.fan_boost == Some(true);
+        let theme = self.theme();
+        let fan_color = if fan_active { theme.warn() } else { theme.muted() };
+        let fan_button = ui.add(egui::Button::new(
+            egui::RichText::new(if fan_active { tr("fan-boost") } else { tr("fan-normal") })
+                .size(font_size)
+                .color(if fan_active { theme.on_accent() } else { fan_color })
+        )
+        .fill(if fan_active { fan_color } else { egui::Color32::TRANSPARENT })
+        .stroke(egui::Stroke::new(1.0, fan_color))
+        .min_size(egui::vec2(60.0, 20.0)));
+        toggle_state(&fan_button, fan_active, tr("a11y-fan-boost").to_string());
+        let mut hint = format!("[F] {}", self.state.fan_backend);
+        if !self.fan_boost.requesters().is_empty() {
+            hint.push('\n');
+            hint.push_str(&tr_args("hint-fan-requesters", &[("who", &self.boost_requesters_text())]));
+        }
+        if focus_ring(fan_button).on_hover_text(hint).clicked() {
+            self.toggle_fan_boost();
+        }
+    }
+
+    /// Who holds fan boost on, e.g. "you, auto control"
+    fn boost_requesters_text(&self) -> String {
+        let names: Vec<&str> = self
+            .fan_boost
+            .requesters()
+            .iter()
+            .map(|requester| match requester {
+                BoostRequester::User => tr("fan-by-user"),
+                BoostRequester::AutoControl => tr("fan-by-auto"),
+                BoostRequester::Daemon => tr("fan-by-mode"),
+                BoostRequester::Rule => tr("fan-by-rule"),
+            })
+            .collect();
+        names.join(", ")
+    }
+
+    /// Fan speed next to the fan control, if a driver reports it
+    fn render_fan_rpm(&self, ui: &mut egui::Ui, font_size: f32) {
+        if let Some(rpm) = self.state.fan_rpm {
+            let text = tr_args("fan-rpm", &[("rpm", &rpm.to_string())]);
+            ui.label(egui::RichText::new(text).size(font_size).monospace());
+        }
+    }
+
     /// Render history graph - adaptive version
     fn render_history_adaptive(&self, ui: &mut egui::Ui, target_temp: f32, height: f32) {
         if self.history.is_empty() {
-            ui.label("Collecting data...");
+            ui.label(tr("history-collecting"));
             return;
         }
 
-        let cpu_line = Line::new(self.history.cpu_points())
-            .name("CPU")
-            .color(egui::Color32::from_rgb(255, 100, 100))
+        let unit = self.units().temp;
+        let theme = self.theme();
+        let cpu_line = Line::new(self.history.cpu_points(unit))
+            .name(tr("history-cpu"))
+            .color(theme.zone_color(ThermalZone::Critical))
             .width(2.0);
 
-        let kbd_line = Line::new(self.history.kbd_points())
-            .name("Kbd")
-            .color(egui::Color32::from_rgb(100, 200, 255))
+        let kbd_line = Line::new(self.history.kbd_points(unit))
+            .name(tr("history-kbd"))
+            .color(theme.zone_color(ThermalZone::Cool))
             .width(2.0);
 
         let target_points: Vec<[f64; 2]> = (0..HISTORY_CAPACITY)
-            .map(|i| [i as f64, target_temp as f64])
+            .map(|i| [i as f64, unit.convert(target_temp) as f64])
             .collect();
         let target_line = Line::new(PlotPoints::new(target_points))
-            .name("Target")
-            .color(egui::Color32::from_rgb(255, 200, 100))
+            .name(tr("history-target"))
+            .color(theme.mode_color(&Mode::Auto))
             .width(1.0)
             .style(egui_plot::LineStyle::dashed_loose());
 
@@ -372,8 +1758,8 @@ impl ThermalApp {
             .height(height)
             .show_axes(true)
             .show_grid(true)
-            .include_y(30.0)
-            .include_y(80.0)
+            .include_y(unit.convert(30.0))
+            .include_y(unit.convert(80.0))
             .allow_zoom(false)
             .allow_drag(false)
             .allow_scroll(false)
@@ -383,31 +1769,186 @@ impl ThermalApp {
                 plot_ui.line(kbd_line);
                 plot_ui.line(target_line);
             });
+
+        // RPM and watts do not share a scale with temperatures, so each gets its own strip
+        let strip_height = (height / 3.0).max(40.0);
+        if self.history.has_fan() {
+            let fan_line = Line::new(self.history.fan_points())
+                .name(tr("history-fan"))
+                .color(theme.warn())
+                .width(1.5);
+            history_strip(ui, "fan_history", fan_line, strip_height);
+        }
+        if self.history.has_power() {
+            let power_line = Line::new(self.history.power_points())
+                .name(tr("history-power"))
+                .color(theme.zone_color(ThermalZone::Hot))
+                .width(1.5);
+            history_strip(ui, "power_history", power_line, strip_height);
+        }
     }
 
     /// Render status bar
     fn render_status(&mut self, ui: &mut egui::Ui) {
+        let theme = self.theme();
         ui.horizontal(|ui| {
             // Status message (auto-clear after 3 seconds)
-            if let Some((msg, time)) = &self.status_message {
-                if time.elapsed() < Duration::from_secs(3) {
-                    ui.label(egui::RichText::new(msg).size(12.0).color(egui::Color32::YELLOW));
-                } else {
-                    self.status_message = None;
-                }
+            if self.status_message.as_ref().is_some_and(|(_, time)| time.elapsed() >= Duration::from_secs(3)) {
+                self.status_message = None;
             }
+            // Always present so screen readers track it as a live region
+            let msg = self.status_message.as_ref().map(|(msg, _)| msg.as_str()).unwrap_or_default();
+            let status = ui.label(egui::RichText::new(msg).size(12.0).color(theme.status()));
+            live_region(&status);
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.label(
-                    egui::RichText::new("Thermal Monitor v1.3.0")
+                    egui::RichText::new(tr_args("app-version", &[("version", env!("CARGO_PKG_VERSION"))]))
                         .size(11.0)
-                        .color(egui::Color32::DARK_GRAY),
+                        .color(theme.faint()),
                 );
+
+                // Theme selector
+                let mut pref = self.config.theme;
+                egui::ComboBox::from_id_salt("theme")
+                    .selected_text(pref.label())
+                    .show_ui(ui, |ui| {
+                        for option in ThemePreference::all() {
+                            ui.selectable_value(&mut pref, *option, option.label());
+                        }
+                    });
+                if pref != self.config.theme {
+                    self.set_theme_preference(pref);
+                }
+
+                // Control held back while locked or asleep
+                if self.paused {
+                    let label = ui.label(egui::RichText::new(tr("paused-label")).size(11.0).color(theme.warn()));
+                    describe(&label, tr("hint-paused").to_string());
+                    label.on_hover_text(tr("hint-paused"));
+                }
+
+                // Rules indicator with what fired recently
+                if !self.rules.rules.is_empty() {
+                    let text = if self.rules.rules.dry_run { tr("rules-label-dry-run") } else { tr("rules-label") };
+                    let label = ui.label(egui::RichText::new(text).size(11.0).color(theme.faint()));
+                    let mut hint = tr_args("hint-rules", &[("count", &self.rules.rules.len().to_string())]);
+                    for line in self.rules.log() {
+                        hint.push('\n');
+                        hint.push_str(line);
+                    }
+                    describe(&label, hint.clone());
+                    label.on_hover_text(hint);
+                }
+
+                let help = ui.small_button("?");
+                describe(&help, tr("shortcut-title").to_string());
+                if help.on_hover_text(tr("shortcut-title")).clicked() {
+                    self.show_shortcut_help = !self.show_shortcut_help;
+                }
+
+                // Unit toggles
+                let units = self.units();
+                let freq_toggle = ui.small_button(units.freq.symbol());
+                describe(&freq_toggle, format!("{}: {}", tr("hint-freq-unit"), units.freq.spoken_name()));
+                if freq_toggle.on_hover_text(tr("hint-freq-unit")).clicked() {
+                    self.set_units(Units { freq: units.freq.toggled(), ..units });
+                }
+                let temp_toggle = ui.small_button(units.temp.symbol());
+                describe(&temp_toggle, format!("{}: {}", tr("hint-temp-unit"), units.temp.spoken_name()));
+                if temp_toggle.on_hover_text(tr("hint-temp-unit")).clicked() {
+                    self.set_units(Units { temp: units.temp.toggled(), ..units });
+                }
             });
         });
     }
 }
 
+/// Small plot under the temperature history, aligned with its time axis
+fn history_strip(ui: &mut egui::Ui, id: &str, line: Line, height: f32) {
+    Plot::new(id)
+        .height(height)
+        .show_axes(true)
+        .show_grid(true)
+        .include_x(0.0)
+        .include_x((HISTORY_CAPACITY - 1) as f64)
+        .include_y(0.0)
+        .allow_zoom(false)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .legend(egui_plot::Legend::default().position(egui_plot::Corner::RightTop))
+        .show(ui, |plot_ui| plot_ui.line(line));
+}
+
+/// Give a widget a spoken description for screen readers
+fn describe(response: &egui::Response, text: String) {
+    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Label, true, &text));
+}
+
+/// Expose a toggle button's pressed state to screen readers
+fn toggle_state(response: &egui::Response, pressed: bool, label: String) {
+    response.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::Button, true, pressed, &label));
+}
+
+/// Combo box for a knob that can be switched on, off or left alone
+fn tri_state(ui: &mut egui::Ui, id: &str, value: &mut Option<bool>, on: &str, off: &str) {
+    let text = match value {
+        Some(true) => on,
+        Some(false) => off,
+        None => tr("mode-unchanged"),
+    };
+    egui::ComboBox::from_id_salt(id).selected_text(text).show_ui(ui, |ui| {
+        ui.selectable_value(value, None, tr("mode-unchanged"));
+        ui.selectable_value(value, Some(true), on);
+        ui.selectable_value(value, Some(false), off);
+    });
+}
+
+/// Mark a widget as a polite live region so text changes are announced
+fn live_region(response: &egui::Response) {
+    response.ctx.accesskit_node_builder(response.id, |builder| {
+        builder.set_live(egui::accesskit::Live::Polite);
+    });
+}
+
+/// Draw a visible outline around a widget that has keyboard focus
+fn focus_ring(response: egui::Response) -> egui::Response {
+    if response.has_focus() {
+        let stroke = egui::Stroke::new(2.0, response.ctx.style().visuals.selection.stroke.color);
+        response
+            .ctx
+            .layer_painter(response.layer_id)
+            .rect_stroke(response.rect.expand(2.0), 4.0, stroke);
+    }
+    response
+}
+
+/// Write history CSV to `$HOME/thermal-history-<unix time>.csv`
+fn write_history_csv(history: &TemperatureHistory, unit: TempUnit) -> io::Result<PathBuf> {
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME not set"))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = home.join(format!("thermal-history-{}.csv", stamp));
+    fs::write(&path, history.to_csv(unit))?;
+    Ok(path)
+}
+
+impl Drop for ThermalApp {
+    /// Processes stopped from the heat panel would otherwise stay stopped
+    /// with nothing left to continue them
+    fn drop(&mut self) {
+        for process in &self.stopped {
+            if let Err(e) = processes::perform(process, ProcessAction::Continue) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+}
+
 impl eframe::App for ThermalApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Update state every UPDATE_INTERVAL_SECS
@@ -419,8 +1960,15 @@ impl eframe::App for ThermalApp {
         // Request repaint to keep updating
         ctx.request_repaint_after(Duration::from_millis(100));
 
-        // Dark theme
-        ctx.set_visuals(egui::Visuals::dark());
+        let theme = self.theme();
+        ctx.set_visuals(theme.visuals());
+
+        self.handle_shortcuts(ctx);
+        if self.show_shortcut_help {
+            self.render_shortcut_help(ctx);
+        }
+        self.render_mode_editor(ctx);
+        self.render_fan_curve_editor(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             // Get available width to determine layout
@@ -437,23 +1985,20 @@ impl eframe::App for ThermalApp {
                 // Title - adaptive size
                 let title_size = if is_wide { 22.0 } else if is_medium { 18.0 } else { 16.0 };
                 ui.horizontal(|ui| {
-                    ui.heading(egui::RichText::new("Thermal Monitor").size(title_size));
+                    ui.heading(egui::RichText::new(tr("app-title")).size(title_size));
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        ui.label(
-                            egui::RichText::new(format!("{}", self.state.platform_profile))
-                                .size(if is_wide { 12.0 } else { 10.0 })
-                                .color(egui::Color32::GRAY),
-                        );
+                        self.render_platform_profile(ui, if is_wide { 12.0 } else { 10.0 });
                     });
                 });
                 // Localized description
                 let desc_size = if is_wide { 12.0 } else { 10.0 };
                 ui.label(
-                    egui::RichText::new(get_localized_description())
+                    egui::RichText::new(tr("app-description"))
                         .size(desc_size)
-                        .color(egui::Color32::from_rgb(180, 180, 180))
+                        .color(theme.muted())
                         .italics(),
                 );
+                self.render_conflicts(ui, desc_size);
                 ui.separator();
 
                 // Temperatures and Performance - side by side on wide, stacked on narrow
@@ -462,63 +2007,97 @@ impl eframe::App for ThermalApp {
                         let half_width = (available_width - 20.0) / 2.0;
                         ui.group(|ui| {
                             ui.set_width(half_width);
-                            ui.label(egui::RichText::new("Temperatures").size(13.0).strong());
+                            ui.label(egui::RichText::new(tr("section-temperatures")).size(13.0).strong());
                             self.render_temperatures_adaptive(ui, is_medium);
                         });
                         ui.group(|ui| {
                             ui.set_width(half_width);
-                            ui.label(egui::RichText::new("Performance").size(13.0).strong());
+                            ui.label(egui::RichText::new(tr("section-performance")).size(13.0).strong());
                             self.render_performance_adaptive(ui, is_medium);
                         });
                     });
                 } else {
                     ui.group(|ui| {
-                        ui.label(egui::RichText::new("Temperatures").size(13.0).strong());
+                        ui.label(egui::RichText::new(tr("section-temperatures")).size(13.0).strong());
                         self.render_temperatures_adaptive(ui, is_medium);
                     });
                     ui.group(|ui| {
-                        ui.label(egui::RichText::new("Performance").size(13.0).strong());
+                        ui.label(egui::RichText::new(tr("section-performance")).size(13.0).strong());
                         self.render_performance_adaptive(ui, is_medium);
                     });
                 }
 
                 // Mode Control - wrapping buttons
                 ui.group(|ui| {
-                    ui.label(egui::RichText::new("Mode Control").size(13.0).strong());
+                    ui.label(egui::RichText::new(tr("section-mode-control")).size(13.0).strong());
                     self.render_controls_adaptive(ui, available_width);
                 });
 
-                // Target and Fan - side by side on wide, stacked on narrow
-                if is_medium {
+                // EPP, turbo and minimum performance
+                ui.group(|ui| {
+                    ui.label(egui::RichText::new(tr("section-tuning")).size(13.0).strong());
+                    self.render_tuning_adaptive(ui, is_wide);
+                });
+
+                // Target and Fan - side by side on wide, stacked on narrow.
+                // The fan section is hidden without a fan backend or speed reading
+                let has_fan = self.state.fan_boost.is_some() || self.state.fan_rpm.is_some();
+                if !has_fan {
+                    ui.group(|ui| {
+                        ui.label(egui::RichText::new(tr("section-target-temp")).size(13.0).strong());
+                        self.render_target_temp_adaptive(ui, is_wide);
+                    });
+                } else if is_medium {
                     ui.horizontal(|ui| {
                         let half_width = (available_width - 20.0) / 2.0;
                         ui.group(|ui| {
                             ui.set_width(half_width);
-                            ui.label(egui::RichText::new("Target Temp").size(13.0).strong());
+                            ui.label(egui::RichText::new(tr("section-target-temp")).size(13.0).strong());
                             self.render_target_temp_adaptive(ui, is_wide);
                         });
                         ui.group(|ui| {
                             ui.set_width(half_width);
-                            ui.label(egui::RichText::new("Fan").size(13.0).strong());
+                            ui.label(egui::RichText::new(tr("section-fan")).size(13.0).strong());
                             self.render_fan_control_adaptive(ui, is_wide);
                         });
                     });
                 } else {
                     ui.group(|ui| {
-                        ui.label(egui::RichText::new("Target Temp").size(13.0).strong());
+                        ui.label(egui::RichText::new(tr("section-target-temp")).size(13.0).strong());
                         self.render_target_temp_adaptive(ui, is_wide);
                     });
                     ui.group(|ui| {
-                        ui.label(egui::RichText::new("Fan").size(13.0).strong());
+                        ui.label(egui::RichText::new(tr("section-fan")).size(13.0).strong());
                         self.render_fan_control_adaptive(ui, is_wide);
                     });
                 }
 
+                // Top CPU users, sampled only while shown
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(tr("section-heat")).size(13.0).strong());
+                        let label = if self.show_heat { tr("heat-hide") } else { tr("heat-show") };
+                        if focus_ring(ui.small_button(label)).on_hover_text(tr("hint-heat")).clicked() {
+                            self.show_heat = !self.show_heat;
+                            self.heat.reset();
+                            self.contributors.clear();
+                        }
+                    });
+                    if self.show_heat {
+                        self.render_heat(ui, is_wide);
+                    }
+                });
+
                 // History graph - adaptive height
                 let target = self.target_temp;
                 let graph_height = if is_wide { 180.0 } else if is_medium { 120.0 } else { 80.0 };
                 ui.group(|ui| {
-                    ui.label(egui::RichText::new("History").size(13.0).strong());
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(tr("section-history")).size(13.0).strong());
+                        if ui.small_button(tr("history-export")).on_hover_text("[Ctrl+E]").clicked() {
+                            self.export_history();
+                        }
+                    });
                     self.render_history_adaptive(ui, target, graph_height);
                 });
 
@@ -536,12 +2115,12 @@ mod tests {
     #[test]
     fn test_history_capacity() {
         let mut history = TemperatureHistory::new(3);
-        history.push(40.0, 35.0);
-        history.push(42.0, 36.0);
-        history.push(44.0, 37.0);
+        history.push(40.0, 35.0, None, None);
+        history.push(42.0, 36.0, None, None);
+        history.push(44.0, 37.0, None, None);
         assert_eq!(history.len(), 3);
 
-        history.push(46.0, 38.0);
+        history.push(46.0, 38.0, None, None);
         assert_eq!(history.len(), 3); // Should not exceed capacity
     }
 
@@ -562,11 +2141,11 @@ mod tests {
     #[test]
     fn test_history_points() {
         let mut history = TemperatureHistory::new(10);
-        history.push(40.0, 35.0);
-        history.push(42.0, 36.0);
+        history.push(40.0, 35.0, None, None);
+        history.push(42.0, 36.0, None, None);
 
-        let _cpu_points = history.cpu_points();
-        let _kbd_points = history.kbd_points();
+        let _cpu_points = history.cpu_points(TempUnit::Celsius);
+        let _kbd_points = history.kbd_points(TempUnit::Fahrenheit);
 
         // Verify points are generated correctly
         assert!(!history.is_empty());
@@ -576,14 +2155,83 @@ mod tests {
     #[test]
     fn test_history_fifo_behavior() {
         let mut history = TemperatureHistory::new(2);
-        history.push(10.0, 5.0);  // First in
-        history.push(20.0, 10.0);
-        history.push(30.0, 15.0); // Should push out first
+        history.push(10.0, 5.0, None, None);  // First in
+        history.push(20.0, 10.0, None, None);
+        history.push(30.0, 15.0, None, None); // Should push out first
 
         assert_eq!(history.len(), 2);
         // First value (10.0) should be gone
     }
 
+    #[test]
+    fn test_history_csv_export() {
+        let mut history = TemperatureHistory::new(10);
+        history.push(50.0, 37.9, None, None);
+        history.push(100.0, 60.0, None, None);
+
+        let csv = history.to_csv(TempUnit::Celsius);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "seconds,cpu_C,keyboard_C");
+        assert_eq!(lines[1], "-2,50.0,37.9");
+        assert_eq!(lines[2], "0,100.0,60.0");
+
+        let csv = history.to_csv(TempUnit::Fahrenheit);
+        assert!(csv.starts_with("seconds,cpu_F,keyboard_F\n"));
+        assert!(csv.contains("0,212.0,140.0"));
+    }
+
+    #[test]
+    fn test_fan_curve_editor() {
+        let mut editor = FanCurveEditor::new(FanCurve::default());
+        // 45:0, 60:35, 75:70, 85:100; dragging stays between the neighbors
+        editor.drag_to(1, 90.0, 90.0);
+        assert_eq!(editor.curve.points[1], CurvePoint::new(74.0, 70));
+        editor.drag_to(0, 10.0, -5.0);
+        assert_eq!(editor.curve.points[0], CurvePoint::new(20.0, 0));
+
+        editor.insert(80.0);
+        assert_eq!(editor.curve.points.len(), 5);
+        assert_eq!(editor.curve.points[3], CurvePoint::new(80.0, 85));
+        editor.insert(80.0);
+        assert_eq!(editor.curve.points.len(), 5);
+        editor.curve.validate().unwrap();
+
+        for _ in 0..5 {
+            editor.remove(0);
+        }
+        assert_eq!(editor.curve.points.len(), 2);
+    }
+
+    #[test]
+    fn test_history_power() {
+        let mut history = TemperatureHistory::new(4);
+        history.push(50.0, 37.9, None, None);
+        assert!(!history.has_power());
+        history.push(60.0, 40.0, Some(2650), Some(14.26));
+        assert!(history.has_power());
+        assert_eq!(history.power_points().points().len(), 1);
+        let csv = history.to_csv(TempUnit::Celsius);
+        assert_eq!(csv, "seconds,cpu_C,keyboard_C,fan_rpm,power_w\n-2,50.0,37.9,,\n0,60.0,40.0,2650,14.3\n");
+    }
+
+    #[test]
+    fn test_history_fan_rpm() {
+        let mut history = TemperatureHistory::new(2);
+        history.push(50.0, 37.9, None, None);
+        assert!(!history.has_fan());
+        history.push(60.0, 40.0, Some(2650), None);
+        history.push(70.0, 45.0, Some(4100), None);
+        assert!(history.has_fan());
+        assert_eq!(history.fan_points().points().len(), 2);
+
+        let csv = history.to_csv(TempUnit::Celsius);
+        assert_eq!(csv, "seconds,cpu_C,keyboard_C,fan_rpm\n-2,60.0,40.0,2650\n0,70.0,45.0,4100\n");
+
+        // Gaps stay empty cells
+        history.push(80.0, 50.0, None, None);
+        assert!(history.to_csv(TempUnit::Celsius).ends_with("-2,70.0,45.0,4100\n0,80.0,50.0,\n"));
+    }
+
     #[test]
     fn test_zone_colors() {
         // Verify all zones have valid colors
@@ -595,7 +2243,7 @@ mod tests {
             ThermalZone::Hot,
             ThermalZone::Critical,
         ] {
-            let color = ThermalApp::zone_color(zone);
+            let color = Theme::Dark.zone_color(zone);
             assert_ne!(color, egui::Color32::TRANSPARENT);
         }
     }
@@ -612,7 +2260,7 @@ mod tests {
             ThermalZone::Critical,
         ] {
             let (r, g, b) = zone.color_rgb();
-            let color = ThermalApp::zone_color(zone);
+            let color = Theme::Dark.zone_color(zone);
             assert_eq!(color, egui::Color32::from_rgb(r, g, b));
         }
     }
@@ -621,21 +2269,21 @@ mod tests {
     fn test_mode_colors() {
         // Verify all modes have colors
         for mode in Mode::all() {
-            let color = ThermalApp::mode_color(*mode);
+            let color = Theme::Dark.mode_color(mode);
             assert_ne!(color, egui::Color32::TRANSPARENT);
         }
     }
 
     #[test]
     fn test_mode_color_unknown() {
-        let color = ThermalApp::mode_color(Mode::Unknown);
+        let color = Theme::Dark.mode_color(&Mode::Unknown);
         assert_eq!(color, egui::Color32::GRAY);
     }
 
     #[test]
     fn test_mode_colors_distinct() {
         // Each mode should have a distinct color
-        let colors: Vec<_> = Mode::all().iter().map(|m| ThermalApp::mode_color(*m)).collect();
+        let colors: Vec<_> = Mode::all().iter().map(|m| Theme::Dark.mode_color(m)).collect();
 
         // Performance should be reddish
         assert!(colors[0].r() > colors[0].b());
@@ -0,0 +1,218 @@
+//! Per-application CPU throttling with cgroup v2
+//!
+//! systemd runs every service, session and desktop app in its own cgroup
+//! below `/sys/fs/cgroup` (`app-gnome-firefox-1234.scope`, `background.slice`).
+//! Two files of the cpu controller slow a group down without touching the
+//! rest of the system:
+//! - `cpu.max`: "QUOTA PERIOD" in µs of CPU time per period, summed over
+//!   CPUs, or "max PERIOD" for no cap
+//! - `cpu.weight`: share under contention, 1-10000 with 100 the default
+//!
+//! Groups are picked below `user.slice` only, by unit name: a pattern `P`
+//! stands for `P.service`, `P.slice` and app scopes `app-…-P-….scope`,
+//! where `P` is one or more whole dash-separated parts, or for a full unit
+//! name. Patterns that would cover `user.slice` itself, a whole user session,
+//! one of the session's own slices or anything outside it are refused. Groups without the cpu controller
+//! have no `cpu.max` and are skipped.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cpufreq::SysfsWrite;
+use crate::helper::{write_privileged, WriteReport};
+use crate::system::read_sysfs_value;
+
+/// cgroup v2 mount point
+pub const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// `cpu.max` period in µs, the kernel default
+const PERIOD_US: u64 = 100_000;
+
+/// Lowest throttle, in percent of all CPUs
+pub const MIN_THROTTLE_PCT: u8 = 10;
+
+/// Weight of an unthrottled group
+const DEFAULT_WEIGHT: u32 = 100;
+
+/// The only slice groups are picked from; the helper writes nowhere else
+pub const USER_SLICE: &str = "user.slice";
+
+/// Units a pattern must not match: the root, `user.slice` and the slices
+/// systemd groups every app and session unit into
+const PROTECTED: [&str; 7] =
+    ["-.slice", "init.scope", "system.slice", USER_SLICE, "app.slice", "session.slice", "background.slice"];
+
+/// Whether unit `name` is matched by `pattern`
+fn matches(name: &str, pattern: &str) -> bool {
+    if name == pattern || name.strip_suffix(".service").or_else(|| name.strip_suffix(".slice")) == Some(pattern) {
+        return true;
+    }
+    // app-<launcher>-<app>-<id>.scope: the pattern is whole parts of it
+    name.strip_prefix("app-")
+        .and_then(|name| name.strip_suffix(".scope"))
+        .is_some_and(|stem| format!("-{}", stem).contains(&format!("-{}-", pattern)))
+}
+
+/// Whether `pattern` may be used: it names no protected unit nor a
+/// whole user session (`user-1000.slice`, `user@1000.service`)
+pub fn valid_pattern(pattern: &str) -> bool {
+    !pattern.is_empty()
+        && !pattern.contains('/')
+        && !pattern.starts_with("user-")
+        && !pattern.starts_with("user@")
+        && !PROTECTED.iter().any(|name| matches(name, pattern))
+}
+
+/// Groups below `root`/user.slice matched by one of `patterns`; invalid
+/// patterns are ignored, and sub-groups of a match are covered by it and
+/// not listed
+pub fn find_groups(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let patterns: Vec<&str> = patterns.iter().map(String::as_str).filter(|p| valid_pattern(p)).collect();
+    let mut found = Vec::new();
+    if !patterns.is_empty() {
+        walk(&root.join(USER_SLICE), &patterns, &mut found);
+    }
+    found.sort();
+    found
+}
+
+fn walk(dir: &Path, patterns: &[&str], found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            continue;
+        }
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if patterns.iter().any(|pattern| matches(&name, pattern)) && path.join("cpu.max").exists() {
+            found.push(path);
+        } else {
+            walk(&path, patterns, found);
+        }
+    }
+}
+
+/// Matching groups on this machine; this walks the cgroup tree, so callers
+/// keep the result for a while
+pub fn groups(patterns: &[String]) -> Vec<PathBuf> {
+    find_groups(Path::new(CGROUP_ROOT), patterns)
+}
+
+/// Number of CPUs a quota is spread over
+pub fn cpu_count() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Current cap of a group in percent of all CPUs; 100 when uncapped
+pub fn throttle_pct(group: &Path, cpus: usize) -> Option<u8> {
+    let value = read_sysfs_value(group.join("cpu.max")).ok()?;
+    let (quota, period) = value.split_once(' ')?;
+    if quota == "max" {
+        return Some(100);
+    }
+    let (quota, period) = (quota.parse::<u64>().ok()?, period.parse::<u64>().ok()?.max(1));
+    Some((quota * 100 / (period * cpus.max(1) as u64)).min(100) as u8)
+}
+
+/// Lowest cap among `groups`
+pub fn current_pct(groups: &[PathBuf], cpus: usize) -> Option<u8> {
+    groups.iter().filter_map(|group| throttle_pct(group, cpus)).min()
+}
+
+/// Writes capping `groups` at `pct` percent of all CPUs, with their weight
+/// lowered in proportion; 100 removes the cap
+pub fn throttle_writes(groups: &[PathBuf], pct: u8, cpus: usize) -> Vec<SysfsWrite> {
+    let pct = pct.clamp(MIN_THROTTLE_PCT, 100);
+    let (max, weight) = if pct >= 100 {
+        (format!("max {}", PERIOD_US), DEFAULT_WEIGHT)
+    } else {
+        let quota = PERIOD_US * cpus.max(1) as u64 * pct as u64 / 100;
+        (format!("{} {}", quota, PERIOD_US), DEFAULT_WEIGHT * pct as u32 / 100)
+    };
+    groups
+        .iter()
+        .flat_map(|group| [SysfsWrite::new(group.join("cpu.max"), &max), SysfsWrite::new(group.join("cpu.weight"), weight)])
+        .collect()
+}
+
+/// Cap `groups` at `pct` percent of all CPUs through the privileged helper
+pub fn set_throttle(groups: &[PathBuf], pct: u8) -> io::Result<WriteReport> {
+    write_privileged(&throttle_writes(groups, pct, cpu_count()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+
+    #[test]
+    fn test_find_groups() {
+        let root = tempfile::tempdir().unwrap();
+        let session = "user.slice/user-1000.slice/user@1000.service";
+        let app = format!("{}/app.slice", session);
+        put(root.path(), &format!("{}/app-gnome-firefox-4242.scope/cpu.max", app), "max 100000");
+        put(root.path(), &format!("{}/app-gnome-firefox-4242.scope/tab/cpu.max", app), "max 100000");
+        put(root.path(), &format!("{}/app-gnome-firefoxpwa-77.scope/cpu.max", app), "max 100000");
+        put(root.path(), &format!("{}/app-gnome-terminal.scope/cpu.max", app), "max 100000");
+        put(root.path(), &format!("{}/background.slice/cpu.max", session), "max 100000");
+        put(root.path(), &format!("{}/background.slice/tracker-miner-fs-3.service/cpu.max", session), "max 100000");
+        // No cpu controller: skipped
+        put(root.path(), &format!("{}/firefox-updater.service/pids.max", session), "max");
+        // Outside user.slice: never touched
+        put(root.path(), "system.slice/build.slice/cpu.max", "max 100000");
+
+        let patterns = ["firefox".to_string(), "tracker-miner-fs-3".to_string(), "build".to_string()];
+        let found = find_groups(root.path(), &patterns);
+        assert_eq!(
+            found,
+            [
+                root.path().join(&app).join("app-gnome-firefox-4242.scope"),
+                root.path().join(session).join("background.slice/tracker-miner-fs-3.service"),
+            ]
+        );
+        assert!(find_groups(root.path(), &[]).is_empty());
+        // Would cover the whole session or one of its slices
+        let patterns = ["user", "user-1000", "app", "background.slice"].map(String::from);
+        assert!(find_groups(root.path(), &patterns).is_empty());
+    }
+
+    #[test]
+    fn test_valid_pattern() {
+        assert!(valid_pattern("firefox"));
+        assert!(valid_pattern("org.mozilla.firefox"));
+        assert!(valid_pattern("tracker-miner-fs-3.service"));
+        for pattern in [
+            "", "user", "user.slice", "system", "init.scope", "-", "user-1000", "user@1000.service", "app.slice/x", "app", "app.slice",
+            "session", "session.slice", "background", "background.slice",
+        ] {
+            assert!(!valid_pattern(pattern), "{}", pattern);
+        }
+        assert!(matches("app-flatpak-org.mozilla.firefox-4242.scope", "org.mozilla.firefox"));
+        assert!(!matches("app-flatpak-org.mozilla.firefox-4242.scope", "firefox"));
+        assert!(matches("rustc-build.service", "rustc-build"));
+    }
+
+    #[test]
+    fn test_throttle_writes() {
+        let root = tempfile::tempdir().unwrap();
+        let groups = [root.path().join("background.slice")];
+        let group = &groups[0];
+        let writes = throttle_writes(&groups, 25, 8);
+        assert_eq!(
+            writes,
+            [SysfsWrite::new(group.join("cpu.max"), "200000 100000"), SysfsWrite::new(group.join("cpu.weight"), "25")]
+        );
+        assert_eq!(throttle_writes(&groups, 0, 8)[0].value, "80000 100000");
+        assert_eq!(throttle_writes(&groups, 100, 8)[1].value, "100");
+
+        put(root.path(), "background.slice/cpu.max", "max 100000");
+        assert_eq!(throttle_pct(group, 8), Some(100));
+        put(root.path(), "background.slice/cpu.max", "200000 100000");
+        assert_eq!(throttle_pct(group, 8), Some(25));
+        assert_eq!(current_pct(&[group.clone(), root.path().join("gone")], 8), Some(25));
+    }
+}
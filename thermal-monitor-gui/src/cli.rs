@@ -0,0 +1,553 @@
+//! Command-line interface
+//!
+//! Running `thermal-monitor` without arguments starts the GUI.
+//! Subcommands print readings for terminals and scripts, using the
+//! same units preference as the GUI unless overridden by flags.
+
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::conflicts::{self, Conflict};
+use crate::fan_curve;
+use crate::helper::{self, WriteOutcome, WriteReport};
+use crate::i18n::{tr, tr_args};
+use crate::modes::{self, ModeProfile};
+use crate::platform_profile::profile_label;
+use crate::power::{self, LimitRequest};
+use crate::rules::{self, RuleSet, Sample};
+use crate::system::{
+    apply_mode, energy_preference_label, read_mode, read_platform_profile, read_platform_profile_choices, set_perf_pct,
+    set_platform_profile, set_policy_perf_pct, set_power_limits, Mode, ThermalState,
+};
+use crate::units::{FreqUnit, TempUnit, Units};
+
+/// Interval over which `status` measures power
+const POWER_WINDOW: Duration = Duration::from_millis(250);
+
+const USAGE: &str = "\
+Usage: thermal-monitor [COMMAND] [OPTIONS]
+
+Commands:
+  (none)        Start the graphical monitor
+  status        Print current thermal state
+  perf PCT      Limit CPU performance to PCT% and print per-policy results
+  profile [NAME]
+                List platform profiles, or switch to NAME
+  mode [NAME]   List CPU modes, or apply mode NAME
+  power-limit [WATTS] [--short WATTS] [--window SECS]
+                Print RAPL package power limits, or set the sustained
+                (PL1) limit, the burst (PL2) limit and the PL1 window
+  rules         List automation rules, marking those whose conditions
+                hold now
+  fan-curve [--release]
+                Drive the hwmon fan from the saved curve (root; run by
+                thermal-fan-curve.service), or hand it back to the firmware
+  help          Show this help
+
+Options:
+  -C, --celsius      Show temperatures in Celsius
+  -F, --fahrenheit   Show temperatures in Fahrenheit
+      --ghz          Show frequencies in GHz
+      --mhz          Show frequencies in MHz
+      --policy N     Apply perf only to cpufreq policy N";
+
+/// Parsed command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Gui,
+    Status(Units),
+    Perf { pct: u8, policy: Option<u32> },
+    Profile(Option<String>),
+    Mode(Option<String>),
+    /// Print power limits, or set those in the request
+    PowerLimit(LimitRequest),
+    /// List rules and check them against the current state
+    Rules,
+    /// Fan curve loop, or `--release` to restore automatic fan control
+    FanCurve { release: bool },
+    Help,
+    /// Privileged writer, invoked through pkexec by the GUI
+    Helper(Vec<String>),
+}
+
+/// Parse arguments (without the program name)
+pub fn parse(args: &[String], config: &Config) -> Result<Command, String> {
+    if let Some(("helper", rest)) = args.split_first().map(|(first, rest)| (first.as_str(), rest)) {
+        return Ok(Command::Helper(rest.to_vec()));
+    }
+
+    let mut units = config.units;
+    let mut command = None;
+    let mut policy = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-C" | "--celsius" => units.temp = TempUnit::Celsius,
+            "-F" | "--fahrenheit" => units.temp = TempUnit::Fahrenheit,
+            "--ghz" => units.freq = FreqUnit::GHz,
+            "--mhz" => units.freq = FreqUnit::MHz,
+            "--policy" => policy = Some(parse_value::<u32>(arg, iter.next())?),
+            "-h" | "--help" | "help" => command = Some(Command::Help),
+            "status" if command.is_none() => command = Some(Command::Status(units)),
+            "perf" if command.is_none() => {
+                let pct = parse_value::<u8>(arg, iter.next())?;
+                if !(1..=100).contains(&pct) {
+                    return Err(format!("Performance must be 1-100, got {}", pct));
+                }
+                command = Some(Command::Perf { pct, policy: None });
+            }
+            "profile" if command.is_none() => command = Some(Command::Profile(None)),
+            "mode" if command.is_none() => command = Some(Command::Mode(None)),
+            "power-limit" if command.is_none() => command = Some(Command::PowerLimit(LimitRequest::default())),
+            "--short" | "--window" if matches!(command, Some(Command::PowerLimit(_))) => {
+                let value = parse_value::<u32>(arg, iter.next())?;
+                if let Some(Command::PowerLimit(request)) = &mut command {
+                    match arg.as_str() {
+                        "--short" => request.short_term_w = Some(value),
+                        _ => request.window_s = Some(value),
+                    }
+                }
+            }
+            _ if matches!(command, Some(Command::PowerLimit(LimitRequest { long_term_w: None, .. }))) => {
+                let value = parse_value::<u32>("power-limit", Some(arg))?;
+                if let Some(Command::PowerLimit(request)) = &mut command {
+                    request.long_term_w = Some(value);
+                }
+            }
+            "rules" if command.is_none() => command = Some(Command::Rules),
+            "fan-curve" if command.is_none() => command = Some(Command::FanCurve { release: false }),
+            "--release" if matches!(command, Some(Command::FanCurve { .. })) => {
+                command = Some(Command::FanCurve { release: true });
+            }
+            name if !name.starts_with('-') && matches!(command, Some(Command::Profile(None))) => {
+                command = Some(Command::Profile(Some(name.to_string())));
+            }
+            name if !name.starts_with('-') && matches!(command, Some(Command::Mode(None))) => {
+                command = Some(Command::Mode(Some(name.to_string())));
+            }
+            other => return Err(format!("Unknown argument: {}\n\n{}", other, USAGE)),
+        }
+    }
+
+    // Flags may come after the subcommand
+    Ok(match command {
+        Some(Command::Status(_)) => Command::Status(units),
+        Some(Command::Perf { pct, .. }) => Command::Perf { pct, policy },
+        Some(command) => command,
+        None if policy.is_some() => return Err(format!("--policy requires perf\n\n{}", USAGE)),
+        None => Command::Gui,
+    })
+}
+
+fn parse_value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    value
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| format!("{} expects a number\n\n{}", option, USAGE))
+}
+
+/// Run a non-GUI command, returning the process exit code
+pub fn run(command: Command) -> i32 {
+    match command {
+        Command::Gui => 0,
+        Command::Help => {
+            println!("{}", USAGE);
+            0
+        }
+        Command::Status(units) => {
+            // Power needs two counter readings
+            power::read_power();
+            thread::sleep(POWER_WINDOW);
+            let mut state = ThermalState::read();
+            let found = conflicts::detect();
+            if via_ppd(&found) {
+                state.mode_drift.retain(|result| !conflicts::ppd_owns(&result.write.path));
+            }
+            print!("{}", format_status(&state, &units));
+            for conflict in &found {
+                println!("{}", conflict.message());
+            }
+            0
+        }
+        Command::Perf { pct, policy } => {
+            let result = match policy {
+                Some(policy) => set_policy_perf_pct(policy, pct),
+                None => set_perf_pct(pct),
+            };
+            match result {
+                Ok(report) => {
+                    print!("{}", format_write_report(&report));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", tr_args("status-error", &[("error", &e.to_string())]));
+                    1
+                }
+            }
+        }
+        Command::Profile(None) => {
+            print!("{}", format_profiles(&read_platform_profile(), &read_platform_profile_choices()));
+            0
+        }
+        Command::Profile(Some(name)) => match set_platform_profile(&name, via_ppd(&conflicts::detect())) {
+            Ok(_) => {
+                println!("{}", tr_args("status-profile", &[("profile", profile_label(&name))]));
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", tr_args("status-error", &[("error", &e.to_string())]));
+                1
+            }
+        },
+        Command::Mode(None) => {
+            print!("{}", format_modes(&read_mode(), &modes::profiles()));
+            0
+        }
+        Command::Mode(Some(name)) => match apply_mode(&name, via_ppd(&conflicts::detect())) {
+            Ok(_) => {
+                println!("{}", tr_args("status-mode-changed", &[("mode", &Mode::from_name(&name).label())]));
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", tr_args("status-error", &[("error", &e.to_string())]));
+                1
+            }
+        },
+        Command::PowerLimit(request) if request == LimitRequest::default() => {
+            let limits = power::limits();
+            if limits.is_empty() {
+                eprintln!("{}", tr("cli-no-power-limits"));
+                return 1;
+            }
+            println!("{}", limits.summary());
+            0
+        }
+        Command::PowerLimit(request) => match set_power_limits(&request) {
+            Ok(report) => {
+                print!("{}", format_write_report(&report));
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", tr_args("status-error", &[("error", &e.to_string())]));
+                1
+            }
+        },
+        Command::Rules => {
+            let set = rules::load();
+            print!("{}", format_rules(&set, &set.sample(&ThermalState::read())));
+            0
+        }
+        Command::FanCurve { release: false } => fan_curve::run(),
+        Command::FanCurve { release: true } => fan_curve::run_release(),
+        Command::Helper(args) => helper::run(&args),
+    }
+}
+
+/// Whether the user chose to cooperate with a running power-profiles-daemon
+fn via_ppd(found: &[Conflict]) -> bool {
+    Config::load().cooperate_ppd && conflicts::ppd_active(found)
+}
+
+/// Advertised platform profiles, the active one marked with `*`
+pub fn format_profiles(current: &str, choices: &[String]) -> String {
+    if choices.is_empty() {
+        return format!("{}: {}\n", tr("cli-profile"), current);
+    }
+    choices
+        .iter()
+        .map(|choice| {
+            let marker = if choice == current { '*' } else { ' ' };
+            format!("{} {:<22}{}\n", marker, choice, profile_label(choice))
+        })
+        .collect()
+}
+
+/// Known modes with their derived descriptions, the active one marked with `*`
+pub fn format_modes(current: &Mode, profiles: &[ModeProfile]) -> String {
+    let width = profiles.iter().map(|p| p.name.len()).max().unwrap_or(0) + 2;
+    profiles
+        .iter()
+        .map(|profile| {
+            let marker = if *current != Mode::Unknown && profile.name == current.command() { '*' } else { ' ' };
+            format!("{} {:<width$}{}\n", marker, profile.name, profile.description(), width = width)
+        })
+        .collect()
+}
+
+/// Rules in priority order, those whose conditions hold now marked with `*`
+pub fn format_rules(set: &RuleSet, sample: &Sample) -> String {
+    if set.is_empty() {
+        let path = rules::user_rules_file().map(|p| p.display().to_string()).unwrap_or_default();
+        return format!("{}\n", tr_args("cli-no-rules", &[("path", &path)]));
+    }
+    let width = set.rules.iter().map(|r| r.name.len()).max().unwrap_or(0) + 2;
+    let mut out = String::new();
+    if set.dry_run {
+        out.push_str(&format!("{}\n", tr("rules-label-dry-run")));
+    }
+    for rule in &set.rules {
+        let marker = if rule.conditions.matches(sample) { '*' } else { ' ' };
+        out.push_str(&format!("{} {:<width$}{:>4}  {}\n", marker, rule.name, rule.priority, rule.actions_text(), width = width));
+    }
+    out
+}
+
+/// One line per written attribute with the value read back
+pub fn format_write_report(report: &WriteReport) -> String {
+    let width = report.results.iter().map(|r| r.label().chars().count()).max().unwrap_or(0) + 2;
+    report
+        .results
+        .iter()
+        .map(|r| {
+            let detail = match &r.outcome {
+                WriteOutcome::Applied(actual) | WriteOutcome::Mismatch(actual) | WriteOutcome::Failed(actual) => actual,
+            };
+            format!(" {:<width$}{}\n", r.label(), detail, width = width)
+        })
+        .collect()
+}
+
+/// Render a thermal state snapshot as aligned text lines
+pub fn format_status(state: &ThermalState, units: &Units) -> String {
+    let mut rows = vec![
+        (tr("cli-cpu"), format!("{} ({})", units.temp(state.cpu_temp), state.thermal_zone().label())),
+        (tr("cli-keyboard"), format!("~{}", units.temp(state.keyboard_temp))),
+        (tr("cli-ambient"), units.temp(state.ambient_temp)),
+        (
+            tr("cli-frequency"),
+            format!("{} / {}", units.freq(state.current_freq_mhz), units.freq(state.max_freq_mhz)),
+        ),
+        (tr("cli-performance"), format!("{}%", state.perf_pct)),
+        (tr("cli-min-perf"), format!("{}%", state.min_perf_pct)),
+        (tr("cli-driver"), state.cpufreq_driver.clone()),
+    ];
+    if let Some(turbo) = state.turbo {
+        rows.push((tr("cli-turbo"), if turbo { tr("turbo-on") } else { tr("turbo-off") }.to_string()));
+    }
+    if let Some(epp) = &state.energy_preference {
+        rows.push((tr("cli-epp"), energy_preference_label(epp).to_string()));
+    }
+    rows.push((tr("cli-mode"), state.mode.label()));
+    if !state.mode_drift.is_empty() {
+        let items: Vec<String> = state.mode_drift.iter().map(helper::drift_text).collect();
+        rows.push((tr("cli-drift"), items.join("; ")));
+    }
+    rows.push((tr("cli-profile"), profile_label(&state.platform_profile).to_string()));
+    if let Some(boost) = state.fan_boost {
+        let level = if boost { tr("fan-boost") } else { tr("fan-normal") };
+        rows.push((tr("cli-fan"), format!("{} ({})", level, state.fan_backend)));
+    }
+    if let Some(rpm) = state.fan_rpm {
+        rows.push((tr("cli-fan-speed"), tr_args("fan-rpm", &[("rpm", &rpm.to_string())])));
+    }
+    match state.power.total() {
+        Some(_) => rows.push((tr("cli-power"), state.power.breakdown())),
+        None if state.power.restricted => rows.push((tr("cli-power"), tr("hint-power-restricted").to_string())),
+        None => {}
+    }
+    if !state.power_limits.is_empty() {
+        rows.push((tr("cli-power-limits"), state.power_limits.summary()));
+    }
+    if let Some(source) = state.power_supply.source() {
+        rows.push((tr("cli-power-source"), source.label().to_string()));
+    }
+    if state.power_supply.has_battery() {
+        rows.push((tr("cli-battery"), state.power_supply.summary()));
+    }
+
+    let width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0) + 2;
+    let mut out = tr_args("app-version", &[("version", env!("CARGO_PKG_VERSION"))]);
+    out.push('\n');
+    for (label, value) in rows {
+        out.push_str(&format!(" {:<width$}{}\n", format!("{}:", label), value, width = width));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::SysfsWrite;
+    use crate::helper::WriteResult;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_args_starts_gui() {
+        assert_eq!(parse(&[], &Config::default()), Ok(Command::Gui));
+    }
+
+    #[test]
+    fn test_status_uses_config_units() {
+        let config = Config {
+            units: Units { temp: TempUnit::Fahrenheit, freq: FreqUnit::MHz },
+            ..Default::default()
+        };
+        assert_eq!(parse(&args(&["status"]), &config), Ok(Command::Status(config.units)));
+    }
+
+    #[test]
+    fn test_status_flags_override_config() {
+        let cmd = parse(&args(&["status", "-F", "--mhz"]), &Config::default()).unwrap();
+        assert_eq!(cmd, Command::Status(Units { temp: TempUnit::Fahrenheit, freq: FreqUnit::MHz }));
+    }
+
+    #[test]
+    fn test_unknown_argument() {
+        assert!(parse(&args(&["--bogus"]), &Config::default()).is_err());
+        assert_eq!(parse(&args(&["help"]), &Config::default()), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_perf_command() {
+        let config = Config::default();
+        assert_eq!(parse(&args(&["perf", "60"]), &config), Ok(Command::Perf { pct: 60, policy: None }));
+        assert_eq!(
+            parse(&args(&["perf", "60", "--policy", "2"]), &config),
+            Ok(Command::Perf { pct: 60, policy: Some(2) })
+        );
+        assert!(parse(&args(&["perf"]), &config).is_err());
+        assert!(parse(&args(&["perf", "0"]), &config).is_err());
+        assert!(parse(&args(&["perf", "abc"]), &config).is_err());
+        assert!(parse(&args(&["--policy", "1"]), &config).is_err());
+    }
+
+    #[test]
+    fn test_profile_command() {
+        let config = Config::default();
+        assert_eq!(parse(&args(&["profile"]), &config), Ok(Command::Profile(None)));
+        assert_eq!(
+            parse(&args(&["profile", "low-power"]), &config),
+            Ok(Command::Profile(Some("low-power".into())))
+        );
+        assert!(parse(&args(&["profile", "quiet", "balanced"]), &config).is_err());
+    }
+
+    #[test]
+    fn test_mode_command() {
+        let config = Config::default();
+        assert_eq!(parse(&args(&["mode"]), &config), Ok(Command::Mode(None)));
+        assert_eq!(parse(&args(&["mode", "quiet"]), &config), Ok(Command::Mode(Some("quiet".into()))));
+        assert!(parse(&args(&["mode", "quiet", "auto"]), &config).is_err());
+    }
+
+    #[test]
+    fn test_fan_curve_command() {
+        let config = Config::default();
+        assert_eq!(parse(&args(&["fan-curve"]), &config), Ok(Command::FanCurve { release: false }));
+        assert_eq!(parse(&args(&["fan-curve", "--release"]), &config), Ok(Command::FanCurve { release: true }));
+        assert!(parse(&args(&["--release"]), &config).is_err());
+    }
+
+    #[test]
+    fn test_power_limit_command() {
+        let config = Config::default();
+        assert_eq!(parse(&args(&["power-limit"]), &config), Ok(Command::PowerLimit(LimitRequest::default())));
+        assert_eq!(
+            parse(&args(&["power-limit", "12", "--short", "20", "--window", "28"]), &config),
+            Ok(Command::PowerLimit(LimitRequest { long_term_w: Some(12), short_term_w: Some(20), window_s: Some(28) }))
+        );
+        assert_eq!(
+            parse(&args(&["power-limit", "--short", "25"]), &config),
+            Ok(Command::PowerLimit(LimitRequest { short_term_w: Some(25), ..Default::default() }))
+        );
+        assert!(parse(&args(&["power-limit", "lots"]), &config).is_err());
+        assert!(parse(&args(&["power-limit", "12", "15"]), &config).is_err());
+        assert!(parse(&args(&["--short", "25"]), &config).is_err());
+    }
+
+    #[test]
+    fn test_format_rules() {
+        use crate::config::Ini;
+        assert_eq!(parse(&args(&["rules"]), &Config::default()), Ok(Command::Rules));
+        let set = RuleSet::from_ini(&Ini::parse(
+            "[rule hot]\npriority = 5\ncpu_above = 80\nmode = quiet\n[rule cool]\ncpu_below = 50\nmode = balanced\n",
+        ));
+        let sample = Sample { cpu_temp: 85.0, ..Default::default() };
+        assert_eq!(format_rules(&set, &sample), "* hot      5  mode = quiet\n  cool     0  mode = balanced\n");
+        assert!(format_rules(&RuleSet::default(), &sample).starts_with("No rules"));
+    }
+
+    #[test]
+    fn test_format_modes() {
+        let profiles: Vec<ModeProfile> = Mode::all().iter().map(ModeProfile::builtin).collect();
+        let out = format_modes(&Mode::Comfort, &profiles);
+        assert!(out.contains("* comfort"));
+        assert!(out.contains("  quiet"));
+        assert!(out.contains("Cool keyboard"));
+        assert!(!format_modes(&Mode::Unknown, &profiles).contains('*'));
+
+        let mut profiles = profiles;
+        profiles.push(ModeProfile { name: "compile".into(), max_perf_pct: Some(100), fan_boost: Some(true), ..Default::default() });
+        let out = format_modes(&Mode::Custom("compile".into()), &profiles);
+        assert!(out.contains("* compile      100%, Fan: BOOST"), "{}", out);
+    }
+
+    #[test]
+    fn test_format_profiles_marks_current() {
+        let choices: Vec<String> = ["low-power", "balanced"].iter().map(|s| s.to_string()).collect();
+        let out = format_profiles("balanced", &choices);
+        assert!(out.contains("* balanced"));
+        assert!(out.contains("  low-power"));
+        assert!(format_profiles("unknown", &[]).contains("unknown"));
+    }
+
+    #[test]
+    fn test_helper_passes_arguments_through() {
+        let cmd = parse(&args(&["helper", "write", "/sys/x=1", "--policy"]), &Config::default());
+        assert_eq!(cmd, Ok(Command::Helper(args(&["write", "/sys/x=1", "--policy"]))));
+    }
+
+    #[test]
+    fn test_format_status_units() {
+        let state = ThermalState {
+            cpu_temp: 50.0,
+            keyboard_temp: 37.9,
+            current_freq_mhz: 2500,
+            max_freq_mhz: 4400,
+            ..Default::default()
+        };
+        let metric = format_status(&state, &Units::default());
+        assert!(metric.contains("50°C"));
+        assert!(metric.contains("2.5 GHz / 4.4 GHz"));
+
+        let imperial = format_status(&state, &Units { temp: TempUnit::Fahrenheit, freq: FreqUnit::MHz });
+        assert!(imperial.contains("122°F"));
+        assert!(imperial.contains("2500 MHz / 4400 MHz"));
+        assert!(!imperial.contains("°C"));
+        assert!(!imperial.contains("Turbo"));
+    }
+
+    #[test]
+    fn test_format_status_tuning() {
+        let state = ThermalState {
+            min_perf_pct: 15,
+            turbo: Some(false),
+            energy_preference: Some("balance_power".into()),
+            ..Default::default()
+        };
+        let out = format_status(&state, &Units::default());
+        assert!(out.contains("15%"));
+        assert!(out.contains("Off"));
+        assert!(out.contains("Balance power"));
+        assert!(!out.contains("Drift"));
+    }
+
+    #[test]
+    fn test_format_status_drift() {
+        let state = ThermalState {
+            mode: Mode::Quiet,
+            mode_drift: vec![WriteResult {
+                write: SysfsWrite::new("/sys/devices/system/cpu/intel_pstate/max_perf_pct", 40),
+                outcome: WriteOutcome::Mismatch("100".into()),
+            }],
+            ..Default::default()
+        };
+        let out = format_status(&state, &Units::default());
+        assert!(out.contains("Drift:"));
+        assert!(out.contains("intel_pstate/max_perf_pct: 40 expected, now 100"), "{}", out);
+    }
+}
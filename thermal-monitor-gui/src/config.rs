@@ -0,0 +1,243 @@
+//! User preferences persisted between sessions
+//!
+//! Stored as a small INI file at `$XDG_CONFIG_HOME/thermal-monitor/config.ini`
+//! (default `~/.config/thermal-monitor/config.ini`). The parser is kept
+//! in-tree to avoid pulling a serialization stack for a handful of keys.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::power_supply::PowerSource;
+use crate::system::Actuator;
+use crate::modes::valid_name;
+use crate::theme::ThemePreference;
+use crate::units::{FreqUnit, TempUnit, Units};
+
+/// Config file name inside the application config directory
+const CONFIG_FILE: &str = "config.ini";
+
+/// Ordered INI document: `[section]` headers, `key = value` pairs, `#`/`;` comments
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ini {
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl Ini {
+    pub fn parse(source: &str) -> Self {
+        let mut ini = Ini::default();
+        let mut current = String::new();
+
+        for line in source.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current = name.trim().to_string();
+                ini.section_mut(&current);
+            } else if let Some((key, value)) = line.split_once('=') {
+                ini.set(&current, key.trim(), value.trim());
+            }
+        }
+        ini
+    }
+
+    fn section_mut(&mut self, name: &str) -> &mut Vec<(String, String)> {
+        let index = match self.sections.iter().position(|(n, _)| n == name) {
+            Some(index) => index,
+            None => {
+                self.sections.push((name.to_string(), Vec::new()));
+                self.sections.len() - 1
+            }
+        };
+        &mut self.sections[index].1
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections
+            .iter()
+            .find(|(name, _)| name == section)
+            .and_then(|(_, pairs)| pairs.iter().find(|(k, _)| k == key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Section names in file order
+    pub fn sections(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        let pairs = self.section_mut(section);
+        match pairs.iter_mut().find(|(k, _)| k == key) {
+            Some(pair) => pair.1 = value.to_string(),
+            None => pairs.push((key.to_string(), value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Ini {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, pairs)) in self.sections.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            if !name.is_empty() {
+                writeln!(f, "[{}]", name)?;
+            }
+            for (key, value) in pairs {
+                writeln!(f, "{} = {}", key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Application preferences
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    pub units: Units,
+    pub theme: ThemePreference,
+    /// Request profiles from power-profiles-daemon instead of writing the
+    /// knobs it manages
+    pub cooperate_ppd: bool,
+    /// Knob automatic thermal control turns
+    pub actuator: Actuator,
+    /// Parts of cgroup names the cgroup actuator throttles
+    pub throttle_groups: Vec<String>,
+    /// Mode applied when the charger is unplugged
+    pub battery_mode: Option<String>,
+    /// Mode applied when the charger is plugged in
+    pub ac_mode: Option<String>,
+    /// Mode applied while the lid is closed
+    pub lid_mode: Option<String>,
+}
+
+impl Config {
+    /// Default config file location
+    pub fn path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("thermal-monitor").join(CONFIG_FILE))
+    }
+
+    /// Load preferences, falling back to defaults when missing or unreadable
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| Self::load_from(&path).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        Ok(Self::from_ini(&Ini::parse(&fs::read_to_string(path)?)))
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No config directory"))?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_ini().to_string())
+    }
+
+    /// Mode to switch to when the power source changes to `source`
+    pub fn mode_for_source(&self, source: PowerSource) -> Option<&str> {
+        match source {
+            PowerSource::Battery => self.battery_mode.as_deref(),
+            PowerSource::Ac => self.ac_mode.as_deref(),
+        }
+    }
+
+    pub fn from_ini(ini: &Ini) -> Self {
+        let defaults = Units::default();
+        let mode = |section: &str, key: &str| ini.get(section, key).filter(|name| valid_name(name)).map(String::from);
+        Self {
+            units: Units {
+                temp: ini.get("units", "temperature").and_then(TempUnit::parse).unwrap_or(defaults.temp),
+                freq: ini.get("units", "frequency").and_then(FreqUnit::parse).unwrap_or(defaults.freq),
+            },
+            theme: ini.get("appearance", "theme").and_then(ThemePreference::parse).unwrap_or_default(),
+            cooperate_ppd: ini.get("integration", "power_profiles_daemon") == Some("cooperate"),
+            actuator: ini.get("control", "actuator").and_then(Actuator::parse).unwrap_or_default(),
+            throttle_groups: ini
+                .get("control", "throttle")
+                .map(|list| list.split(',').map(str::trim).filter(|g| !g.is_empty()).map(String::from).collect())
+                .unwrap_or_default(),
+            battery_mode: mode("power_source", "battery"),
+            ac_mode: mode("power_source", "ac"),
+            lid_mode: mode("session", "lid_closed"),
+        }
+    }
+
+    pub fn to_ini(&self) -> Ini {
+        let mut ini = Ini::default();
+        ini.set("units", "temperature", self.units.temp.key());
+        ini.set("units", "frequency", self.units.freq.key());
+        ini.set("appearance", "theme", self.theme.key());
+        ini.set("integration", "power_profiles_daemon", if self.cooperate_ppd { "cooperate" } else { "ignore" });
+        ini.set("control", "actuator", self.actuator.key());
+        ini.set("control", "throttle", &self.throttle_groups.join(", "));
+        // An empty value leaves the mode alone on that source
+        ini.set("power_source", "battery", self.battery_mode.as_deref().unwrap_or_default());
+        ini.set("power_source", "ac", self.ac_mode.as_deref().unwrap_or_default());
+        ini.set("session", "lid_closed", self.lid_mode.as_deref().unwrap_or_default());
+        ini
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ini_parse() {
+        let ini = Ini::parse("# comment\ntop = 1\n[units]\ntemperature = fahrenheit\n; other\n[b]\nx=y=z\n");
+        assert_eq!(ini.get("", "top"), Some("1"));
+        assert_eq!(ini.get("units", "temperature"), Some("fahrenheit"));
+        assert_eq!(ini.get("b", "x"), Some("y=z"));
+        assert_eq!(ini.get("units", "missing"), None);
+    }
+
+    #[test]
+    fn test_ini_roundtrip() {
+        let mut ini = Ini::default();
+        ini.set("units", "temperature", "celsius");
+        ini.set("units", "temperature", "fahrenheit");
+        ini.set("other", "key", "value");
+        assert_eq!(Ini::parse(&ini.to_string()), ini);
+        assert_eq!(ini.get("units", "temperature"), Some("fahrenheit"));
+    }
+
+    #[test]
+    fn test_config_defaults_on_garbage() {
+        let config = Config::from_ini(&Ini::parse("[units]\ntemperature = kelvin\n"));
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_config_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(CONFIG_FILE);
+        let config = Config {
+            units: Units { temp: TempUnit::Fahrenheit, freq: FreqUnit::MHz },
+            theme: ThemePreference::HighContrast,
+            cooperate_ppd: true,
+            actuator: Actuator::PowerLimit,
+            throttle_groups: vec!["firefox".into(), "build.slice".into()],
+            battery_mode: Some("quiet".into()),
+            ac_mode: None,
+            lid_mode: Some("quiet".into()),
+        };
+        config.save_to(&path).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap(), config);
+        assert_eq!(config.mode_for_source(PowerSource::Battery), Some("quiet"));
+        assert_eq!(config.mode_for_source(PowerSource::Ac), None);
+    }
+}
@@ -0,0 +1,267 @@
+//! Other power managers that write the same knobs
+//!
+//! power-profiles-daemon, TLP, thermald and auto-cpufreq all change
+//! platform profile, EPP or performance limits on their own, so values we
+//! set can flip back seconds later. They are detected through their
+//! systemd units (one `systemctl show` call) and, when systemd cannot be
+//! asked, through their config files.
+//!
+//! power-profiles-daemon can be cooperated with: instead of fighting over
+//! the platform profile and EPP, the profile is requested over its D-Bus
+//! interface (via `busctl`) and only the remaining knobs are written.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cpufreq::SysfsWrite;
+use crate::i18n::{tr, tr_args};
+use crate::modes::ModeProfile;
+
+/// D-Bus name, object and interface of power-profiles-daemon
+const PPD_BUS: [&str; 3] = ["net.hadess.PowerProfiles", "/net/hadess/PowerProfiles", "net.hadess.PowerProfiles"];
+
+/// Attributes power-profiles-daemon manages itself
+const PPD_ATTRIBUTES: &[&str] = &["platform_profile", "profile", "energy_performance_preference"];
+
+/// A known power manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Manager {
+    PowerProfilesDaemon,
+    Tlp,
+    Thermald,
+    AutoCpufreq,
+}
+
+impl Manager {
+    pub fn all() -> &'static [Manager] {
+        &[Manager::PowerProfilesDaemon, Manager::Tlp, Manager::Thermald, Manager::AutoCpufreq]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Manager::PowerProfilesDaemon => "power-profiles-daemon",
+            Manager::Tlp => "TLP",
+            Manager::Thermald => "thermald",
+            Manager::AutoCpufreq => "auto-cpufreq",
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Manager::PowerProfilesDaemon => "power-profiles-daemon.service",
+            Manager::Tlp => "tlp.service",
+            Manager::Thermald => "thermald.service",
+            Manager::AutoCpufreq => "auto-cpufreq.service",
+        }
+    }
+
+    /// Files that exist when the manager is installed, relative to `/`
+    fn config_files(&self) -> &'static [&'static str] {
+        match self {
+            Manager::PowerProfilesDaemon => &["var/lib/power-profiles-daemon/state.ini"],
+            Manager::Tlp => &["etc/tlp.conf", "etc/default/tlp"],
+            Manager::Thermald => &["etc/thermald/thermal-conf.xml"],
+            Manager::AutoCpufreq => &["etc/auto-cpufreq.conf"],
+        }
+    }
+
+    /// Localized list of the knobs it overrides
+    pub fn knobs(&self) -> &'static str {
+        match self {
+            Manager::PowerProfilesDaemon => tr("conflict-knobs-ppd"),
+            Manager::Tlp => tr("conflict-knobs-tlp"),
+            Manager::Thermald => tr("conflict-knobs-thermald"),
+            Manager::AutoCpufreq => tr("conflict-knobs-auto-cpufreq"),
+        }
+    }
+}
+
+/// How a manager was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Presence {
+    /// Unit is running
+    Active,
+    /// Unit is stopped but starts at boot
+    Enabled,
+    /// Unit state unknown, config file found
+    Installed(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub manager: Manager,
+    pub presence: Presence,
+}
+
+impl Conflict {
+    /// Banner text, e.g. "TLP is running and may override performance limits, EPP and turbo"
+    pub fn message(&self) -> String {
+        let key = match self.presence {
+            Presence::Active => "conflict-active",
+            Presence::Enabled => "conflict-enabled",
+            Presence::Installed(_) => "conflict-installed",
+        };
+        tr_args(key, &[("manager", self.manager.name()), ("knobs", self.manager.knobs())])
+    }
+
+    /// Command that turns the manager off
+    pub fn remedy(&self) -> String {
+        format!("sudo systemctl disable --now {}", self.manager.unit())
+    }
+}
+
+/// Parse `systemctl show -p Id,ActiveState,UnitFileState` output into
+/// (unit, active, enabled) per unit
+fn parse_units(output: &str) -> Vec<(String, bool, bool)> {
+    output
+        .split("\n\n")
+        .filter_map(|block| {
+            let field = |name: &str| {
+                block.lines().find_map(|line| line.strip_prefix(name)?.strip_prefix('=')).unwrap_or_default()
+            };
+            let id = field("Id");
+            let active = matches!(field("ActiveState"), "active" | "activating" | "reloading");
+            (!id.is_empty()).then(|| (id.to_string(), active, field("UnitFileState") == "enabled"))
+        })
+        .collect()
+}
+
+/// Conflicts from unit states (`None` when systemd could not be asked) and
+/// config files below `root`
+fn detect_from(units: Option<&str>, root: &Path) -> Vec<Conflict> {
+    let states = units.map(parse_units);
+    Manager::all()
+        .iter()
+        .filter_map(|&manager| {
+            let presence = match &states {
+                Some(states) => match states.iter().find(|(id, _, _)| id == manager.unit()) {
+                    Some((_, true, _)) => Presence::Active,
+                    Some((_, false, true)) => Presence::Enabled,
+                    _ => return None,
+                },
+                None => Presence::Installed(
+                    manager.config_files().iter().map(|file| root.join(file)).find(|path| path.exists())?,
+                ),
+            };
+            Some(Conflict { manager, presence })
+        })
+        .collect()
+}
+
+/// Power managers that may override our writes
+pub fn detect() -> Vec<Conflict> {
+    let units: Vec<&str> = Manager::all().iter().map(Manager::unit).collect();
+    let output = Command::new("systemctl")
+        .args(["show", "-p", "Id,ActiveState,UnitFileState"])
+        .args(&units)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+    detect_from(output.as_deref(), Path::new("/"))
+}
+
+/// Whether power-profiles-daemon is running
+pub fn ppd_active(conflicts: &[Conflict]) -> bool {
+    conflicts.iter().any(|c| c.manager == Manager::PowerProfilesDaemon && c.presence == Presence::Active)
+}
+
+/// Whether power-profiles-daemon manages this attribute
+pub fn ppd_owns(path: &Path) -> bool {
+    path.file_name().and_then(|name| name.to_str()).is_some_and(|name| PPD_ATTRIBUTES.contains(&name))
+}
+
+/// Drop writes power-profiles-daemon is responsible for
+pub fn without_ppd_knobs(writes: Vec<SysfsWrite>) -> Vec<SysfsWrite> {
+    writes.into_iter().filter(|w| !ppd_owns(&w.path)).collect()
+}
+
+/// power-profiles-daemon profile matching a platform profile or EPP value
+pub fn ppd_profile(value: &str) -> &'static str {
+    match value {
+        "low-power" | "quiet" | "cool" | "power" | "balance_power" => "power-saver",
+        "performance" | "balanced-performance" | "max-power" => "performance",
+        _ => "balanced",
+    }
+}
+
+/// power-profiles-daemon profile for a mode, if it sets a profile or EPP
+pub fn ppd_profile_for(profile: &ModeProfile) -> Option<&'static str> {
+    profile.platform_profiles.first().or(profile.energy_preference.as_ref()).map(|value| ppd_profile(value))
+}
+
+/// Ask power-profiles-daemon to switch profile; no root needed
+pub fn set_ppd_profile(profile: &str) -> io::Result<()> {
+    let [name, object, interface] = PPD_BUS;
+    let output = Command::new("busctl")
+        .args(["set-property", name, object, interface, "ActiveProfile", "s", profile])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("power-profiles-daemon: {}", stderr.trim())));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+    use crate::system::Mode;
+
+    const SHOW: &str = "Id=power-profiles-daemon.service\nActiveState=active\nUnitFileState=enabled\n\n\
+                        Id=tlp.service\nActiveState=inactive\nUnitFileState=enabled\n\n\
+                        Id=thermald.service\nActiveState=inactive\nUnitFileState=disabled\n\n\
+                        Id=auto-cpufreq.service\nActiveState=inactive\nUnitFileState=\n";
+
+    #[test]
+    fn test_parse_units() {
+        let units = parse_units(SHOW);
+        assert_eq!(units.len(), 4);
+        assert_eq!(units[0], ("power-profiles-daemon.service".to_string(), true, true));
+        assert_eq!(units[3], ("auto-cpufreq.service".to_string(), false, false));
+    }
+
+    #[test]
+    fn test_detect_from_units() {
+        let root = tempfile::tempdir().unwrap();
+        put(root.path(), "etc/thermald/thermal-conf.xml", "");
+        // Config files only count when systemd cannot be asked
+        let found = detect_from(Some(SHOW), root.path());
+        assert_eq!(
+            found,
+            [
+                Conflict { manager: Manager::PowerProfilesDaemon, presence: Presence::Active },
+                Conflict { manager: Manager::Tlp, presence: Presence::Enabled },
+            ]
+        );
+        assert!(ppd_active(&found));
+        assert!(found[1].message().starts_with("TLP is enabled at boot"));
+        assert_eq!(found[1].remedy(), "sudo systemctl disable --now tlp.service");
+
+        let found = detect_from(None, root.path());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].presence, Presence::Installed(root.path().join("etc/thermald/thermal-conf.xml")));
+        assert!(!ppd_active(&found));
+    }
+
+    #[test]
+    fn test_ppd_cooperation() {
+        assert_eq!(ppd_profile_for(&ModeProfile::builtin(&Mode::Quiet)), Some("power-saver"));
+        assert_eq!(ppd_profile_for(&ModeProfile::builtin(&Mode::Comfort)), Some("balanced"));
+        assert_eq!(ppd_profile_for(&ModeProfile::builtin(&Mode::Performance)), Some("performance"));
+        let epp_only = ModeProfile { energy_preference: Some("power".into()), ..Default::default() };
+        assert_eq!(ppd_profile_for(&epp_only), Some("power-saver"));
+        assert_eq!(ppd_profile_for(&ModeProfile::default()), None);
+
+        let writes = vec![
+            SysfsWrite::new("/sys/firmware/acpi/platform_profile", "quiet"),
+            SysfsWrite::new("/sys/devices/system/cpu/intel_pstate/max_perf_pct", 40),
+            SysfsWrite::new("/sys/devices/system/cpu/cpufreq/policy0/energy_performance_preference", "power"),
+        ];
+        let kept = without_ppd_knobs(writes);
+        assert_eq!(kept.len(), 1);
+        assert!(kept[0].path.ends_with("max_perf_pct"));
+    }
+}
@@ -0,0 +1,623 @@
+//! CPU frequency driver abstraction
+//!
+//! Each scaling driver exposes the performance ceiling through different
+//! sysfs attributes. `intel_pstate` has a global `max_perf_pct`, while
+//! amd-pstate and acpi-cpufreq only offer per-CPU `scaling_max_freq`,
+//! so the percentage is derived from the hardware maximum.
+//!
+//! Turbo is `intel_pstate/no_turbo` on Intel and `cpufreq/boost` elsewhere;
+//! the Energy Performance Preference (EPP) is a per-policy attribute shared
+//! by intel_pstate and amd-pstate in active mode.
+//!
+//! Limits are applied per cpufreq policy (`cpufreq/policyN`), which
+//! also allows different ceilings for different core clusters.
+//!
+//! All paths are relative to a CPU sysfs root so the drivers can be
+//! exercised against a fake tree in tests.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::system::read_sysfs_value;
+
+/// Default CPU sysfs root
+pub const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Lowest performance ceiling we ever apply
+pub const MIN_PERF_PCT: u8 = 20;
+
+/// A single sysfs attribute write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysfsWrite {
+    pub path: PathBuf,
+    pub value: String,
+}
+
+impl SysfsWrite {
+    pub fn new(path: impl Into<PathBuf>, value: impl ToString) -> Self {
+        Self { path: path.into(), value: value.to_string() }
+    }
+}
+
+/// A cpufreq policy: a group of CPUs sharing one frequency limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    pub index: u32,
+    pub dir: PathBuf,
+}
+
+impl Policy {
+    /// Hardware maximum in kHz, preferring amd-pstate's boost maximum
+    pub fn hardware_max_khz(&self) -> io::Result<u64> {
+        read_khz(self.dir.join("amd_pstate_max_freq")).or_else(|_| read_khz(self.dir.join("cpuinfo_max_freq")))
+    }
+
+    pub fn scaling_max_khz(&self) -> io::Result<u64> {
+        read_khz(self.dir.join("scaling_max_freq"))
+    }
+
+    /// Current ceiling as a percentage of the hardware maximum
+    pub fn max_perf_pct(&self) -> io::Result<u8> {
+        ratio_pct(self.scaling_max_khz()?, self.hardware_max_khz()?)
+    }
+
+    /// Write that limits this policy to `pct` of its hardware maximum
+    pub fn max_perf_write(&self, pct: u8) -> io::Result<SysfsWrite> {
+        Ok(SysfsWrite::new(self.dir.join("scaling_max_freq"), scale_khz(self.hardware_max_khz()?, pct)))
+    }
+
+    /// Current floor as a percentage of the hardware maximum
+    pub fn min_perf_pct(&self) -> io::Result<u8> {
+        ratio_pct(read_khz(self.dir.join("scaling_min_freq"))?, self.hardware_max_khz()?)
+    }
+
+    /// Write that keeps this policy at or above `pct` of its hardware maximum
+    /// Never below `cpuinfo_min_freq`, which the kernel would reject
+    pub fn min_perf_write(&self, pct: u8) -> io::Result<SysfsWrite> {
+        let khz = scale_khz(self.hardware_max_khz()?, pct);
+        let floor = read_khz(self.dir.join("cpuinfo_min_freq")).unwrap_or(0);
+        Ok(SysfsWrite::new(self.dir.join("scaling_min_freq"), khz.max(floor)))
+    }
+}
+
+/// Operating mode reported by `amd_pstate/status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmdPstateMode {
+    Active,
+    Passive,
+    Guided,
+}
+
+impl AmdPstateMode {
+    pub fn parse(status: &str) -> Option<Self> {
+        match status.trim() {
+            "active" => Some(AmdPstateMode::Active),
+            "passive" => Some(AmdPstateMode::Passive),
+            "guided" => Some(AmdPstateMode::Guided),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AmdPstateMode::Active => "active",
+            AmdPstateMode::Passive => "passive",
+            AmdPstateMode::Guided => "guided",
+        }
+    }
+}
+
+/// Scaling driver able to report and limit the CPU performance ceiling
+pub trait CpuFreqDriver {
+    /// Human-readable driver name, e.g. "amd-pstate (active)"
+    fn name(&self) -> String;
+
+    /// Effective maximum performance as a percentage of hardware maximum
+    fn max_perf_pct(&self) -> io::Result<u8>;
+
+    /// Sysfs writes that set the performance ceiling to `pct`
+    fn max_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>>;
+
+    /// Minimum performance floor as a percentage of hardware maximum
+    fn min_perf_pct(&self) -> io::Result<u8>;
+
+    /// Sysfs writes that set the performance floor to `pct`
+    fn min_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>>;
+
+    /// Whether turbo/boost frequencies are allowed, `None` if not controllable
+    fn turbo_enabled(&self) -> Option<bool>;
+
+    /// Sysfs writes that allow or forbid turbo/boost frequencies
+    fn turbo_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>>;
+
+    /// Sysfs writes for individual per-policy ceilings: (policy index, pct)
+    /// Every driver honours `scaling_max_freq`, so this is shared
+    fn policy_limit_writes(&self, root: &Path, limits: &[(u32, u8)]) -> io::Result<Vec<SysfsWrite>> {
+        let policies = policies(root);
+        limits
+            .iter()
+            .map(|&(index, pct)| {
+                policies
+                    .iter()
+                    .find(|p| p.index == index)
+                    .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("No cpufreq policy{}", index)))?
+                    .max_perf_write(pct)
+            })
+            .collect()
+    }
+}
+
+/// Intel P-state driver (active or passive mode)
+pub struct IntelPstate {
+    root: PathBuf,
+}
+
+impl CpuFreqDriver for IntelPstate {
+    fn name(&self) -> String {
+        match read_sysfs_value(self.root.join("intel_pstate/status")) {
+            Ok(status) => format!("intel_pstate ({})", status),
+            Err(_) => "intel_pstate".into(),
+        }
+    }
+
+    fn max_perf_pct(&self) -> io::Result<u8> {
+        let content = read_sysfs_value(self.root.join("intel_pstate/max_perf_pct"))?;
+        content.parse::<u8>().map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+
+    fn max_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>> {
+        Ok(vec![SysfsWrite::new(self.root.join("intel_pstate/max_perf_pct"), pct)])
+    }
+
+    fn min_perf_pct(&self) -> io::Result<u8> {
+        let content = read_sysfs_value(self.root.join("intel_pstate/min_perf_pct"))?;
+        content.parse::<u8>().map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+
+    fn min_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>> {
+        Ok(vec![SysfsWrite::new(self.root.join("intel_pstate/min_perf_pct"), pct)])
+    }
+
+    fn turbo_enabled(&self) -> Option<bool> {
+        read_sysfs_value(self.root.join("intel_pstate/no_turbo")).ok().map(|v| v == "0")
+    }
+
+    fn turbo_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+        let no_turbo = if enabled { "0" } else { "1" };
+        Ok(vec![SysfsWrite::new(self.root.join("intel_pstate/no_turbo"), no_turbo)])
+    }
+}
+
+/// AMD P-state driver; the ceiling is `scaling_max_freq` relative to
+/// `amd_pstate_max_freq` (falling back to `cpuinfo_max_freq`)
+pub struct AmdPstate {
+    root: PathBuf,
+    mode: Option<AmdPstateMode>,
+}
+
+impl CpuFreqDriver for AmdPstate {
+    fn name(&self) -> String {
+        match self.mode {
+            Some(mode) => format!("amd-pstate ({})", mode.name()),
+            None => "amd-pstate".into(),
+        }
+    }
+
+    fn max_perf_pct(&self) -> io::Result<u8> {
+        first_policy(&self.root)?.max_perf_pct()
+    }
+
+    fn max_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>> {
+        policies(&self.root).iter().map(|p| p.max_perf_write(pct)).collect()
+    }
+
+    fn min_perf_pct(&self) -> io::Result<u8> {
+        first_policy(&self.root)?.min_perf_pct()
+    }
+
+    fn min_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>> {
+        policies(&self.root).iter().map(|p| p.min_perf_write(pct)).collect()
+    }
+
+    fn turbo_enabled(&self) -> Option<bool> {
+        boost_enabled(&self.root)
+    }
+
+    fn turbo_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+        boost_writes(&self.root, enabled)
+    }
+}
+
+/// Generic cpufreq driver (acpi-cpufreq and friends)
+pub struct AcpiCpufreq {
+    root: PathBuf,
+}
+
+impl CpuFreqDriver for AcpiCpufreq {
+    fn name(&self) -> String {
+        first_policy(&self.root)
+            .and_then(|p| read_sysfs_value(p.dir.join("scaling_driver")))
+            .unwrap_or_else(|_| "cpufreq".into())
+    }
+
+    fn max_perf_pct(&self) -> io::Result<u8> {
+        first_policy(&self.root)?.max_perf_pct()
+    }
+
+    fn max_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>> {
+        policies(&self.root).iter().map(|p| p.max_perf_write(pct)).collect()
+    }
+
+    fn min_perf_pct(&self) -> io::Result<u8> {
+        first_policy(&self.root)?.min_perf_pct()
+    }
+
+    fn min_perf_writes(&self, pct: u8) -> io::Result<Vec<SysfsWrite>> {
+        policies(&self.root).iter().map(|p| p.min_perf_write(pct)).collect()
+    }
+
+    fn turbo_enabled(&self) -> Option<bool> {
+        boost_enabled(&self.root)
+    }
+
+    fn turbo_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+        boost_writes(&self.root, enabled)
+    }
+}
+
+/// Pick the driver for the running system
+/// Uses `scaling_driver` first, then the presence of driver directories
+pub fn detect_driver(root: &Path) -> Box<dyn CpuFreqDriver> {
+    let root = root.to_path_buf();
+    let scaling_driver = first_policy(&root)
+        .and_then(|p| read_sysfs_value(p.dir.join("scaling_driver")))
+        .unwrap_or_default();
+
+    if scaling_driver.starts_with("intel_") || root.join("intel_pstate/max_perf_pct").exists() {
+        return Box::new(IntelPstate { root });
+    }
+    if scaling_driver.starts_with("amd-pstate") || root.join("amd_pstate").is_dir() {
+        let mode = read_sysfs_value(root.join("amd_pstate/status"))
+            .ok()
+            .and_then(|s| AmdPstateMode::parse(&s));
+        return Box::new(AmdPstate { root, mode });
+    }
+    Box::new(AcpiCpufreq { root })
+}
+
+/// `boost` files: the global `cpufreq/boost`, or per-policy ones on
+/// drivers that only expose it there (amd-pstate in active mode)
+fn boost_files(root: &Path) -> Vec<PathBuf> {
+    let global = root.join("cpufreq/boost");
+    if global.exists() {
+        return vec![global];
+    }
+    policies(root)
+        .into_iter()
+        .map(|p| p.dir.join("boost"))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+fn boost_enabled(root: &Path) -> Option<bool> {
+    let first = boost_files(root).into_iter().next()?;
+    read_sysfs_value(first).ok().map(|v| v == "1")
+}
+
+fn boost_writes(root: &Path, enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+    let files = boost_files(root);
+    if files.is_empty() {
+        return Err(io::Error::new(ErrorKind::Unsupported, "Turbo control not available"));
+    }
+    let value = if enabled { "1" } else { "0" };
+    Ok(files.into_iter().map(|path| SysfsWrite::new(path, value)).collect())
+}
+
+/// Energy Performance Preference of the first policy, if exposed
+pub fn energy_preference(root: &Path) -> Option<String> {
+    let policy = first_policy(root).ok()?;
+    read_sysfs_value(policy.dir.join("energy_performance_preference")).ok()
+}
+
+/// EPP values the driver accepts (`energy_performance_available_preferences`)
+pub fn energy_preference_choices(root: &Path) -> Vec<String> {
+    first_policy(root)
+        .and_then(|p| read_sysfs_value(p.dir.join("energy_performance_available_preferences")))
+        .map(|list| list.split_whitespace().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Sysfs writes that set the EPP of every policy; `value` must be one of
+/// the advertised choices
+pub fn energy_preference_writes(root: &Path, value: &str) -> io::Result<Vec<SysfsWrite>> {
+    if !energy_preference_choices(root).iter().any(|choice| choice == value) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Unsupported energy preference '{}'", value),
+        ));
+    }
+    Ok(policies(root)
+        .into_iter()
+        .map(|p| p.dir.join("energy_performance_preference"))
+        .filter(|path| path.exists())
+        .map(|path| SysfsWrite::new(path, value))
+        .collect())
+}
+
+/// List `<dir>/<prefix>N` entries that contain `sub`, sorted by N
+fn numbered_dirs(dir: &Path, prefix: &str, sub: &str) -> Vec<(u32, PathBuf)> {
+    let mut found: Vec<(u32, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let index = name.strip_prefix(prefix)?.parse::<u32>().ok()?;
+            let path = entry.path().join(sub);
+            path.is_dir().then_some((index, path))
+        })
+        .collect();
+    found.sort_by_key(|(index, _)| *index);
+    found
+}
+
+/// cpufreq policies in index order
+/// Falls back to per-CPU `cpuN/cpufreq` on kernels without `cpufreq/policyN`
+pub fn policies(root: &Path) -> Vec<Policy> {
+    let mut found = numbered_dirs(&root.join("cpufreq"), "policy", "");
+    if found.is_empty() {
+        found = numbered_dirs(root, "cpu", "cpufreq");
+    }
+    found
+        .into_iter()
+        .map(|(index, dir)| Policy { index, dir })
+        .collect()
+}
+
+fn first_policy(root: &Path) -> io::Result<Policy> {
+    policies(root)
+        .into_iter()
+        .next()
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "No cpufreq policy found"))
+}
+
+fn read_khz(path: impl AsRef<Path>) -> io::Result<u64> {
+    read_sysfs_value(path)?
+        .parse::<u64>()
+        .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+/// `value / max` as a rounded percentage, clamped to 0-100
+fn ratio_pct(value: u64, max: u64) -> io::Result<u8> {
+    if max == 0 {
+        return Err(io::Error::new(ErrorKind::InvalidData, "Zero maximum frequency"));
+    }
+    Ok(((value * 100 + max / 2) / max).min(100) as u8)
+}
+
+fn scale_khz(max_khz: u64, pct: u8) -> u64 {
+    max_khz * pct as u64 / 100
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Write a file, creating parent directories
+    pub(crate) fn put(root: &Path, rel: &str, value: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value).unwrap();
+    }
+
+    /// Fake CPU tree with one policy per CPU and the given scaling driver
+    pub(crate) fn fake_cpus(root: &Path, cpus: usize, driver: &str, max_khz: u64, scaling_khz: u64) {
+        for cpu in 0..cpus {
+            let dir = format!("cpufreq/policy{}", cpu);
+            put(root, &format!("{}/related_cpus", dir), &cpu.to_string());
+            put(root, &format!("{}/scaling_driver", dir), driver);
+            put(root, &format!("{}/cpuinfo_max_freq", dir), &max_khz.to_string());
+            put(root, &format!("{}/scaling_max_freq", dir), &scaling_khz.to_string());
+        }
+    }
+
+    #[test]
+    fn test_detect_intel_pstate() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 2, "intel_pstate", 4_400_000, 4_400_000);
+        put(dir.path(), "intel_pstate/max_perf_pct", "60\n");
+        put(dir.path(), "intel_pstate/status", "active\n");
+
+        let driver = detect_driver(dir.path());
+        assert_eq!(driver.name(), "intel_pstate (active)");
+        assert_eq!(driver.max_perf_pct().unwrap(), 60);
+        let writes = driver.max_perf_writes(75).unwrap();
+        assert_eq!(writes, vec![SysfsWrite::new(dir.path().join("intel_pstate/max_perf_pct"), 75)]);
+    }
+
+    #[test]
+    fn test_detect_amd_pstate_modes() {
+        for (status, name) in [
+            ("active", "amd-pstate (active)"),
+            ("passive", "amd-pstate (passive)"),
+            ("guided", "amd-pstate (guided)"),
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            fake_cpus(dir.path(), 1, "amd-pstate-epp", 3_000_000, 3_000_000);
+            put(dir.path(), "amd_pstate/status", status);
+            assert_eq!(detect_driver(dir.path()).name(), name);
+        }
+    }
+
+    #[test]
+    fn test_amd_pstate_uses_amd_pstate_max_freq() {
+        let dir = tempfile::tempdir().unwrap();
+        // cpuinfo_max_freq is the nominal frequency; boost max is higher
+        fake_cpus(dir.path(), 2, "amd-pstate-epp", 3_300_000, 2_400_000);
+        put(dir.path(), "cpufreq/policy0/amd_pstate_max_freq", "4800000");
+        put(dir.path(), "cpufreq/policy1/amd_pstate_max_freq", "4800000");
+        put(dir.path(), "amd_pstate/status", "active");
+
+        let driver = detect_driver(dir.path());
+        assert_eq!(driver.max_perf_pct().unwrap(), 50);
+
+        let writes = driver.max_perf_writes(75).unwrap();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].path, dir.path().join("cpufreq/policy0/scaling_max_freq"));
+        assert_eq!(writes[0].value, "3600000");
+    }
+
+    #[test]
+    fn test_amd_pstate_never_writes_max_perf_pct() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 1, "amd-pstate", 4_000_000, 4_000_000);
+        put(dir.path(), "amd_pstate/status", "passive");
+
+        let writes = detect_driver(dir.path()).max_perf_writes(50).unwrap();
+        assert!(writes.iter().all(|w| !w.path.ends_with("max_perf_pct")));
+        assert_eq!(writes[0].value, "2000000");
+    }
+
+    #[test]
+    fn test_acpi_cpufreq() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 4, "acpi-cpufreq", 2_000_000, 1_500_000);
+
+        let driver = detect_driver(dir.path());
+        assert_eq!(driver.name(), "acpi-cpufreq");
+        assert_eq!(driver.max_perf_pct().unwrap(), 75);
+        assert_eq!(driver.max_perf_writes(40).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_policies_sorted_numerically() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 12, "acpi-cpufreq", 1, 1);
+        put(dir.path(), "cpufreq/boost", "1");
+        let found = policies(dir.path());
+        assert_eq!(found.len(), 12);
+        assert_eq!(found[2].dir, dir.path().join("cpufreq/policy2"));
+        assert_eq!(found[11].index, 11);
+    }
+
+    #[test]
+    fn test_policies_fall_back_to_per_cpu_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        put(dir.path(), "cpu0/cpufreq/scaling_max_freq", "1000");
+        put(dir.path(), "cpu1/cpufreq/scaling_max_freq", "1000");
+        put(dir.path(), "cpuidle/state", "x");
+        let found = policies(dir.path());
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].dir, dir.path().join("cpu1/cpufreq"));
+    }
+
+    #[test]
+    fn test_intel_min_perf_and_turbo() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 2, "intel_pstate", 4_400_000, 4_400_000);
+        put(dir.path(), "intel_pstate/max_perf_pct", "100");
+        put(dir.path(), "intel_pstate/min_perf_pct", "10");
+        put(dir.path(), "intel_pstate/no_turbo", "1");
+
+        let driver = detect_driver(dir.path());
+        assert_eq!(driver.min_perf_pct().unwrap(), 10);
+        assert_eq!(driver.turbo_enabled(), Some(false));
+        assert_eq!(
+            driver.turbo_writes(true).unwrap(),
+            vec![SysfsWrite::new(dir.path().join("intel_pstate/no_turbo"), "0")]
+        );
+        assert_eq!(
+            driver.min_perf_writes(25).unwrap(),
+            vec![SysfsWrite::new(dir.path().join("intel_pstate/min_perf_pct"), 25)]
+        );
+    }
+
+    #[test]
+    fn test_cpufreq_min_perf_and_boost() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 2, "acpi-cpufreq", 3_000_000, 3_000_000);
+        put(dir.path(), "cpufreq/policy0/scaling_min_freq", "600000");
+        put(dir.path(), "cpufreq/policy0/cpuinfo_min_freq", "400000");
+        put(dir.path(), "cpufreq/policy1/cpuinfo_min_freq", "400000");
+
+        let driver = detect_driver(dir.path());
+        assert_eq!(driver.min_perf_pct().unwrap(), 20);
+        // 10% of 3 GHz is below cpuinfo_min_freq, so the floor wins
+        let writes = driver.min_perf_writes(10).unwrap();
+        assert_eq!(writes[1], SysfsWrite::new(dir.path().join("cpufreq/policy1/scaling_min_freq"), 400_000));
+
+        assert_eq!(driver.turbo_enabled(), None);
+        assert!(driver.turbo_writes(true).is_err());
+        put(dir.path(), "cpufreq/boost", "1");
+        assert_eq!(driver.turbo_enabled(), Some(true));
+        assert_eq!(driver.turbo_writes(false).unwrap(), vec![SysfsWrite::new(dir.path().join("cpufreq/boost"), "0")]);
+    }
+
+    #[test]
+    fn test_amd_per_policy_boost() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 2, "amd-pstate-epp", 3_000_000, 3_000_000);
+        put(dir.path(), "amd_pstate/status", "active");
+        put(dir.path(), "cpufreq/policy0/boost", "0");
+        put(dir.path(), "cpufreq/policy1/boost", "0");
+
+        let driver = detect_driver(dir.path());
+        assert_eq!(driver.turbo_enabled(), Some(false));
+        assert_eq!(driver.turbo_writes(true).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_energy_preference() {
+        let dir = tempfile::tempdir().unwrap();
+        fake_cpus(dir.path(), 2, "intel_pstate", 4_400_000, 4_400_000);
+        assert_eq!(energy_preference(dir.path()), None);
+        assert!(energy_preference_choices(dir.path()).is_empty());
+
+        for policy in 0..2 {
+            put(dir.path(), &format!("cpufreq/policy{}/energy_performance_preference", policy), "balance_performance\n");
+            put(
+                dir.path(),
+                &format!("cpufreq/policy{}/energy_performance_available_preferences", policy),
+                "default performance balance_performance balance_power power \n",
+            );
+        }
+        assert_eq!(energy_preference(dir.path()).as_deref(), Some("balance_performance"));
+        assert_eq!(energy_preference_choices(dir.path()).len(), 5);
+
+        let writes = energy_preference_writes(dir.path(), "power").unwrap();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[1], SysfsWrite::new(dir.path().join("cpufreq/policy1/energy_performance_preference"), "power"));
+        assert!(energy_preference_writes(dir.path(), "turbo; rm -rf /").is_err());
+    }
+
+    #[test]
+    fn test_per_policy_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        // Hybrid CPU: P-core policy0 at 4.4 GHz, E-core policy1 at 3.3 GHz
+        fake_cpus(dir.path(), 2, "intel_pstate", 4_400_000, 4_400_000);
+        put(dir.path(), "cpufreq/policy1/cpuinfo_max_freq", "3300000");
+        put(dir.path(), "intel_pstate/max_perf_pct", "100");
+
+        let driver = detect_driver(dir.path());
+        let writes = driver.policy_limit_writes(dir.path(), &[(0, 50), (1, 100)]).unwrap();
+        assert_eq!(writes[0], SysfsWrite::new(dir.path().join("cpufreq/policy0/scaling_max_freq"), 2_200_000));
+        assert_eq!(writes[1], SysfsWrite::new(dir.path().join("cpufreq/policy1/scaling_max_freq"), 3_300_000));
+        assert!(driver.policy_limit_writes(dir.path(), &[(7, 50)]).is_err());
+    }
+
+    #[test]
+    fn test_no_cpufreq() {
+        let dir = tempfile::tempdir().unwrap();
+        let driver = detect_driver(dir.path());
+        assert!(driver.max_perf_pct().is_err());
+        assert!(driver.max_perf_writes(50).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ratio_pct() {
+        assert_eq!(ratio_pct(2_200_000, 4_400_000).unwrap(), 50);
+        assert_eq!(ratio_pct(5_000_000, 4_400_000).unwrap(), 100);
+        assert!(ratio_pct(1, 0).is_err());
+    }
+}
@@ -0,0 +1,508 @@
+//! Fan control backends
+//!
+//! Laptops expose fan control in very different places:
+//! - `ideapad_acpi`: `fan_mode` on the VPC2004 device (0 = auto, 1 = boost)
+//! - `thinkpad_acpi`: `/proc/acpi/ibm/fan` with `level auto|full-speed|0-7`,
+//!   writable only when the module is loaded with `fan_control=1`
+//! - hwmon: `pwmN` duty cycle (0-255) with `pwmN_enable` (1 = manual, 2 = auto)
+//!
+//! The first backend found is used; without one the fan controls are hidden.
+//! It is detected once and again after a mode change or a fan curve save
+//! ([`redetect`]), not on every sample.
+//!
+//! Boost is shared between several requesters (the user, automatic thermal
+//! control, the applied mode) through [`BoostController`]: it stays on while
+//! anyone asks for it, automatic requests linger for a hold time after their
+//! condition clears, and it is only written when that combined wish changes.
+//!
+//! Fan speed is read separately, since some drivers report RPM without
+//! offering control: hwmon `fanN_input` (including `thinkpad` and
+//! `dell_smm`), then the `speed:` line of the ThinkPad fan file.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::cpufreq::SysfsWrite;
+use crate::fan_curve;
+use crate::platform_profile::SYSFS_ROOT;
+use crate::system::read_sysfs_value;
+
+/// Default procfs mount point
+pub const PROCFS_ROOT: &str = "/proc";
+
+/// Where the IdeaPad driver is usually bound, relative to the sysfs root
+const IDEAPAD_DRIVER: &str = "bus/platform/drivers/ideapad_acpi";
+
+/// Fixed VPC2004 path of the IdeaPad model this tool started on
+const IDEAPAD_FAN_MODE: &str = "devices/pci0000:00/0000:00:1f.0/PNP0C09:00/VPC2004:00/fan_mode";
+
+/// ThinkPad fan interface, relative to the procfs root
+const THINKPAD_FAN: &str = "acpi/ibm/fan";
+
+/// Full duty cycle for hwmon PWM channels
+const PWM_MAX: u8 = 255;
+
+/// A way to switch the fan between automatic control and maximum speed
+pub trait FanBackend: Send + Sync {
+    /// Driver name shown to the user, e.g. "thinkpad_acpi"
+    fn name(&self) -> &'static str;
+
+    /// Whether boost (maximum speed) is on, `None` if not controllable
+    fn boost_enabled(&self) -> Option<bool>;
+
+    /// Writes that switch boost on, or hand the fan back to the firmware
+    fn boost_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>>;
+}
+
+/// Lenovo IdeaPad `fan_mode`
+pub struct IdeapadAcpi {
+    path: PathBuf,
+}
+
+impl FanBackend for IdeapadAcpi {
+    fn name(&self) -> &'static str {
+        "ideapad_acpi"
+    }
+
+    fn boost_enabled(&self) -> Option<bool> {
+        read_sysfs_value(&self.path).ok().map(|mode| mode == "1")
+    }
+
+    fn boost_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+        Ok(vec![SysfsWrite::new(&self.path, if enabled { "1" } else { "0" })])
+    }
+}
+
+/// Lenovo ThinkPad `/proc/acpi/ibm/fan`
+pub struct ThinkpadAcpi {
+    path: PathBuf,
+}
+
+impl FanBackend for ThinkpadAcpi {
+    fn name(&self) -> &'static str {
+        "thinkpad_acpi"
+    }
+
+    fn boost_enabled(&self) -> Option<bool> {
+        let status = read_sysfs_value(&self.path).ok()?;
+        thinkpad_level(&status).map(|level| level == "full-speed")
+    }
+
+    fn boost_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+        Ok(vec![SysfsWrite::new(&self.path, if enabled { "level full-speed" } else { "level auto" })])
+    }
+}
+
+/// hwmon PWM channel (`pwmN` plus `pwmN_enable`)
+pub struct HwmonPwm {
+    pwm: PathBuf,
+}
+
+impl HwmonPwm {
+    fn enable_path(&self) -> PathBuf {
+        pwm_enable_path(&self.pwm)
+    }
+}
+
+/// `pwmN_enable` next to a `pwmN` channel
+pub fn pwm_enable_path(pwm: &Path) -> PathBuf {
+    let mut name = pwm.as_os_str().to_owned();
+    name.push("_enable");
+    PathBuf::from(name)
+}
+
+impl FanBackend for HwmonPwm {
+    fn name(&self) -> &'static str {
+        "hwmon"
+    }
+
+    fn boost_enabled(&self) -> Option<bool> {
+        let manual = read_sysfs_value(self.enable_path()).ok()? == "1";
+        let duty: u8 = read_sysfs_value(&self.pwm).ok()?.parse().ok()?;
+        Some(manual && duty >= PWM_MAX - 5)
+    }
+
+    /// Manual mode before the duty cycle; off returns to automatic (2)
+    fn boost_writes(&self, enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+        Ok(if enabled {
+            vec![SysfsWrite::new(self.enable_path(), 1), SysfsWrite::new(&self.pwm, PWM_MAX)]
+        } else {
+            vec![SysfsWrite::new(self.enable_path(), 2)]
+        })
+    }
+}
+
+/// No controllable fan
+pub struct NoFan;
+
+impl FanBackend for NoFan {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    fn boost_enabled(&self) -> Option<bool> {
+        None
+    }
+
+    fn boost_writes(&self, _enabled: bool) -> io::Result<Vec<SysfsWrite>> {
+        Err(io::Error::new(ErrorKind::Unsupported, "No fan control available"))
+    }
+}
+
+/// Who asked for fan boost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoostRequester {
+    /// The fan button or shortcut
+    User,
+    /// Automatic thermal control after an overshoot
+    AutoControl,
+    /// The applied mode (`fan_boost = on`), set by the helper or the thermal manager
+    Daemon,
+    /// A rule with `fan_boost = on`, until one with `fan_boost = off` fires
+    Rule,
+}
+
+/// How long automatic requests keep boost on after their condition clears
+pub const BOOST_HOLD: Duration = Duration::from_secs(60);
+
+/// How long to wait before retrying a failed boost write
+const BOOST_RETRY: Duration = Duration::from_secs(30);
+
+/// Combines boost requests into one wanted state
+#[derive(Debug)]
+pub struct BoostController {
+    /// Active requests, with the time a released one expires
+    requests: Vec<(BoostRequester, Option<Instant>)>,
+    hold: Duration,
+    /// Last wanted state acted on
+    target: bool,
+    retry_at: Option<Instant>,
+}
+
+impl Default for BoostController {
+    fn default() -> Self {
+        Self::new(BOOST_HOLD)
+    }
+}
+
+impl BoostController {
+    pub fn new(hold: Duration) -> Self {
+        Self { requests: Vec::new(), hold, target: false, retry_at: None }
+    }
+
+    /// Ask for boost, cancelling a pending release
+    pub fn request(&mut self, who: BoostRequester) {
+        match self.requests.iter_mut().find(|(r, _)| *r == who) {
+            Some(request) => request.1 = None,
+            None => self.requests.push((who, None)),
+        }
+    }
+
+    /// Withdraw a request: the user's at once, automatic ones after the hold time
+    pub fn release(&mut self, who: BoostRequester, now: Instant) {
+        if who == BoostRequester::User {
+            return self.cancel(who);
+        }
+        if let Some(request) = self.requests.iter_mut().find(|(r, _)| *r == who) {
+            request.1.get_or_insert(now + self.hold);
+        }
+    }
+
+    /// Withdraw a request at once
+    pub fn cancel(&mut self, who: BoostRequester) {
+        self.requests.retain(|(r, _)| *r != who);
+    }
+
+    pub fn is_requested(&self, who: BoostRequester) -> bool {
+        self.requests.iter().any(|(r, _)| *r == who)
+    }
+
+    /// Requesters currently holding boost on
+    pub fn requesters(&self) -> Vec<BoostRequester> {
+        self.requests.iter().map(|(r, _)| *r).collect()
+    }
+
+    /// Whether anyone wants boost, dropping expired releases
+    pub fn wanted(&mut self, now: Instant) -> bool {
+        self.requests.retain(|(_, expires)| expires.is_none_or(|at| now < at));
+        !self.requests.is_empty()
+    }
+
+    /// State to write given the verified `actual` one, if any. Only changes
+    /// of the wanted state are written, so boost switched by something else
+    /// is left alone; a failed write is retried after a while
+    pub fn pending(&mut self, actual: Option<bool>, now: Instant) -> Option<bool> {
+        let wanted = self.wanted(now);
+        actual?;
+        let retry = self.retry_at.is_some_and(|at| now >= at);
+        if wanted == self.target && !retry {
+            return None;
+        }
+        self.target = wanted;
+        self.retry_at = None;
+        (actual != Some(wanted)).then_some(wanted)
+    }
+
+    /// Note the outcome of writing what [`pending`](Self::pending) returned
+    pub fn record(&mut self, result: &io::Result<()>, now: Instant) {
+        if result.is_err() {
+            self.retry_at = Some(now + BOOST_RETRY);
+        }
+    }
+}
+
+/// `level:` value of the ThinkPad fan status; "disengaged" is full speed too
+fn thinkpad_level(status: &str) -> Option<&str> {
+    let level = status.lines().find_map(|line| line.strip_prefix("level:"))?.trim();
+    Some(if level == "disengaged" { "full-speed" } else { level })
+}
+
+/// Normalize a value read back after writing, so it compares with what was
+/// written: the ThinkPad fan file reports a whole status block
+pub fn read_back(path: &Path, raw: String) -> String {
+    if path.ends_with(THINKPAD_FAN) {
+        if let Some(level) = thinkpad_level(&raw) {
+            return format!("level {}", level);
+        }
+    }
+    raw
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> =
+        fs::read_dir(dir).into_iter().flatten().flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+    entries
+}
+
+fn find_ideapad(sysfs: &Path) -> Option<PathBuf> {
+    sorted_entries(&sysfs.join(IDEAPAD_DRIVER))
+        .into_iter()
+        .map(|device| device.join("fan_mode"))
+        .chain([sysfs.join(IDEAPAD_FAN_MODE)])
+        .find(|path| path.exists())
+}
+
+/// ThinkPad fan file, only when `fan_control=1` makes it writable
+fn find_thinkpad(procfs: &Path) -> Option<PathBuf> {
+    let path = procfs.join(THINKPAD_FAN);
+    let status = fs::read_to_string(&path).ok()?;
+    status.lines().any(|line| line.starts_with("commands:") && line.contains("level")).then_some(path)
+}
+
+/// First hwmon `pwmN` channel that can be switched to manual control
+pub fn find_pwm(sysfs: &Path) -> Option<PathBuf> {
+    sorted_entries(&sysfs.join("class/hwmon")).into_iter().find_map(|hwmon| {
+        sorted_entries(&hwmon).into_iter().find(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            name.strip_prefix("pwm").is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+                && pwm_enable_path(path).exists()
+        })
+    })
+}
+
+/// Speeds in RPM of all hwmon fans, in hwmon order
+fn hwmon_rpms(sysfs: &Path) -> Vec<u32> {
+    sorted_entries(&sysfs.join("class/hwmon"))
+        .into_iter()
+        .flat_map(|hwmon| sorted_entries(&hwmon))
+        .filter(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            name.strip_prefix("fan")
+                .and_then(|n| n.strip_suffix("_input"))
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .filter_map(|path| read_sysfs_value(path).ok()?.parse().ok())
+        .collect()
+}
+
+/// Current fan speed in RPM, the fastest fan when there are several;
+/// `None` when no driver reports a speed
+pub fn read_rpm(sysfs: &Path, procfs: &Path) -> Option<u32> {
+    let hwmon = hwmon_rpms(sysfs).into_iter().max();
+    hwmon.or_else(|| {
+        let status = fs::read_to_string(procfs.join(THINKPAD_FAN)).ok()?;
+        status.lines().find_map(|line| line.strip_prefix("speed:"))?.trim().parse().ok()
+    })
+}
+
+/// First PWM channel of the running system, the one a fan curve drives
+pub fn pwm() -> Option<PathBuf> {
+    find_pwm(Path::new(SYSFS_ROOT))
+}
+
+/// Fan speed of the running system
+pub fn rpm() -> Option<u32> {
+    read_rpm(Path::new(SYSFS_ROOT), Path::new(PROCFS_ROOT))
+}
+
+/// Pick the fan backend for this machine; with `curve_enabled` the hwmon
+/// channel belongs to the fan curve service
+pub fn detect(sysfs: &Path, procfs: &Path, curve_enabled: bool) -> Box<dyn FanBackend> {
+    if let Some(path) = find_ideapad(sysfs) {
+        return Box::new(IdeapadAcpi { path });
+    }
+    if let Some(path) = find_thinkpad(procfs) {
+        return Box::new(ThinkpadAcpi { path });
+    }
+    // The fan curve service drives the same channel and would overwrite
+    // boost on its next duty change, so the channel is left to it
+    match find_pwm(sysfs) {
+        Some(pwm) if !curve_enabled => Box::new(HwmonPwm { pwm }),
+        _ => Box::new(NoFan),
+    }
+}
+
+fn detect_system() -> Arc<dyn FanBackend> {
+    Arc::from(detect(Path::new(SYSFS_ROOT), Path::new(PROCFS_ROOT), fan_curve::load().enabled))
+}
+
+fn detected() -> &'static RwLock<Arc<dyn FanBackend>> {
+    static BACKEND: OnceLock<RwLock<Arc<dyn FanBackend>>> = OnceLock::new();
+    BACKEND.get_or_init(|| RwLock::new(detect_system()))
+}
+
+/// Fan backend of the running system, as last detected
+pub fn backend() -> Arc<dyn FanBackend> {
+    Arc::clone(&detected().read().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Detect the backend again, e.g. once the fan curve was switched on or off
+pub fn redetect() {
+    *detected().write().unwrap_or_else(PoisonError::into_inner) = detect_system();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+
+    const THINKPAD_STATUS: &str = "status:\t\tenabled\nspeed:\t\t2650\nlevel:\t\tauto\n\
+                                   commands:\tlevel <level> (<level> is 0-7, auto, disengaged, full-speed)\n";
+
+    #[test]
+    fn test_no_backend() {
+        let root = tempfile::tempdir().unwrap();
+        let fan = detect(root.path(), root.path(), false);
+        assert_eq!(fan.name(), "none");
+        assert_eq!(fan.boost_enabled(), None);
+        assert!(fan.boost_writes(true).is_err());
+    }
+
+    #[test]
+    fn test_ideapad_discovery() {
+        let root = tempfile::tempdir().unwrap();
+        put(root.path(), "bus/platform/drivers/ideapad_acpi/VPC2004:00/fan_mode", "1");
+        let fan = detect(root.path(), root.path(), false);
+        assert_eq!(fan.name(), "ideapad_acpi");
+        assert_eq!(fan.boost_enabled(), Some(true));
+        let writes = fan.boost_writes(false).unwrap();
+        assert_eq!(writes[0].path, root.path().join("bus/platform/drivers/ideapad_acpi/VPC2004:00/fan_mode"));
+        assert_eq!(writes[0].value, "0");
+    }
+
+    #[test]
+    fn test_thinkpad_needs_fan_control() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let procfs = tempfile::tempdir().unwrap();
+        // Without fan_control=1 there is no "commands:" line
+        put(procfs.path(), THINKPAD_FAN, "status:\t\tenabled\nspeed:\t\t2650\nlevel:\t\tauto\n");
+        assert_eq!(detect(sysfs.path(), procfs.path(), false).name(), "none");
+
+        put(procfs.path(), THINKPAD_FAN, THINKPAD_STATUS);
+        let fan = detect(sysfs.path(), procfs.path(), false);
+        assert_eq!(fan.name(), "thinkpad_acpi");
+        assert_eq!(fan.boost_enabled(), Some(false));
+        assert_eq!(fan.boost_writes(true).unwrap()[0].value, "level full-speed");
+
+        let path = procfs.path().join(THINKPAD_FAN);
+        let disengaged = THINKPAD_STATUS.replace("level:\t\tauto", "level:\t\tdisengaged");
+        assert_eq!(read_back(&path, disengaged), "level full-speed");
+        assert_eq!(read_back(Path::new("/sys/x/pwm1"), "128".into()), "128");
+    }
+
+    #[test]
+    fn test_hwmon_pwm() {
+        let root = tempfile::tempdir().unwrap();
+        put(root.path(), "class/hwmon/hwmon0/temp1_input", "45000");
+        put(root.path(), "class/hwmon/hwmon2/pwm1", "255");
+        put(root.path(), "class/hwmon/hwmon2/pwm1_enable", "1");
+        // pwm2 has no enable switch and is skipped
+        put(root.path(), "class/hwmon/hwmon1/pwm2", "100");
+        let fan = detect(root.path(), root.path(), false);
+        assert_eq!(fan.name(), "hwmon");
+        assert_eq!(fan.boost_enabled(), Some(true));
+
+        let on = fan.boost_writes(true).unwrap();
+        let values: Vec<(&str, &str)> =
+            on.iter().map(|w| (w.path.file_name().unwrap().to_str().unwrap(), w.value.as_str())).collect();
+        assert_eq!(values, [("pwm1_enable", "1"), ("pwm1", "255")]);
+        assert_eq!(fan.boost_writes(false).unwrap(), [SysfsWrite::new(root.path().join("class/hwmon/hwmon2/pwm1_enable"), 2)]);
+        // The fan curve owns the channel
+        assert_eq!(detect(root.path(), root.path(), true).name(), "none");
+    }
+
+    #[test]
+    fn test_boost_controller() {
+        let start = Instant::now();
+        let mut boost = BoostController::new(Duration::from_secs(60));
+        assert_eq!(boost.pending(Some(false), start), None);
+        // No fan, nothing to write
+        boost.request(BoostRequester::AutoControl);
+        assert_eq!(boost.pending(None, start), None);
+
+        let mut boost = BoostController::new(Duration::from_secs(60));
+        boost.request(BoostRequester::AutoControl);
+        boost.request(BoostRequester::User);
+        assert_eq!(boost.pending(Some(false), start), Some(true));
+        assert_eq!(boost.pending(Some(true), start), None);
+
+        // Auto control lingers for the hold time after the user lets go
+        boost.release(BoostRequester::User, start);
+        boost.release(BoostRequester::AutoControl, start);
+        assert_eq!(boost.requesters(), [BoostRequester::AutoControl]);
+        assert_eq!(boost.pending(Some(true), start + Duration::from_secs(30)), None);
+        // A new overshoot cancels the pending release
+        boost.request(BoostRequester::AutoControl);
+        assert_eq!(boost.pending(Some(true), start + Duration::from_secs(90)), None);
+        boost.release(BoostRequester::AutoControl, start + Duration::from_secs(90));
+        assert_eq!(boost.pending(Some(true), start + Duration::from_secs(151)), Some(false));
+
+        // Boost switched on elsewhere is not fought
+        assert_eq!(boost.pending(Some(true), start + Duration::from_secs(152)), None);
+    }
+
+    #[test]
+    fn test_boost_retry_after_failure() {
+        let start = Instant::now();
+        let mut boost = BoostController::default();
+        boost.request(BoostRequester::Daemon);
+        assert_eq!(boost.pending(Some(false), start), Some(true));
+        boost.record(&Err(io::Error::other("dismissed")), start);
+        assert_eq!(boost.pending(Some(false), start + Duration::from_secs(5)), None);
+        assert_eq!(boost.pending(Some(false), start + BOOST_RETRY), Some(true));
+        boost.record(&Ok(()), start + BOOST_RETRY);
+        assert_eq!(boost.pending(Some(false), start + BOOST_RETRY * 3), None);
+    }
+
+    #[test]
+    fn test_read_rpm() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let procfs = tempfile::tempdir().unwrap();
+        assert_eq!(read_rpm(sysfs.path(), procfs.path()), None);
+
+        // ThinkPad status file works without fan_control=1
+        put(procfs.path(), THINKPAD_FAN, "status:\t\tenabled\nspeed:\t\t2650\nlevel:\t\tauto\n");
+        assert_eq!(read_rpm(sysfs.path(), procfs.path()), Some(2650));
+
+        // hwmon takes precedence; the fastest fan is reported
+        put(sysfs.path(), "class/hwmon/hwmon3/name", "dell_smm");
+        put(sysfs.path(), "class/hwmon/hwmon3/fan1_input", "2400");
+        put(sysfs.path(), "class/hwmon/hwmon3/fan2_input", "3100");
+        put(sysfs.path(), "class/hwmon/hwmon3/fan2_label", "GPU Fan");
+        assert_eq!(read_rpm(sysfs.path(), procfs.path()), Some(3100));
+    }
+}
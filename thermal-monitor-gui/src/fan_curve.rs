@@ -0,0 +1,384 @@
+//! Temperature to duty-cycle fan curves for hwmon PWM channels
+//!
+//! The curve lives in `/etc/thermal-monitor/fan-curve.ini`, which only the
+//! privileged helper writes (`thermal-monitor helper fan-curve KEY=VALUE...`):
+//!
+//! ```ini
+//! [curve]
+//! enabled = on
+//! points = 45:0, 60:35, 75:70, 85:100
+//! hysteresis = 4
+//! ```
+//!
+//! Points are `°C:duty%`. Between points the duty is interpolated, outside
+//! them the nearest point holds, and from 90°C on the fan always runs at full
+//! speed. `thermal-monitor fan-curve`, run as root by
+//! `thermal-fan-curve.service`, follows the CPU temperature and drives the
+//! channel in manual mode (`pwmN_enable=1`). The duty rises with the curve
+//! but only falls along the curve shifted by `hysteresis` degrees, so the
+//! fan does not hunt around a point. Only the first channel with an enable
+//! switch is driven; further fans stay with the firmware.
+//!
+//! While the curve is enabled fan boost leaves hwmon channels alone, since
+//! both would set the duty of the same manual channel.
+//!
+//! Whenever the curve is not in charge the channel is handed back to the
+//! firmware (`pwmN_enable=2`): when the curve is disabled or removed and when
+//! the temperature cannot be read. A crash or a signal skips any cleanup in
+//! the process, so the service runs `thermal-monitor fan-curve --release`
+//! as `ExecStopPost` for that.
+
+use std::io::{self, ErrorKind};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Ini;
+use crate::cpufreq::SysfsWrite;
+use crate::fan;
+use crate::helper::{self, read_owned_file, write_and_verify, WriteOutcome, WriteResult};
+use crate::system::{read_cpu_temp, read_sysfs_value};
+
+/// Curve file, written by the helper
+pub const CURVE_FILE: &str = "/etc/thermal-monitor/fan-curve.ini";
+
+/// systemd service running the curve loop
+pub const CURVE_SERVICE: &str = "thermal-fan-curve.service";
+
+/// INI section of the curve file
+const SECTION: &str = "curve";
+
+/// Most points a curve may have
+pub const MAX_POINTS: usize = 8;
+
+/// Temperatures a point may sit at (Celsius)
+pub const TEMP_RANGE: RangeInclusive<f32> = 20.0..=100.0;
+
+/// Largest hysteresis accepted (Celsius)
+pub const MAX_HYSTERESIS: f32 = 15.0;
+
+/// From here on the fan runs at full speed whatever the curve says
+pub const CRITICAL_TEMP: f32 = 90.0;
+
+/// Seconds between temperature checks
+const POLL_SECS: u64 = 2;
+
+/// One curve point: at `temp` Celsius run the fan at `duty` percent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurvePoint {
+    pub temp: f32,
+    pub duty: u8,
+}
+
+impl CurvePoint {
+    pub fn new(temp: f32, duty: u8) -> Self {
+        Self { temp, duty }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanCurve {
+    /// Whether the service drives the fan; off leaves it to the firmware
+    pub enabled: bool,
+    /// Points ordered by temperature
+    pub points: Vec<CurvePoint>,
+    /// Degrees the temperature must fall before the duty follows it down
+    pub hysteresis: f32,
+}
+
+impl Default for FanCurve {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            points: vec![
+                CurvePoint::new(45.0, 0),
+                CurvePoint::new(60.0, 35),
+                CurvePoint::new(75.0, 70),
+                CurvePoint::new(85.0, 100),
+            ],
+            hysteresis: 4.0,
+        }
+    }
+}
+
+impl FanCurve {
+    /// Check the curve is safe to run: 2 to 8 points with rising
+    /// temperatures, and a duty that never falls as it gets hotter
+    pub fn validate(&self) -> Result<(), String> {
+        if !(2..=MAX_POINTS).contains(&self.points.len()) {
+            return Err(format!("A fan curve needs 2 to {} points", MAX_POINTS));
+        }
+        for point in &self.points {
+            if !TEMP_RANGE.contains(&point.temp) || point.duty > 100 {
+                return Err(format!("Point {}:{} is outside 20-100°C or 0-100%", point.temp, point.duty));
+            }
+        }
+        for pair in self.points.windows(2) {
+            if pair[1].temp <= pair[0].temp {
+                return Err("Curve temperatures must rise from point to point".into());
+            }
+            if pair[1].duty < pair[0].duty {
+                return Err("Fan duty must not drop as the temperature rises".into());
+            }
+        }
+        if !(0.0..=MAX_HYSTERESIS).contains(&self.hysteresis) {
+            return Err(format!("Hysteresis must be 0-{}°C", MAX_HYSTERESIS));
+        }
+        Ok(())
+    }
+
+    /// `points` value of the curve file: "45:0, 60:35, 75:70"
+    pub fn points_text(&self) -> String {
+        let points: Vec<String> = self.points.iter().map(|p| format!("{}:{}", p.temp, p.duty)).collect();
+        points.join(", ")
+    }
+
+    /// Parse and validate the `[curve]` section
+    pub fn from_ini(ini: &Ini) -> Result<Self, String> {
+        let points = ini
+            .get(SECTION, "points")
+            .ok_or("Fan curve has no points")?
+            .split(',')
+            .map(|point| {
+                let (temp, duty) = point.trim().split_once(':').ok_or(format!("Invalid point '{}'", point.trim()))?;
+                match (temp.trim().parse(), duty.trim().trim_end_matches('%').parse()) {
+                    (Ok(temp), Ok(duty)) => Ok(CurvePoint::new(temp, duty)),
+                    _ => Err(format!("Invalid point '{}'", point.trim())),
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let hysteresis = match ini.get(SECTION, "hysteresis") {
+            Some(value) => value.parse().map_err(|_| format!("Invalid hysteresis '{}'", value))?,
+            None => FanCurve::default().hysteresis,
+        };
+        let curve = Self { enabled: ini.get(SECTION, "enabled") == Some("on"), points, hysteresis };
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    pub fn to_ini(&self) -> Ini {
+        let mut ini = Ini::default();
+        ini.set(SECTION, "enabled", if self.enabled { "on" } else { "off" });
+        ini.set(SECTION, "points", &self.points_text());
+        ini.set(SECTION, "hysteresis", &self.hysteresis.to_string());
+        ini
+    }
+
+    /// Duty in percent for a temperature, interpolated between points
+    pub fn duty_at(&self, temp: f32) -> u8 {
+        if temp >= CRITICAL_TEMP {
+            return 100;
+        }
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return 100;
+        };
+        if temp <= first.temp {
+            return first.duty;
+        }
+        if temp >= last.temp {
+            return last.duty;
+        }
+        self.points
+            .windows(2)
+            .find(|pair| temp <= pair[1].temp)
+            .map(|pair| {
+                let (a, b) = (pair[0], pair[1]);
+                let t = (temp - a.temp) / (b.temp - a.temp);
+                (a.duty as f32 + t * (b.duty as f32 - a.duty as f32)).round() as u8
+            })
+            .unwrap_or(last.duty)
+    }
+}
+
+/// Follows a curve, holding the duty while the temperature falls by less
+/// than the hysteresis
+#[derive(Debug, Default)]
+pub struct CurveController {
+    duty: Option<u8>,
+}
+
+impl CurveController {
+    /// New duty for `temp`, or `None` when it stays the same
+    pub fn step(&mut self, curve: &FanCurve, temp: f32) -> Option<u8> {
+        let rising = curve.duty_at(temp);
+        let falling = curve.duty_at(temp + curve.hysteresis);
+        let duty = match self.duty {
+            Some(duty) if rising <= duty && falling >= duty => return None,
+            Some(duty) if rising > duty => rising,
+            Some(_) => falling,
+            None => rising,
+        };
+        self.duty = Some(duty);
+        Some(duty)
+    }
+
+    /// Forget the duty, so the next step writes it again
+    pub fn reset(&mut self) {
+        self.duty = None;
+    }
+}
+
+/// `pwmN` value for a duty in percent
+pub fn pwm_value(duty: u8) -> u8 {
+    ((duty.min(100) as u32 * 255 + 50) / 100) as u8
+}
+
+/// Curve from a file only root can write; `None` when missing or invalid
+pub fn read_curve(path: &Path) -> Option<FanCurve> {
+    FanCurve::from_ini(&Ini::parse(&read_owned_file(path, 0)?)).ok()
+}
+
+/// Saved curve, or the default one
+pub fn load() -> FanCurve {
+    read_curve(Path::new(CURVE_FILE)).unwrap_or_default()
+}
+
+/// Hand the channel back to the firmware
+fn release(pwm: &Path) -> WriteResult {
+    write_and_verify(&SysfsWrite::new(fan::pwm_enable_path(pwm), 2))
+}
+
+/// Set the channel to `duty` percent in manual mode. Some drivers round the
+/// duty cycle, so only failed writes count
+fn drive(pwm: &Path, duty: u8) -> Result<(), WriteResult> {
+    let writes = [SysfsWrite::new(fan::pwm_enable_path(pwm), 1), SysfsWrite::new(pwm, pwm_value(duty))];
+    writes
+        .iter()
+        .map(write_and_verify)
+        .find(|result| matches!(result.outcome, WriteOutcome::Failed(_)))
+        .map_or(Ok(()), Err)
+}
+
+/// Channel the curve drives: the first one `find_pwm` returns
+fn pwm_channel() -> io::Result<PathBuf> {
+    fan::pwm()
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "No hwmon PWM channel found"))
+}
+
+/// `thermal-monitor fan-curve`: drive the fan from the curve until stopped
+/// (runs as root)
+pub fn run() -> i32 {
+    let pwm = match pwm_channel() {
+        Ok(pwm) => pwm,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let mut controller = CurveController::default();
+    let mut driving = false;
+    loop {
+        let curve = read_curve(Path::new(CURVE_FILE)).filter(|curve| curve.enabled);
+        match (curve, read_cpu_temp()) {
+            (Some(curve), Ok(temp)) => {
+                // The firmware took the channel back (a suspend, a reset): write again
+                if read_sysfs_value(fan::pwm_enable_path(&pwm)).ok().as_deref() != Some("1") {
+                    controller.reset();
+                }
+                if let Some(duty) = controller.step(&curve, temp) {
+                    if let Err(result) = drive(&pwm, duty) {
+                        eprintln!("{}", result.to_line());
+                        release(&pwm);
+                        return 1;
+                    }
+                }
+                driving = true;
+            }
+            _ if driving => {
+                let result = release(&pwm);
+                if !result.is_applied() {
+                    eprintln!("{}", result.to_line());
+                }
+                controller.reset();
+                driving = false;
+            }
+            _ => {}
+        }
+        thread::sleep(Duration::from_secs(POLL_SECS));
+    }
+}
+
+/// `thermal-monitor fan-curve --release`: return the fan to automatic control
+pub fn run_release() -> i32 {
+    match pwm_channel() {
+        Ok(pwm) => {
+            let result = release(&pwm);
+            println!("{}", result.to_line());
+            i32::from(!result.is_applied())
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// Save the curve through pkexec and start or stop the service
+pub fn save_privileged(curve: &FanCurve) -> io::Result<()> {
+    curve.validate().map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+    let ini = curve.to_ini();
+    let args: Vec<String> = ["enabled", "points", "hysteresis"]
+        .iter()
+        .filter_map(|key| Some(format!("{}={}", key, ini.get(SECTION, key)?)))
+        .collect();
+    helper::run_fan_curve_helper(&args).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(points: &[(f32, u8)]) -> FanCurve {
+        FanCurve {
+            enabled: true,
+            points: points.iter().map(|&(temp, duty)| CurvePoint::new(temp, duty)).collect(),
+            hysteresis: 4.0,
+        }
+    }
+
+    #[test]
+    fn test_curve_roundtrip_and_validation() {
+        let ini = Ini::parse("[curve]\nenabled = on\npoints = 40:0, 60:50%, 80:100\nhysteresis = 3\n");
+        let parsed = FanCurve::from_ini(&ini).unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.points, [CurvePoint::new(40.0, 0), CurvePoint::new(60.0, 50), CurvePoint::new(80.0, 100)]);
+        assert_eq!(parsed.hysteresis, 3.0);
+        assert_eq!(FanCurve::from_ini(&parsed.to_ini()).unwrap(), parsed);
+        FanCurve::default().validate().unwrap();
+
+        assert!(curve(&[(50.0, 20)]).validate().is_err());
+        assert!(curve(&[(60.0, 20), (50.0, 40)]).validate().is_err());
+        assert!(curve(&[(50.0, 40), (60.0, 20)]).validate().is_err());
+        assert!(curve(&[(50.0, 40), (110.0, 100)]).validate().is_err());
+        assert!(FanCurve::from_ini(&Ini::parse("[curve]\npoints = 40-0, 60:50\n")).is_err());
+    }
+
+    #[test]
+    fn test_duty_interpolation() {
+        let curve = curve(&[(40.0, 20), (60.0, 60), (80.0, 80)]);
+        assert_eq!(curve.duty_at(30.0), 20);
+        assert_eq!(curve.duty_at(50.0), 40);
+        assert_eq!(curve.duty_at(70.0), 70);
+        assert_eq!(curve.duty_at(85.0), 80);
+        assert_eq!(curve.duty_at(CRITICAL_TEMP), 100);
+        assert_eq!(pwm_value(0), 0);
+        assert_eq!(pwm_value(50), 128);
+        assert_eq!(pwm_value(100), 255);
+    }
+
+    #[test]
+    fn test_controller_hysteresis() {
+        let curve = curve(&[(40.0, 0), (80.0, 80)]);
+        let mut controller = CurveController::default();
+        assert_eq!(controller.step(&curve, 60.0), Some(40));
+        assert_eq!(controller.step(&curve, 60.0), None);
+        // Rises follow the curve at once
+        assert_eq!(controller.step(&curve, 65.0), Some(50));
+        // Small drops are held, larger ones follow the shifted curve
+        assert_eq!(controller.step(&curve, 62.0), None);
+        assert_eq!(controller.step(&curve, 60.0), Some(48));
+        controller.reset();
+        assert_eq!(controller.step(&curve, 60.0), Some(40));
+    }
+}
@@ -0,0 +1,672 @@
+//! Privileged sysfs writer
+//!
+//! The GUI never writes sysfs itself. Writes are handed to this same binary
+//! running as root through pkexec
+//! (`thermal-monitor helper write [--mode NAME [--automatic]] PATH=VALUE...`),
+//! which checks every path against an allowlist, writes it, reads it back
+//! and prints one tab-separated result line per write. With `--mode` the
+//! mode name is recorded afterwards and the thermal manager timer is
+//! started or stopped; that is reported as one more line:
+//!
+//! ```text
+//! ok        <path>  <requested>  <read back>
+//! mismatch  <path>  <requested>  <read back>
+//! error     <path>  <requested>  <message>
+//! ```
+//!
+//! The recorded mode lives in `/run/thermal-monitor/mode`, which only root
+//! can write, and is trusted only while it stays root-owned.
+//! `thermal-monitor helper state` prints it together with any knob that no
+//! longer matches the mode (`mismatch` lines as above), and exits with 1
+//! when something else changed them.
+//!
+//! `thermal-monitor helper fan-curve KEY=VALUE...` validates and saves the
+//! fan curve (see [`fan_curve`](crate::fan_curve)) and starts or stops its
+//! service, reported as one result line.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cgroup;
+use crate::config::Ini;
+use crate::fan;
+use crate::fan_curve::{self, FanCurve};
+use crate::cpufreq::{SysfsWrite, CPU_ROOT};
+use crate::i18n::tr_args;
+use crate::modes::{self, valid_name, ModeProfile};
+use crate::platform_profile::SYSFS_ROOT;
+use crate::system::read_sysfs_value;
+
+/// Mode recorded by the helper; the directory is created root-owned, 0755
+pub const MODE_STATE_FILE: &str = "/run/thermal-monitor/mode";
+
+/// INI section of the state file
+const STATE_SECTION: &str = "state";
+
+/// systemd timer running thermal-manager.sh for automatic mode
+const AUTO_TIMER: &str = "thermal-manager.timer";
+
+/// Attributes the helper may write, per directory (after resolving symlinks)
+const ALLOWED: &[(&str, &[&str])] = &[
+    (
+        "/sys/devices/system/cpu/",
+        &[
+            "max_perf_pct",
+            "min_perf_pct",
+            "scaling_max_freq",
+            "scaling_min_freq",
+            "no_turbo",
+            "boost",
+            "energy_performance_preference",
+        ],
+    ),
+    ("/sys/firmware/acpi/", &["platform_profile"]),
+    ("/sys/devices/virtual/platform-profile/", &["profile"]),
+    // Fan backends; `#` stands for a channel number
+    ("/sys/devices/", &["fan_mode", "pwm#", "pwm#_enable"]),
+    ("/proc/acpi/ibm/", &["fan"]),
+    // RAPL power limits
+    ("/sys/devices/virtual/powercap/", &["constraint_#_power_limit_uw", "constraint_#_time_window_us"]),
+    // Per-application CPU throttling, for user apps and services only
+    ("/sys/fs/cgroup/user.slice/", &["cpu.max", "cpu.weight"]),
+];
+
+/// Result of a single verified write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteOutcome {
+    /// Value read back matches the request
+    Applied(String),
+    /// Write succeeded but the kernel reports a different value
+    Mismatch(String),
+    /// Write was rejected or not attempted
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteResult {
+    pub write: SysfsWrite,
+    pub outcome: WriteOutcome,
+}
+
+impl WriteResult {
+    pub fn is_applied(&self) -> bool {
+        matches!(self.outcome, WriteOutcome::Applied(_))
+    }
+
+    /// Short path for display, with the CPUs of a cpufreq policy
+    /// ("cpufreq/policy3/scaling_max_freq (CPUs 6-7)")
+    pub fn label(&self) -> String {
+        let path = &self.write.path;
+        let short = path.strip_prefix(CPU_ROOT).unwrap_or(path).display().to_string();
+        match path.parent().map(|dir| read_sysfs_value(dir.join("related_cpus"))) {
+            Some(Ok(cpus)) => format!("{} ({})", short, tr_args("write-cpus", &[("cpus", &cpus)])),
+            _ => short,
+        }
+    }
+
+    pub(crate) fn to_line(&self) -> String {
+        let (status, detail) = match &self.outcome {
+            WriteOutcome::Applied(actual) => ("ok", actual),
+            WriteOutcome::Mismatch(actual) => ("mismatch", actual),
+            WriteOutcome::Failed(message) => ("error", message),
+        };
+        format!("{}\t{}\t{}\t{}", status, self.write.path.display(), self.write.value, detail.replace(['\t', '\n'], " "))
+    }
+
+    fn parse_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        let status = fields.next()?;
+        let write = SysfsWrite::new(fields.next()?, fields.next()?);
+        let detail = fields.next().unwrap_or_default().to_string();
+        let outcome = match status {
+            "ok" => WriteOutcome::Applied(detail),
+            "mismatch" => WriteOutcome::Mismatch(detail),
+            "error" => WriteOutcome::Failed(detail),
+            _ => return None,
+        };
+        Some(Self { write, outcome })
+    }
+}
+
+/// Per-attribute results of a batch of privileged writes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteReport {
+    pub results: Vec<WriteResult>,
+}
+
+impl WriteReport {
+    pub fn failures(&self) -> impl Iterator<Item = &WriteResult> {
+        self.results.iter().filter(|r| !r.is_applied())
+    }
+
+    /// Turn any failed or unverified write into an error listing each of them
+    pub fn into_result(self) -> io::Result<Self> {
+        let failed: Vec<String> = self
+            .failures()
+            .map(|r| match &r.outcome {
+                WriteOutcome::Mismatch(actual) => {
+                    tr_args("write-mismatch", &[("path", &r.label()), ("value", &r.write.value), ("actual", actual)])
+                }
+                WriteOutcome::Failed(message) | WriteOutcome::Applied(message) => {
+                    tr_args("write-failed", &[("path", &r.label()), ("error", message)])
+                }
+            })
+            .collect();
+        if failed.is_empty() {
+            return Ok(self);
+        }
+        let summary = tr_args(
+            "write-summary",
+            &[("failed", &failed.len().to_string()), ("total", &self.results.len().to_string())],
+        );
+        Err(io::Error::other(format!("{}: {}", summary, failed.join("; "))))
+    }
+}
+
+/// Check that a resolved path is a writable attribute we manage
+pub fn validate_path(path: &Path) -> Result<(), String> {
+    let text = path.to_str().ok_or("Path is not valid UTF-8")?;
+    if !path.is_absolute() || path.components().any(|c| c.as_os_str() == "..") {
+        return Err("Path must be absolute and normalized".into());
+    }
+    let mut directories = ALLOWED.iter().filter(|(prefix, _)| text.starts_with(prefix)).peekable();
+    if directories.peek().is_none() {
+        return Err("Path is outside the allowed sysfs directories".into());
+    }
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    if !directories.any(|(_, attributes)| attributes.iter().any(|pattern| attribute_matches(pattern, name))) {
+        return Err(format!("Attribute '{}' is not allowed", name));
+    }
+    // Throttle single apps and services, never a whole user session
+    if path.starts_with(Path::new(cgroup::CGROUP_ROOT)) {
+        let group = path.parent().and_then(Path::file_name).and_then(|n| n.to_str()).unwrap_or_default();
+        if !cgroup::valid_pattern(group) {
+            return Err(format!("Cgroup '{}' may not be throttled", group));
+        }
+    }
+    Ok(())
+}
+
+/// Match an attribute name where `#` in the pattern stands for a number
+fn attribute_matches(pattern: &str, name: &str) -> bool {
+    let Some((before, after)) = pattern.split_once('#') else {
+        return pattern == name;
+    };
+    name.strip_prefix(before)
+        .and_then(|rest| rest.strip_suffix(after))
+        .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn validate_value(value: &str) -> Result<(), String> {
+    // Spaces only for commands such as "level auto"
+    if value.is_empty() || value.len() > 64 || !value.chars().all(|c| c.is_ascii_graphic() || c == ' ') {
+        return Err("Invalid value".into());
+    }
+    Ok(())
+}
+
+/// Attributes the kernel rounds: frequencies to a P-state step, percents
+/// to a ratio, power limits and time windows to the RAPL units
+const ROUNDED_SUFFIXES: [&str; 4] = ["_freq", "_perf_pct", "_uw", "_us"];
+
+/// Whether `actual` read back from `path` is what was requested; values of
+/// rounded attributes may be off by 1%, everything else must match exactly
+fn values_match(path: &Path, requested: &str, actual: &str) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    if !ROUNDED_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+        return requested == actual;
+    }
+    match (requested.parse::<u64>(), actual.parse::<u64>()) {
+        (Ok(want), Ok(got)) => want.abs_diff(got) <= (want / 100).max(1),
+        _ => requested == actual,
+    }
+}
+
+/// Write one attribute and read it back
+pub fn write_and_verify(write: &SysfsWrite) -> WriteResult {
+    let outcome = match fs::write(&write.path, &write.value) {
+        Err(e) => WriteOutcome::Failed(e.to_string()),
+        Ok(()) => match read_sysfs_value(&write.path).map(|raw| fan::read_back(&write.path, raw)) {
+            Ok(actual) if values_match(&write.path, &write.value, &actual) => WriteOutcome::Applied(actual),
+            Ok(actual) => WriteOutcome::Mismatch(actual),
+            Err(e) => WriteOutcome::Failed(e.to_string()),
+        },
+    };
+    WriteResult { write: write.clone(), outcome }
+}
+
+/// Validate and apply one `PATH=VALUE` argument
+fn apply_argument(arg: &str) -> WriteResult {
+    let Some((path, value)) = arg.split_once('=') else {
+        return WriteResult {
+            write: SysfsWrite::new(arg, ""),
+            outcome: WriteOutcome::Failed("Expected PATH=VALUE".into()),
+        };
+    };
+    let write = SysfsWrite::new(path, value);
+    let checked = fs::canonicalize(path)
+        .map_err(|e| e.to_string())
+        .and_then(|resolved| validate_path(&resolved).map(|_| resolved))
+        .and_then(|resolved| validate_value(value).map(|_| resolved));
+    match checked {
+        Ok(resolved) => WriteResult { write, ..write_and_verify(&SysfsWrite::new(resolved, value)) },
+        Err(message) => WriteResult { write, outcome: WriteOutcome::Failed(message) },
+    }
+}
+
+/// Mode declared by the last `helper write --mode`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeState {
+    pub name: String,
+    /// The thermal manager timer adjusts the knobs itself
+    pub automatic: bool,
+}
+
+impl ModeState {
+    fn to_ini(&self) -> Ini {
+        let mut ini = Ini::default();
+        ini.set(STATE_SECTION, "mode", &self.name);
+        ini.set(STATE_SECTION, "automatic", if self.automatic { "on" } else { "off" });
+        ini
+    }
+
+    fn from_ini(ini: &Ini) -> Option<Self> {
+        let name = ini.get(STATE_SECTION, "mode").filter(|name| valid_name(name))?;
+        Some(Self { name: name.to_string(), automatic: ini.get(STATE_SECTION, "automatic") == Some("on") })
+    }
+}
+
+/// Replace a file atomically, leaving it and its directory writable only by the owner
+pub(crate) fn save_owned_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o755))?;
+    }
+    let staging = path.with_extension("new");
+    fs::write(&staging, contents)?;
+    fs::set_permissions(&staging, fs::Permissions::from_mode(0o644))?;
+    fs::rename(&staging, path)
+}
+
+/// Read a file, ignoring it unless `owner` owns it and nobody else can write it
+pub(crate) fn read_owned_file(path: &Path, owner: u32) -> Option<String> {
+    let meta = fs::symlink_metadata(path).ok()?;
+    if !meta.is_file() || meta.uid() != owner || meta.mode() & 0o022 != 0 {
+        return None;
+    }
+    fs::read_to_string(path).ok()
+}
+
+/// Replace the state file atomically, keeping its directory private to `root`
+fn save_mode_state(path: &Path, state: &ModeState) -> io::Result<()> {
+    save_owned_file(path, &state.to_ini().to_string())
+}
+
+/// Read the state file, ignoring it unless `owner` owns it and nobody else can write it
+fn read_mode_state(path: &Path, owner: u32) -> Option<ModeState> {
+    ModeState::from_ini(&Ini::parse(&read_owned_file(path, owner)?))
+}
+
+/// Mode recorded by the helper, if any
+pub fn mode_state() -> Option<ModeState> {
+    read_mode_state(Path::new(MODE_STATE_FILE), 0)
+}
+
+/// Knobs below `sysfs` that no longer hold the value `profile` sets, as
+/// `Mismatch` results carrying the value found. Automatic modes have none
+pub fn check_drift(profile: &ModeProfile, sysfs: &Path) -> Vec<WriteResult> {
+    if profile.automatic {
+        return Vec::new();
+    }
+    profile
+        .writes(sysfs)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|write| {
+            let actual = read_sysfs_value(&write.path).ok()?;
+            (!values_match(&write.path, &write.value, &actual))
+                .then_some(WriteResult { write, outcome: WriteOutcome::Mismatch(actual) })
+        })
+        .collect()
+}
+
+/// One drifted knob for display: "max_perf_pct: 40 expected, now 100"
+pub fn drift_text(result: &WriteResult) -> String {
+    let actual = match &result.outcome {
+        WriteOutcome::Applied(actual) | WriteOutcome::Mismatch(actual) | WriteOutcome::Failed(actual) => actual,
+    };
+    tr_args("drift-item", &[("path", &result.label()), ("expected", &result.write.value), ("actual", actual)])
+}
+
+/// Record the applied mode and start or stop the thermal manager timer
+fn record_mode(name: &str, automatic: bool) -> WriteResult {
+    let write = SysfsWrite::new(MODE_STATE_FILE, name);
+    if !valid_name(name) {
+        return WriteResult { write, outcome: WriteOutcome::Failed("Invalid mode name".into()) };
+    }
+    let state = ModeState { name: name.to_string(), automatic };
+    if let Err(e) = save_mode_state(Path::new(MODE_STATE_FILE), &state) {
+        return WriteResult { write, outcome: WriteOutcome::Failed(e.to_string()) };
+    }
+
+    // The timer may not be installed; manual modes then need nothing
+    let action = if automatic { "start" } else { "stop" };
+    let timer = Command::new("systemctl").args([action, AUTO_TIMER]).output();
+    let outcome = match timer {
+        Ok(output) if !output.status.success() && automatic => {
+            WriteOutcome::Failed(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+        Err(e) if automatic => WriteOutcome::Failed(e.to_string()),
+        _ => WriteOutcome::Applied(name.to_string()),
+    };
+    WriteResult { write, outcome }
+}
+
+/// Save the fan curve and start the service when it is enabled, stop it otherwise
+fn save_fan_curve(args: &[String]) -> WriteResult {
+    let mut ini = Ini::default();
+    for arg in args {
+        if let Some((key, value)) = arg.split_once('=') {
+            ini.set("curve", key, value);
+        }
+    }
+    let write = SysfsWrite::new(fan_curve::CURVE_FILE, ini.get("curve", "points").unwrap_or_default());
+    let curve = match FanCurve::from_ini(&ini) {
+        Ok(curve) => curve,
+        Err(e) => return WriteResult { write, outcome: WriteOutcome::Failed(e) },
+    };
+    if let Err(e) = save_owned_file(Path::new(fan_curve::CURVE_FILE), &curve.to_ini().to_string()) {
+        return WriteResult { write, outcome: WriteOutcome::Failed(e.to_string()) };
+    }
+
+    // Stopping the service hands the fan back to the firmware
+    let action = if curve.enabled { "enable" } else { "disable" };
+    let service = Command::new("systemctl").args([action, "--now", fan_curve::CURVE_SERVICE]).output();
+    let outcome = match service {
+        Ok(output) if !output.status.success() && curve.enabled => {
+            WriteOutcome::Failed(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+        Err(e) if curve.enabled => WriteOutcome::Failed(e.to_string()),
+        _ => WriteOutcome::Applied(write.value.clone()),
+    };
+    WriteResult { write, outcome }
+}
+
+/// Entry point for `thermal-monitor helper ...` (runs as root)
+pub fn run(args: &[String]) -> i32 {
+    match args.split_first() {
+        Some((command, rest)) if command == "write" && !rest.is_empty() => {
+            let mut mode = None;
+            let mut automatic = false;
+            let mut writes = Vec::new();
+            let mut iter = rest.iter();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    // Checked before any write, so a bad name changes nothing
+                    "--mode" => match iter.next() {
+                        Some(name) if valid_name(name) => mode = Some(name),
+                        name => {
+                            eprintln!("--mode needs a valid mode name, got {:?}", name.map_or("", String::as_str));
+                            return 2;
+                        }
+                    },
+                    "--automatic" => automatic = true,
+                    _ => writes.push(arg),
+                }
+            }
+
+            let mut results: Vec<WriteResult> = writes.iter().map(|arg| apply_argument(arg)).collect();
+            if let Some(name) = mode {
+                results.push(record_mode(name, automatic));
+            }
+            for result in &results {
+                println!("{}", result.to_line());
+            }
+            if results.iter().all(WriteResult::is_applied) {
+                0
+            } else {
+                1
+            }
+        }
+        Some((command, [])) if command == "state" => {
+            let Some(state) = mode_state() else {
+                eprintln!("No mode recorded");
+                return 1;
+            };
+            println!("mode\t{}\t{}", state.name, if state.automatic { "automatic" } else { "manual" });
+            let drift = modes::find(&state.name)
+                .map(|profile| check_drift(&profile, Path::new(SYSFS_ROOT)))
+                .unwrap_or_default();
+            for result in &drift {
+                println!("{}", result.to_line());
+            }
+            i32::from(!drift.is_empty())
+        }
+        Some((command, rest)) if command == "fan-curve" && !rest.is_empty() => {
+            let result = save_fan_curve(rest);
+            println!("{}", result.to_line());
+            i32::from(!result.is_applied())
+        }
+        _ => {
+            eprintln!("Usage: thermal-monitor helper write [--mode NAME [--automatic]] PATH=VALUE...");
+            eprintln!("       thermal-monitor helper state");
+            eprintln!("       thermal-monitor helper fan-curve KEY=VALUE...");
+            2
+        }
+    }
+}
+
+/// Apply writes through pkexec and collect the per-attribute results
+pub fn write_privileged(writes: &[SysfsWrite]) -> io::Result<WriteReport> {
+    if writes.is_empty() {
+        return Err(io::Error::new(ErrorKind::NotFound, "No sysfs attributes to write"));
+    }
+    run_helper(&["write".into()], writes)
+}
+
+/// Apply a mode's writes and record it as the current mode
+pub fn apply_mode_privileged(name: &str, automatic: bool, writes: &[SysfsWrite]) -> io::Result<WriteReport> {
+    let mut flags = vec!["write".to_string(), "--mode".to_string(), name.to_string()];
+    if automatic {
+        flags.push("--automatic".into());
+    }
+    run_helper(&flags, writes)
+}
+
+/// Save the fan curve (`KEY=VALUE` arguments) through pkexec
+pub fn run_fan_curve_helper(args: &[String]) -> io::Result<WriteReport> {
+    run_pkexec(&["fan-curve".to_string()], args, 1)
+}
+
+fn run_helper(flags: &[String], writes: &[SysfsWrite]) -> io::Result<WriteReport> {
+    let args: Vec<String> = writes.iter().map(|w| format!("{}={}", w.path.display(), w.value)).collect();
+    // The mode, if any, is reported as one extra line
+    run_pkexec(flags, &args, writes.len() + usize::from(flags.len() > 1))
+}
+
+/// Run `helper COMMAND...` as root, expecting `expected` result lines
+fn run_pkexec(command: &[String], args: &[String], expected: usize) -> io::Result<WriteReport> {
+    let exe: PathBuf = std::env::current_exe()?;
+    let output = Command::new("pkexec").arg(exe).arg("helper").args(command).args(args).output()?;
+
+    let results: Vec<WriteResult> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(WriteResult::parse_line)
+        .collect();
+    if results.len() != expected {
+        // pkexec exits with 126/127 when authorization is dismissed or denied
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!("Privileged helper failed: {}", stderr.trim()),
+        ));
+    }
+    WriteReport { results }.into_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::{fake_cpus, put};
+    use crate::system::Mode;
+
+    #[test]
+    fn test_validate_path_allowlist() {
+        assert!(validate_path(Path::new("/sys/devices/system/cpu/cpufreq/policy0/scaling_max_freq")).is_ok());
+        assert!(validate_path(Path::new("/sys/devices/system/cpu/intel_pstate/max_perf_pct")).is_ok());
+        assert!(validate_path(Path::new("/sys/devices/system/cpu/cpu3/online")).is_err());
+        assert!(validate_path(Path::new("/sys/devices/virtual/platform-profile/platform-profile-0/profile")).is_ok());
+        assert!(validate_path(Path::new("/sys/devices/system/cpu/profile")).is_err());
+        assert!(validate_path(Path::new("/sys/firmware/acpi/platform_profile_choices")).is_err());
+        assert!(validate_path(Path::new("/etc/scaling_max_freq")).is_err());
+        assert!(validate_path(Path::new("/sys/devices/platform/VPC2004:00/fan_mode")).is_ok());
+        assert!(validate_path(Path::new("/sys/devices/platform/nct6775.656/hwmon/hwmon3/pwm2_enable")).is_ok());
+        assert!(validate_path(Path::new("/sys/devices/platform/nct6775.656/hwmon/hwmon3/pwm")).is_err());
+        assert!(validate_path(Path::new("/sys/devices/platform/nct6775.656/hwmon/hwmon3/pwm1_mode")).is_err());
+        assert!(validate_path(Path::new("/proc/acpi/ibm/fan")).is_ok());
+        assert!(validate_path(Path::new("/proc/acpi/ibm/led")).is_err());
+        let rapl = "/sys/devices/virtual/powercap/intel-rapl/intel-rapl:0";
+        assert!(validate_path(&Path::new(rapl).join("constraint_0_power_limit_uw")).is_ok());
+        assert!(validate_path(&Path::new(rapl).join("constraint_1_time_window_us")).is_ok());
+        assert!(validate_path(&Path::new(rapl).join("constraint_0_max_power_uw")).is_err());
+        assert!(validate_path(&Path::new(rapl).join("enabled")).is_err());
+        let scope = "/sys/fs/cgroup/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox-4242.scope";
+        assert!(validate_path(&Path::new(scope).join("cpu.max")).is_ok());
+        assert!(validate_path(&Path::new(scope).join("cpu.weight")).is_ok());
+        assert!(validate_path(&Path::new(scope).join("cgroup.procs")).is_err());
+        assert!(validate_path(&Path::new(scope).join("memory.max")).is_err());
+        assert!(validate_path(Path::new("/sys/fs/cgroup/system.slice/sshd.service/cpu.max")).is_err());
+        assert!(validate_path(Path::new("/sys/fs/cgroup/cpu.max")).is_err());
+        assert!(validate_path(Path::new("/sys/fs/cgroup/user.slice/cpu.max")).is_err());
+        assert!(validate_path(Path::new("/sys/fs/cgroup/user.slice/user-1000.slice/cpu.weight")).is_err());
+        assert!(validate_path(Path::new("/sys/fs/cgroup/user.slice/user-1000.slice/user@1000.service/cpu.max")).is_err());
+        assert!(validate_path(Path::new("/sys/devices/system/cpu/../../../etc/max_perf_pct")).is_err());
+        assert!(validate_path(Path::new("sys/devices/system/cpu/intel_pstate/max_perf_pct")).is_err());
+    }
+
+    #[test]
+    fn test_write_rejects_bad_mode() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let dir = tempfile::tempdir().unwrap();
+        let write = format!("{}=1", dir.path().join("no_turbo").display());
+        assert_eq!(run(&args(&["write", &write, "--mode"])), 2);
+        assert_eq!(run(&args(&["write", "--mode", "../quiet", &write])), 2);
+        assert!(!dir.path().join("no_turbo").exists());
+    }
+
+    #[test]
+    fn test_values_match_tolerates_rounding() {
+        let freq = Path::new("/sys/devices/system/cpu/cpufreq/policy0/scaling_max_freq");
+        let pct = Path::new("/sys/devices/system/cpu/intel_pstate/max_perf_pct");
+        let no_turbo = Path::new("/sys/devices/system/cpu/intel_pstate/no_turbo");
+        let pwm_enable = Path::new("/sys/class/hwmon/hwmon2/pwm1_enable");
+        assert!(values_match(freq, "2200000", "2200000"));
+        assert!(values_match(freq, "2200000", "2199000"));
+        assert!(!values_match(freq, "2200000", "1800000"));
+        assert!(values_match(pct, "50", "49"));
+        assert!(values_match(no_turbo, "1", "1"));
+        assert!(!values_match(no_turbo, "1", "0x"));
+        // Switches and modes must match exactly
+        assert!(!values_match(no_turbo, "1", "0"));
+        assert!(!values_match(no_turbo, "0", "1"));
+        assert!(!values_match(pwm_enable, "2", "1"));
+        assert!(!values_match(Path::new("/sys/devices/platform/VPC2004:00/fan_mode"), "1", "0"));
+    }
+
+    #[test]
+    fn test_write_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        put(dir.path(), "cpufreq/policy0/scaling_max_freq", "4400000");
+        let path = dir.path().join("cpufreq/policy0/scaling_max_freq");
+
+        let result = write_and_verify(&SysfsWrite::new(&path, 2_200_000));
+        assert_eq!(result.outcome, WriteOutcome::Applied("2200000".into()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2200000");
+
+        put(dir.path(), "cpufreq/policy0/related_cpus", "0-3");
+        assert!(result.label().ends_with("cpufreq/policy0/scaling_max_freq (CPUs 0-3)"));
+
+        let missing = write_and_verify(&SysfsWrite::new(dir.path().join("nope/scaling_max_freq"), 1));
+        assert!(matches!(missing.outcome, WriteOutcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_result_line_roundtrip() {
+        let results = [
+            WriteOutcome::Applied("2200000".into()),
+            WriteOutcome::Mismatch("1800000".into()),
+            WriteOutcome::Failed("Permission denied".into()),
+        ]
+        .map(|outcome| WriteResult {
+            write: SysfsWrite::new("/sys/devices/system/cpu/cpufreq/policy1/scaling_max_freq", 2_200_000),
+            outcome,
+        });
+        for result in &results {
+            assert_eq!(WriteResult::parse_line(&result.to_line()).as_ref(), Some(result));
+        }
+        assert_eq!(results[0].label(), "cpufreq/policy1/scaling_max_freq");
+        assert_eq!(WriteResult::parse_line("garbage"), None);
+    }
+
+    #[test]
+    fn test_mode_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run/thermal-monitor/mode");
+        let state = ModeState { name: "presenting".into(), automatic: false };
+        save_mode_state(&path, &state).unwrap();
+        let owner = fs::metadata(&path).unwrap().uid();
+        assert_eq!(read_mode_state(&path, owner), Some(state));
+        assert_eq!(read_mode_state(&path, owner + 1), None);
+
+        // Group or world writable files could have been spoofed
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o666)).unwrap();
+        assert_eq!(read_mode_state(&path, owner), None);
+
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&path, &link).unwrap();
+        assert_eq!(read_mode_state(&link, owner), None);
+        put(dir.path(), "bad", "[state]\nmode = ../etc\n");
+        assert_eq!(read_mode_state(&dir.path().join("bad"), owner), None);
+    }
+
+    #[test]
+    fn test_check_drift() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let cpu = sysfs.path().join("devices/system/cpu");
+        fake_cpus(&cpu, 1, "intel_pstate", 4_400_000, 4_400_000);
+        put(&cpu, "intel_pstate/max_perf_pct", "40");
+        put(&cpu, "intel_pstate/min_perf_pct", "10");
+        let quiet = ModeProfile::builtin(&Mode::Quiet);
+        assert!(check_drift(&quiet, sysfs.path()).is_empty());
+
+        // Something else raised the ceiling
+        put(&cpu, "intel_pstate/max_perf_pct", "100");
+        let drift = check_drift(&quiet, sysfs.path());
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].write.value, "40");
+        assert_eq!(drift[0].outcome, WriteOutcome::Mismatch("100".into()));
+        assert!(drift_text(&drift[0]).ends_with("40 expected, now 100"));
+        assert!(check_drift(&ModeProfile::builtin(&Mode::Auto), sysfs.path()).is_empty());
+    }
+
+    #[test]
+    fn test_report_lists_each_failure() {
+        let ok = WriteResult {
+            write: SysfsWrite::new("/sys/devices/system/cpu/cpufreq/policy0/scaling_max_freq", 1),
+            outcome: WriteOutcome::Applied("1".into()),
+        };
+        assert!(WriteReport { results: vec![ok.clone()] }.into_result().is_ok());
+
+        let bad = WriteResult {
+            write: SysfsWrite::new("/sys/devices/system/cpu/cpufreq/policy5/scaling_max_freq", 1),
+            outcome: WriteOutcome::Failed("Permission denied".into()),
+        };
+        let err = WriteReport { results: vec![ok, bad] }.into_result().unwrap_err().to_string();
+        assert!(err.contains("1 of 2"));
+        assert!(err.contains("cpufreq/policy5/scaling_max_freq"));
+        assert!(!err.contains("policy0"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Message catalogs for UI strings
+//!
+//! Catalogs live in `locales/<lang>.ftl` and are embedded at compile time,
+//! so the binary stays self-contained. The format is a subset of Fluent:
+//! one `key = value` message per line, `#` comments, and `{ $name }`
+//! placeables for arguments.
+//!
+//! Supported languages: English, Spanish, Chinese, Portuguese, German.
+//! Missing keys fall back to English, then to the key itself.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Fallback language, also used when the environment names no catalog
+const DEFAULT_LANG: &str = "en";
+
+/// Embedded catalogs: (language prefix, Fluent source)
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+    ("zh", include_str!("../locales/zh.ftl")),
+    ("pt", include_str!("../locales/pt.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+];
+
+/// Parsed message catalog for one language
+#[derive(Debug, Default)]
+pub struct Catalog {
+    messages: HashMap<&'static str, &'static str>,
+}
+
+impl Catalog {
+    /// Parse a Fluent source into a catalog
+    pub fn parse(source: &'static str) -> Self {
+        let messages = source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+        Self { messages }
+    }
+
+    /// Load the embedded catalog for a language prefix (e.g. "es")
+    pub fn for_lang(lang: &str) -> Option<Self> {
+        CATALOGS
+            .iter()
+            .find(|(code, _)| *code == lang)
+            .map(|(_, source)| Self::parse(source))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'static str> {
+        self.messages.get(key).copied()
+    }
+}
+
+/// Active catalog plus English fallback
+struct Translator {
+    active: Catalog,
+    fallback: Catalog,
+}
+
+fn translator() -> &'static Translator {
+    static TRANSLATOR: OnceLock<Translator> = OnceLock::new();
+    TRANSLATOR.get_or_init(|| Translator {
+        active: Catalog::for_lang(&active_language()).unwrap_or_default(),
+        fallback: Catalog::for_lang(DEFAULT_LANG).unwrap_or_default(),
+    })
+}
+
+/// Extract the language prefix from a POSIX locale ("pt_BR.UTF-8" -> "pt")
+pub fn language_prefix(locale: &str) -> &str {
+    let code = locale.split(['.', '@']).next().unwrap_or(DEFAULT_LANG);
+    code.split('_').next().unwrap_or(DEFAULT_LANG)
+}
+
+/// Detect UI language from the environment
+/// Follows POSIX precedence: LC_ALL, then LC_MESSAGES, then LANG
+pub fn detect_language() -> String {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+
+    let prefix = language_prefix(&locale);
+    if CATALOGS.iter().any(|(code, _)| *code == prefix) {
+        prefix.to_string()
+    } else {
+        DEFAULT_LANG.to_string()
+    }
+}
+
+/// Language used by `tr` (tests always run in English)
+fn active_language() -> String {
+    if cfg!(test) {
+        DEFAULT_LANG.to_string()
+    } else {
+        detect_language()
+    }
+}
+
+/// Translate a message key
+pub fn tr(key: &'static str) -> &'static str {
+    let t = translator();
+    t.active.get(key).or_else(|| t.fallback.get(key)).unwrap_or(key)
+}
+
+/// Translate a message key and substitute `{ $name }` placeables
+pub fn tr_args(key: &'static str, args: &[(&str, &str)]) -> String {
+    format_message(tr(key), args)
+}
+
+/// Substitute `{ $name }` placeables in a message
+pub fn format_message(message: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(message.to_string(), |msg, (name, value)| {
+        msg.replace(&format!("{{ ${} }}", name), value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_catalog() {
+        let catalog = Catalog::parse("# comment\n\nfoo = Foo\nbar = Bar = baz\n");
+        assert_eq!(catalog.messages.len(), 2);
+        assert_eq!(catalog.get("foo"), Some("Foo"));
+        assert_eq!(catalog.get("bar"), Some("Bar = baz"));
+        assert_eq!(catalog.get("missing"), None);
+    }
+
+    #[test]
+    fn test_language_prefix() {
+        assert_eq!(language_prefix("es_CO.UTF-8"), "es");
+        assert_eq!(language_prefix("pt_BR"), "pt");
+        assert_eq!(language_prefix("de_DE@euro"), "de");
+        assert_eq!(language_prefix("zh"), "zh");
+        assert_eq!(language_prefix("C.UTF-8"), "C");
+    }
+
+    #[test]
+    fn test_format_message() {
+        let msg = format_message("Mode changed to { $mode }", &[("mode", "QUIET")]);
+        assert_eq!(msg, "Mode changed to QUIET");
+
+        // Unknown placeables are left untouched
+        let msg = format_message("{ $a } and { $b }", &[("a", "1")]);
+        assert_eq!(msg, "1 and { $b }");
+    }
+
+    #[test]
+    fn test_tr_english_in_tests() {
+        assert_eq!(tr("mode-performance"), "PERFORMANCE");
+        assert_eq!(tr("no-such-key"), "no-such-key");
+    }
+
+    #[test]
+    fn test_all_catalogs_have_full_coverage() {
+        let english = Catalog::for_lang("en").unwrap();
+        assert!(!english.messages.is_empty());
+
+        for (lang, _) in CATALOGS {
+            let catalog = Catalog::for_lang(lang).unwrap();
+            for key in english.messages.keys() {
+                assert!(catalog.get(key).is_some(), "{} is missing key {}", lang, key);
+            }
+            assert_eq!(catalog.messages.len(), english.messages.len(), "{} has extra keys", lang);
+        }
+    }
+
+    #[test]
+    fn test_placeables_preserved_in_translations() {
+        let english = Catalog::for_lang("en").unwrap();
+        for (lang, _) in CATALOGS {
+            let catalog = Catalog::for_lang(lang).unwrap();
+            for (key, value) in &english.messages {
+                for placeable in value.split("{ $").skip(1) {
+                    let name = placeable.split(' ').next().unwrap();
+                    let needle = format!("{{ ${} }}", name);
+                    assert!(
+                        catalog.get(key).unwrap().contains(&needle),
+                        "{}: {} lacks {}",
+                        lang,
+                        key,
+                        needle
+                    );
+                }
+            }
+        }
+    }
+}
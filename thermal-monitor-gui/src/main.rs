@@ -4,16 +4,46 @@
 //! Displays CPU and estimated keyboard temperatures, allows mode control.
 
 mod app;
+mod cgroup;
+mod cli;
+mod config;
+mod conflicts;
+mod cpufreq;
+mod fan;
+mod fan_curve;
+mod helper;
+mod i18n;
+mod modes;
+mod platform_profile;
+mod power;
+mod power_supply;
+mod processes;
+mod rules;
+mod session;
+mod shortcuts;
 mod system;
+mod theme;
+mod units;
 
 use app::ThermalApp;
+use config::Config;
 
 fn main() -> eframe::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::parse(&args, &Config::load()) {
+        Ok(cli::Command::Gui) => {}
+        Ok(command) => std::process::exit(cli::run(command)),
+        Err(msg) => {
+            eprintln!("{}", msg);
+            std::process::exit(2);
+        }
+    }
+
     let options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
             .with_inner_size([800.0, 600.0])
             .with_min_inner_size([320.0, 400.0])  // Allow small windows
-            .with_title("Thermal Monitor"),
+            .with_title(i18n::tr("app-title")),
         ..Default::default()
     };
 
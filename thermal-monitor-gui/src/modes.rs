@@ -0,0 +1,804 @@
+//! Declarative CPU mode profiles
+//!
+//! A mode is a set of knobs (performance ceiling and floor, EPP, turbo,
+//! RAPL power limits, platform profile) applied together. Built-in modes mirror the original
+//! `cpu-mode` script; additional or overriding modes are read from
+//! `modes/<name>.ini` in `/etc/thermal-monitor` and in the user config
+//! directory, later files replacing earlier ones with the same name:
+//!
+//! ```ini
+//! [mode]
+//! label = Builds
+//! description = Long builds on battery
+//! color = #e0a040
+//! max_perf = 55
+//! min_perf = 10
+//! epp = balance_power
+//! turbo = off
+//! fan_boost = on
+//! power_limit = 12
+//! power_limit_short = 20
+//! power_window = 28
+//! platform_profile = low-power, quiet
+//! processes = cargo, rustc, cc1plus
+//! cgroups = app-flatpak-org.gnome.Builder
+//! ```
+//!
+//! `power_limit` is the sustained package limit (PL1) in watts, averaged over
+//! `power_window` seconds; `power_limit_short` is the burst limit (PL2).
+//!
+//! `processes` (process names) and `cgroups` (parts of a cgroup path) make
+//! the GUI switch to the mode while any of them runs and switch back
+//! afterwards, see [`ProcessSwitch`].
+//!
+//! Modes whose name is not a built-in one are user-defined modes; the GUI
+//! editor saves them to the user directory.
+//!
+//! Knobs left out of a file are not touched when the mode is applied.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use crate::config::{self, Ini};
+use crate::cpufreq::{detect_driver, energy_preference_choices, energy_preference_writes, SysfsWrite, MIN_PERF_PCT};
+use crate::i18n::{tr, tr_args};
+use crate::platform_profile::{self, profile_label};
+use crate::fan::{self, FanBackend};
+use crate::power::{self, LimitRequest};
+use crate::processes;
+use crate::system::{energy_preference_label, Mode};
+
+/// System-wide mode directory
+const SYSTEM_MODES_DIR: &str = "/etc/thermal-monitor/modes";
+
+/// INI section holding the knobs
+const SECTION: &str = "mode";
+
+/// All knobs a mode can set; `None` leaves the current value alone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModeProfile {
+    /// Identifier used by the CLI and the state file ("comfort")
+    pub name: String,
+    /// Button label for user-defined modes ("Presenting")
+    pub label: Option<String>,
+    /// What the mode is for ("Cool keyboard")
+    pub purpose: Option<String>,
+    /// Button color for user-defined modes (sRGB)
+    pub color: Option<[u8; 3]>,
+    pub max_perf_pct: Option<u8>,
+    pub min_perf_pct: Option<u8>,
+    pub energy_preference: Option<String>,
+    pub turbo: Option<bool>,
+    pub fan_boost: Option<bool>,
+    /// Sustained package power limit (PL1) in watts
+    pub power_limit_w: Option<u32>,
+    /// Burst package power limit (PL2) in watts
+    pub power_limit_short_w: Option<u32>,
+    /// PL1 averaging window in seconds
+    pub power_window_s: Option<u32>,
+    /// Platform profiles in order of preference; the first advertised one wins
+    pub platform_profiles: Vec<String>,
+    /// Hand over to the thermal manager timer after applying
+    pub automatic: bool,
+    /// Process names that switch to this mode while running
+    pub processes: Vec<String>,
+    /// Cgroup path fragments that switch to this mode while running
+    pub cgroups: Vec<String>,
+}
+
+impl ModeProfile {
+    /// Built-in profile for a predefined mode
+    pub fn builtin(mode: &Mode) -> Self {
+        let (max, min, epp, turbo, platform): (u8, u8, &str, bool, &[&str]) = match mode {
+            Mode::Performance => (100, 20, "performance", true, &["performance"]),
+            Mode::Comfort => (60, 10, "balance_power", false, &["balanced"]),
+            Mode::Balanced | Mode::Auto | Mode::Custom(_) | Mode::Unknown => {
+                (75, 10, "balance_performance", true, &["balanced"])
+            }
+            Mode::Quiet => (40, 10, "power", false, &["low-power", "quiet", "cool"]),
+        };
+        Self {
+            name: mode.command().to_string(),
+            purpose: Some(builtin_purpose(mode).to_string()),
+            max_perf_pct: Some(max),
+            min_perf_pct: Some(min),
+            energy_preference: Some(epp.to_string()),
+            turbo: Some(turbo),
+            platform_profiles: platform.iter().map(|p| p.to_string()).collect(),
+            automatic: *mode == Mode::Auto,
+            ..Self::default()
+        }
+    }
+
+    /// Parse the `[mode]` section of a mode file
+    pub fn from_ini(name: &str, ini: &Ini) -> Result<Self, String> {
+        if !valid_name(name) {
+            return Err(format!("Invalid mode name '{}'", name));
+        }
+        let pct = |key: &str| -> Result<Option<u8>, String> {
+            ini.get(SECTION, key)
+                .map(|v| match v.trim_end_matches('%').parse::<u8>() {
+                    Ok(pct) if pct <= 100 => Ok(pct),
+                    _ => Err(format!("{} must be 0-100, got '{}'", key, v)),
+                })
+                .transpose()
+        };
+        let flag = |key: &str| -> Result<Option<bool>, String> {
+            ini.get(SECTION, key).map(|v| parse_bool(v).ok_or(format!("{} must be on or off", key))).transpose()
+        };
+        let text = |key: &str| ini.get(SECTION, key).filter(|v| !v.is_empty()).map(String::from);
+        let list = |key: &str| -> Vec<String> {
+            ini.get(SECTION, key)
+                .map(|list| list.split(',').map(str::trim).filter(|p| !p.is_empty()).map(String::from).collect())
+                .unwrap_or_default()
+        };
+        let number = |key: &str, unit: &str| -> Result<Option<u32>, String> {
+            ini.get(SECTION, key)
+                .map(|v| match v.trim_end_matches(unit).trim_end().parse::<u32>() {
+                    Ok(n) if n > 0 => Ok(n),
+                    _ => Err(format!("{} must be a positive number, got '{}'", key, v)),
+                })
+                .transpose()
+        };
+        Ok(Self {
+            name: name.to_string(),
+            label: text("label"),
+            purpose: text("description"),
+            color: ini
+                .get(SECTION, "color")
+                .map(|v| parse_color(v).ok_or(format!("color must be #rrggbb, got '{}'", v)))
+                .transpose()?,
+            max_perf_pct: pct("max_perf")?,
+            min_perf_pct: pct("min_perf")?,
+            energy_preference: ini.get(SECTION, "epp").map(String::from),
+            turbo: flag("turbo")?,
+            fan_boost: flag("fan_boost")?,
+            power_limit_w: number("power_limit", "W")?,
+            power_limit_short_w: number("power_limit_short", "W")?,
+            power_window_s: number("power_window", "s")?,
+            platform_profiles: list("platform_profile"),
+            automatic: flag("automatic")?.unwrap_or(false),
+            processes: list("processes"),
+            cgroups: list("cgroups"),
+        })
+    }
+
+    /// Mode file contents; only knobs that are set are written
+    pub fn to_ini(&self) -> Ini {
+        let mut ini = Ini::default();
+        let on_off = |flag: bool| if flag { "on" } else { "off" };
+        if let Some(label) = &self.label {
+            ini.set(SECTION, "label", label);
+        }
+        if let Some(purpose) = &self.purpose {
+            ini.set(SECTION, "description", purpose);
+        }
+        if let Some([r, g, b]) = self.color {
+            ini.set(SECTION, "color", &format!("#{:02x}{:02x}{:02x}", r, g, b));
+        }
+        if let Some(pct) = self.max_perf_pct {
+            ini.set(SECTION, "max_perf", &pct.to_string());
+        }
+        if let Some(pct) = self.min_perf_pct {
+            ini.set(SECTION, "min_perf", &pct.to_string());
+        }
+        if let Some(epp) = &self.energy_preference {
+            ini.set(SECTION, "epp", epp);
+        }
+        if let Some(turbo) = self.turbo {
+            ini.set(SECTION, "turbo", on_off(turbo));
+        }
+        if let Some(boost) = self.fan_boost {
+            ini.set(SECTION, "fan_boost", on_off(boost));
+        }
+        if let Some(watts) = self.power_limit_w {
+            ini.set(SECTION, "power_limit", &watts.to_string());
+        }
+        if let Some(watts) = self.power_limit_short_w {
+            ini.set(SECTION, "power_limit_short", &watts.to_string());
+        }
+        if let Some(seconds) = self.power_window_s {
+            ini.set(SECTION, "power_window", &seconds.to_string());
+        }
+        if !self.platform_profiles.is_empty() {
+            ini.set(SECTION, "platform_profile", &self.platform_profiles.join(", "));
+        }
+        if self.automatic {
+            ini.set(SECTION, "automatic", "on");
+        }
+        if !self.processes.is_empty() {
+            ini.set(SECTION, "processes", &self.processes.join(", "));
+        }
+        if !self.cgroups.is_empty() {
+            ini.set(SECTION, "cgroups", &self.cgroups.join(", "));
+        }
+        ini
+    }
+
+    /// Human-readable summary of the knobs, e.g. "60%, Turbo: Off, Balance power"
+    /// Automatic modes change the knobs themselves, so they have none
+    pub fn limits(&self) -> String {
+        if self.automatic {
+            return String::new();
+        }
+        let mut parts = Vec::new();
+        if let Some(pct) = self.max_perf_pct {
+            parts.push(format!("{}%", pct));
+        }
+        if let Some(turbo) = self.turbo {
+            parts.push(format!("{}: {}", tr("label-turbo"), if turbo { tr("turbo-on") } else { tr("turbo-off") }));
+        }
+        if let Some(epp) = &self.energy_preference {
+            parts.push(energy_preference_label(epp).to_string());
+        }
+        if let Some(boost) = self.fan_boost {
+            parts.push(format!("{}: {}", tr("section-fan"), if boost { tr("fan-boost") } else { tr("fan-normal") }));
+        }
+        if let Some(watts) = self.power_limit_w {
+            parts.push(format!("PL1 {} W", watts));
+        }
+        if let Some(watts) = self.power_limit_short_w {
+            parts.push(format!("PL2 {} W", watts));
+        }
+        if parts.is_empty() {
+            if let Some(profile) = self.platform_profiles.first() {
+                parts.push(profile_label(profile).to_string());
+            }
+        }
+        parts.join(", ")
+    }
+
+    /// Description derived from the knobs plus the stated purpose
+    pub fn description(&self) -> String {
+        let limits = self.limits();
+        match (limits.is_empty(), &self.purpose) {
+            (false, Some(purpose)) => tr_args("mode-description", &[("limits", &limits), ("purpose", purpose)]),
+            (true, Some(purpose)) => purpose.clone(),
+            (_, None) => limits,
+        }
+    }
+
+    /// Sysfs writes that apply this profile below `sysfs` (normally `/sys`,
+    /// whose fan backend is already known)
+    /// Knobs the hardware does not offer are skipped
+    pub fn writes(&self, sysfs: &Path) -> io::Result<Vec<SysfsWrite>> {
+        if sysfs == Path::new(platform_profile::SYSFS_ROOT) {
+            return self.writes_with_fan(sysfs, fan::backend().as_ref());
+        }
+        self.writes_with_fan(sysfs, fan::detect(sysfs, Path::new(fan::PROCFS_ROOT), false).as_ref())
+    }
+
+    /// Same as [`writes`](Self::writes) with the fan driven through `fan`
+    pub fn writes_with_fan(&self, sysfs: &Path, fan: &dyn FanBackend) -> io::Result<Vec<SysfsWrite>> {
+        let cpu_root = sysfs.join("devices/system/cpu");
+        let driver = detect_driver(&cpu_root);
+        let mut writes = Vec::new();
+
+        let advertised = platform_profile::choices(sysfs);
+        if let Some(profile) = self.platform_profiles.iter().find(|p| advertised.contains(p)) {
+            writes.extend(platform_profile::profile_writes(sysfs, profile)?);
+        }
+        // Ceiling before floor, so a raised floor never exceeds the old ceiling
+        if let Some(pct) = self.max_perf_pct {
+            writes.extend(driver.max_perf_writes(pct.clamp(MIN_PERF_PCT, 100))?);
+        }
+        if let Some(pct) = self.min_perf_pct {
+            writes.extend(driver.min_perf_writes(pct.min(self.max_perf_pct.unwrap_or(100)))?);
+        }
+        if let Some(epp) = &self.energy_preference {
+            if energy_preference_choices(&cpu_root).contains(epp) {
+                writes.extend(energy_preference_writes(&cpu_root, epp)?);
+            }
+        }
+        if let Some(turbo) = self.turbo {
+            if driver.turbo_enabled().is_some() {
+                writes.extend(driver.turbo_writes(turbo)?);
+            }
+        }
+        if let (Some(boost), Some(_)) = (self.fan_boost, fan.boost_enabled()) {
+            writes.extend(fan.boost_writes(boost)?);
+        }
+        let request = LimitRequest {
+            long_term_w: self.power_limit_w,
+            short_term_w: self.power_limit_short_w,
+            window_s: self.power_window_s,
+        };
+        if request != LimitRequest::default() && !power::read_limits(sysfs).is_empty() {
+            writes.extend(power::limit_writes(sysfs, &request)?);
+        }
+        Ok(writes)
+    }
+
+    /// Whether running processes switch to this mode
+    pub fn has_triggers(&self) -> bool {
+        !self.processes.is_empty() || !self.cgroups.is_empty()
+    }
+
+    /// First configured process or cgroup found among the running ones
+    pub fn running_trigger(&self, names: &[String], cgroups: &[String]) -> Option<String> {
+        let process = self.processes.iter().find(|p| processes::is_running(names, p));
+        let cgroup = || self.cgroups.iter().find(|c| cgroups.iter().any(|path| path.contains(c.as_str())));
+        process.or_else(cgroup).cloned()
+    }
+}
+
+/// First profile one of whose processes runs, with the process found
+pub fn triggered<'a>(profiles: &'a [ModeProfile], names: &[String], cgroups: &[String]) -> Option<(&'a ModeProfile, String)> {
+    profiles.iter().find_map(|profile| Some((profile, profile.running_trigger(names, cgroups)?)))
+}
+
+/// Switches to a mode while its processes run and back afterwards; also
+/// used for the lid. A mode picked by hand in the meantime is left alone
+#[derive(Debug, Default)]
+pub struct ProcessSwitch {
+    /// Mode switched to for running processes
+    active: Option<String>,
+    /// Mode to return to when they exit
+    restore: Option<Mode>,
+    /// `active` and `restore` once the proposed mode is applied
+    pending: Option<(Option<String>, Option<Mode>)>,
+}
+
+impl ProcessSwitch {
+    /// Mode to apply now, given the triggered mode and the current one;
+    /// the caller reports the outcome to [`Self::commit`]
+    pub fn step(&mut self, wanted: Option<&str>, current: &Mode) -> Option<Mode> {
+        let before = (self.active.clone(), self.restore.clone());
+        let proposed = self.propose(wanted, current);
+        self.pending = None;
+        if proposed.is_some() {
+            self.pending = Some((self.active.take(), self.restore.take()));
+            (self.active, self.restore) = before;
+        }
+        proposed
+    }
+
+    /// Record the switch proposed by [`Self::step`] once its mode was
+    /// applied; a failed one is proposed again on the next step
+    pub fn commit(&mut self, applied: bool) {
+        if let Some((active, restore)) = self.pending.take().filter(|_| applied) {
+            (self.active, self.restore) = (active, restore);
+        }
+    }
+
+    fn propose(&mut self, wanted: Option<&str>, current: &Mode) -> Option<Mode> {
+        let switch_to = |name: &str| (current.command() != name).then(|| Mode::from_name(name));
+        match (wanted, self.active.take()) {
+            (Some(wanted), None) => {
+                self.active = Some(wanted.to_string());
+                self.restore = Some(current.clone()).filter(|mode| *mode != Mode::Unknown);
+                switch_to(wanted)
+            }
+            (Some(wanted), Some(active)) if wanted != active => {
+                self.active = Some(wanted.to_string());
+                // Only switch over from our own mode, not from one picked by hand
+                (current.command() == active).then(|| switch_to(wanted)).flatten()
+            }
+            (Some(_), active) => {
+                self.active = active;
+                None
+            }
+            (None, Some(active)) => {
+                let restore = self.restore.take()?;
+                (current.command() == active && restore != *current).then_some(restore)
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// Mode applied for running processes, if any
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+}
+
+fn builtin_purpose(mode: &Mode) -> &'static str {
+    match mode {
+        Mode::Performance => tr("mode-performance-desc"),
+        Mode::Comfort => tr("mode-comfort-desc"),
+        Mode::Balanced => tr("mode-balanced-desc"),
+        Mode::Quiet => tr("mode-quiet-desc"),
+        Mode::Auto => tr("mode-auto-desc"),
+        Mode::Custom(_) | Mode::Unknown => tr("mode-unknown-desc"),
+    }
+}
+
+/// Whether `name` belongs to one of the predefined modes
+pub fn is_builtin(name: &str) -> bool {
+    Mode::all().iter().any(|mode| mode.command() == name)
+}
+
+/// Mode name derived from a display label: "Compile (fast)" -> "compile-fast"
+pub fn slug(label: &str) -> String {
+    let mut name = String::new();
+    for c in label.trim().to_lowercase().chars() {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    name.truncate(32);
+    name.trim_end_matches('-').to_string()
+}
+
+/// Mode names double as file names and helper arguments
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 32
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn parse_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.trim().strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => Some(true),
+        "off" | "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Mode directory inside the user config directory
+pub fn user_modes_dir() -> Option<PathBuf> {
+    config::Config::path().and_then(|path| path.parent().map(|dir| dir.join("modes")))
+}
+
+/// Read every `*.ini` mode file in `dir`, sorted by name
+/// Broken files are reported on stderr and skipped
+pub fn load_dir(dir: &Path) -> Vec<ModeProfile> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ini"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?;
+            let parsed = fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|source| ModeProfile::from_ini(name, &Ini::parse(&source)));
+            match parsed {
+                Ok(profile) => Some(profile),
+                Err(e) => {
+                    eprintln!("Ignoring mode file {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Built-ins followed by profiles from `dirs`; same names replace earlier ones
+pub fn load(dirs: &[PathBuf]) -> Vec<ModeProfile> {
+    let mut profiles: Vec<ModeProfile> = Mode::all().iter().map(ModeProfile::builtin).collect();
+    for profile in dirs.iter().flat_map(|dir| load_dir(dir)) {
+        match profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+    }
+    profiles
+}
+
+fn search_dirs() -> Vec<PathBuf> {
+    // Tests only see the built-ins
+    if cfg!(test) {
+        return Vec::new();
+    }
+    std::iter::once(PathBuf::from(SYSTEM_MODES_DIR)).chain(user_modes_dir()).collect()
+}
+
+/// Write `profile` to `dir/<name>.ini`, creating the directory
+pub fn save_to(dir: &Path, profile: &ModeProfile) -> io::Result<PathBuf> {
+    if !valid_name(&profile.name) {
+        return Err(io::Error::new(ErrorKind::InvalidInput, format!("Invalid mode name '{}'", profile.name)));
+    }
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.ini", profile.name));
+    fs::write(&path, profile.to_ini().to_string())?;
+    Ok(path)
+}
+
+/// Remove `dir/<name>.ini`
+pub fn delete_from(dir: &Path, name: &str) -> io::Result<()> {
+    if !valid_name(name) {
+        return Err(io::Error::new(ErrorKind::InvalidInput, format!("Invalid mode name '{}'", name)));
+    }
+    fs::remove_file(dir.join(format!("{}.ini", name)))
+}
+
+fn require_user_dir() -> io::Result<PathBuf> {
+    user_modes_dir().ok_or_else(|| io::Error::new(ErrorKind::NotFound, "No config directory"))
+}
+
+/// Save a mode to the user directory and make it available immediately
+pub fn save_user(profile: &ModeProfile) -> io::Result<PathBuf> {
+    let path = save_to(&require_user_dir()?, profile)?;
+    reload();
+    Ok(path)
+}
+
+/// Delete a user mode; a system file with the same name takes over again
+pub fn delete_user(name: &str) -> io::Result<()> {
+    delete_from(&require_user_dir()?, name)?;
+    reload();
+    Ok(())
+}
+
+fn registry() -> &'static RwLock<Vec<ModeProfile>> {
+    static REGISTRY: OnceLock<RwLock<Vec<ModeProfile>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(load(&search_dirs())))
+}
+
+/// Re-read the mode directories
+pub fn reload() {
+    let profiles = load(&search_dirs());
+    *registry().write().unwrap_or_else(PoisonError::into_inner) = profiles;
+}
+
+/// All known profiles: built-ins first, then system and user files
+pub fn profiles() -> Vec<ModeProfile> {
+    registry().read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Profile by name
+pub fn find(name: &str) -> Option<ModeProfile> {
+    registry().read().unwrap_or_else(PoisonError::into_inner).iter().find(|p| p.name == name).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::{fake_cpus, put};
+
+    #[test]
+    fn test_builtin_descriptions_derived() {
+        let comfort = ModeProfile::builtin(&Mode::Comfort);
+        assert_eq!(comfort.description(), "60%, Turbo: Off, Balance power - Cool keyboard");
+        assert_eq!(ModeProfile::builtin(&Mode::Auto).description(), "Automatic");
+        assert_eq!(Mode::Performance.description(), ModeProfile::builtin(&Mode::Performance).description());
+    }
+
+    #[test]
+    fn test_parse_mode_file() {
+        let ini = Ini::parse(
+            "[mode]\ndescription = Builds\nmax_perf = 55%\nepp = balance_power\nturbo = off\nplatform_profile = low-power, quiet\n",
+        );
+        let profile = ModeProfile::from_ini("builds", &ini).unwrap();
+        assert_eq!(profile.max_perf_pct, Some(55));
+        assert_eq!(profile.min_perf_pct, None);
+        assert_eq!(profile.turbo, Some(false));
+        assert_eq!(profile.platform_profiles, ["low-power", "quiet"]);
+        assert_eq!(profile.description(), "55%, Turbo: Off, Balance power - Builds");
+    }
+
+    #[test]
+    fn test_custom_mode_roundtrip() {
+        let ini = Ini::parse("[mode]\nlabel = Compile\ncolor = #E0a040\nmax_perf = 100\nfan_boost = on\n");
+        let profile = ModeProfile::from_ini("compile", &ini).unwrap();
+        assert_eq!(profile.label.as_deref(), Some("Compile"));
+        assert_eq!(profile.color, Some([0xe0, 0xa0, 0x40]));
+        assert_eq!(profile.description(), "100%, Fan: BOOST");
+        assert_eq!(ModeProfile::from_ini("compile", &profile.to_ini()), Ok(profile.clone()));
+
+        let builtin = ModeProfile::builtin(&Mode::Quiet);
+        assert_eq!(ModeProfile::from_ini("quiet", &builtin.to_ini()), Ok(builtin));
+        assert!(ModeProfile::from_ini("x", &Ini::parse("[mode]\ncolor = red\n")).is_err());
+    }
+
+    #[test]
+    fn test_shipped_modes_parse() {
+        let shipped = load_dir(&Path::new(env!("CARGO_MANIFEST_DIR")).join("modes"));
+        let names: Vec<&str> = shipped.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["compile", "presenting"]);
+        assert!(shipped.iter().all(|p| p.label.is_some() && p.color.is_some() && !is_builtin(&p.name)));
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Compile"), "compile");
+        assert_eq!(slug("  Presenting (quiet fan) "), "presenting-quiet-fan");
+        assert_eq!(slug("Ünïcode only"), "n-code-only");
+        assert_eq!(slug("!!!"), "");
+        assert!(valid_name(&slug(&"x".repeat(40))));
+    }
+
+    #[test]
+    fn test_save_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let modes_dir = dir.path().join("modes");
+        let profile = ModeProfile { name: "presenting".into(), max_perf_pct: Some(70), fan_boost: Some(false), ..Default::default() };
+        let path = save_to(&modes_dir, &profile).unwrap();
+        assert_eq!(path, modes_dir.join("presenting.ini"));
+        assert_eq!(load_dir(&modes_dir), std::slice::from_ref(&profile));
+
+        delete_from(&modes_dir, "presenting").unwrap();
+        assert!(load_dir(&modes_dir).is_empty());
+        assert!(save_to(&modes_dir, &ModeProfile { name: "../x".into(), ..profile }).is_err());
+        assert!(delete_from(&modes_dir, "..").is_err());
+    }
+
+    #[test]
+    fn test_invalid_mode_files() {
+        assert!(ModeProfile::from_ini("x", &Ini::parse("[mode]\nmax_perf = 150\n")).is_err());
+        assert!(ModeProfile::from_ini("x", &Ini::parse("[mode]\nturbo = maybe\n")).is_err());
+        assert!(ModeProfile::from_ini("../etc", &Ini::default()).is_err());
+        assert!(ModeProfile::from_ini("Mixed", &Ini::default()).is_err());
+    }
+
+    #[test]
+    fn test_user_files_override_and_extend() {
+        let dir = tempfile::tempdir().unwrap();
+        put(dir.path(), "comfort.ini", "[mode]\nmax_perf = 50\n");
+        put(dir.path(), "render.ini", "[mode]\nmax_perf = 90\nturbo = on\n");
+        put(dir.path(), "broken.ini", "[mode]\nturbo = sometimes\n");
+        put(dir.path(), "notes.txt", "ignored");
+
+        let profiles = load(&[dir.path().to_path_buf()]);
+        assert_eq!(profiles.len(), Mode::all().len() + 1);
+        let comfort = profiles.iter().find(|p| p.name == "comfort").unwrap();
+        assert_eq!(comfort.max_perf_pct, Some(50));
+        assert_eq!(comfort.turbo, None);
+        assert_eq!(profiles.last().unwrap().name, "render");
+    }
+
+    #[test]
+    fn test_writes_skip_unsupported_knobs() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let cpu = sysfs.path().join("devices/system/cpu");
+        fake_cpus(&cpu, 2, "intel_pstate", 4_400_000, 4_400_000);
+        put(&cpu, "intel_pstate/max_perf_pct", "100");
+        put(&cpu, "intel_pstate/min_perf_pct", "20");
+        put(sysfs.path(), "firmware/acpi/platform_profile", "balanced");
+        put(sysfs.path(), "firmware/acpi/platform_profile_choices", "quiet balanced performance");
+
+        // No EPP and no turbo switch on this machine; "low-power" is not offered
+        let writes = ModeProfile::builtin(&Mode::Quiet).writes(sysfs.path()).unwrap();
+        let values: Vec<(String, &str)> = writes
+            .iter()
+            .map(|w| (w.path.strip_prefix(sysfs.path()).unwrap().display().to_string(), w.value.as_str()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("firmware/acpi/platform_profile".to_string(), "quiet"),
+                ("devices/system/cpu/intel_pstate/max_perf_pct".to_string(), "40"),
+                ("devices/system/cpu/intel_pstate/min_perf_pct".to_string(), "10"),
+            ]
+        );
+
+        put(&cpu, "intel_pstate/no_turbo", "0");
+        let writes = ModeProfile::builtin(&Mode::Quiet).writes(sysfs.path()).unwrap();
+        assert_eq!(writes.last().unwrap().value, "1");
+
+        // Fan boost only where a fan backend exists
+        let compile = ModeProfile { fan_boost: Some(true), ..Default::default() };
+        let no_fan = fan::detect(sysfs.path(), sysfs.path(), false);
+        assert!(compile.writes_with_fan(sysfs.path(), no_fan.as_ref()).unwrap().is_empty());
+        let fan_mode = "bus/platform/drivers/ideapad_acpi/VPC2004:00/fan_mode";
+        put(sysfs.path(), fan_mode, "0");
+        let ideapad = fan::detect(sysfs.path(), sysfs.path(), false);
+        let writes = compile.writes_with_fan(sysfs.path(), ideapad.as_ref()).unwrap();
+        assert_eq!(writes, [SysfsWrite::new(sysfs.path().join(fan_mode), "1")]);
+    }
+
+    #[test]
+    fn test_power_limit_mode() {
+        let ini = Ini::parse("[mode]\ndescription = Quiet lap\npower_limit = 12 W\npower_limit_short = 20\npower_window = 28s\n");
+        let profile = ModeProfile::from_ini("lap", &ini).unwrap();
+        assert_eq!(profile.power_limit_w, Some(12));
+        assert_eq!(profile.power_window_s, Some(28));
+        assert_eq!(profile.description(), "PL1 12 W, PL2 20 W - Quiet lap");
+        assert_eq!(ModeProfile::from_ini("lap", &profile.to_ini()), Ok(profile.clone()));
+        assert!(ModeProfile::from_ini("x", &Ini::parse("[mode]\npower_limit = 0\n")).is_err());
+        assert!(ModeProfile::from_ini("x", &Ini::parse("[mode]\npower_limit = lots\n")).is_err());
+
+        // Skipped without RAPL, written on every package with it
+        let sysfs = tempfile::tempdir().unwrap();
+        assert!(profile.writes_with_fan(sysfs.path(), fan::detect(sysfs.path(), sysfs.path(), false).as_ref()).unwrap().is_empty());
+        let zone = "class/powercap/intel-rapl:0";
+        put(sysfs.path(), &format!("{}/name", zone), "package-0");
+        put(sysfs.path(), &format!("{}/constraint_0_name", zone), "long_term");
+        put(sysfs.path(), &format!("{}/constraint_0_power_limit_uw", zone), "28000000");
+        put(sysfs.path(), &format!("{}/constraint_0_time_window_us", zone), "27983872");
+        let writes = profile.writes_with_fan(sysfs.path(), fan::detect(sysfs.path(), sysfs.path(), false).as_ref()).unwrap();
+        let zone = sysfs.path().join(zone);
+        assert_eq!(
+            writes,
+            [
+                SysfsWrite::new(zone.join("constraint_0_power_limit_uw"), "12000000"),
+                SysfsWrite::new(zone.join("constraint_0_time_window_us"), "28000000"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_process_triggers() {
+        let ini = Ini::parse("[mode]\nprocesses = zoom, teams,\ncgroups = us.zoom.Zoom\n");
+        let presenting = ModeProfile::from_ini("presenting", &ini).unwrap();
+        assert_eq!(presenting.processes, ["zoom", "teams"]);
+        assert_eq!(ModeProfile::from_ini("presenting", &presenting.to_ini()), Ok(presenting.clone()));
+        let compile = ModeProfile::from_ini("compile", &Ini::parse("[mode]\nprocesses = cargo\n")).unwrap();
+        let profiles = [ModeProfile::builtin(&Mode::Quiet), presenting, compile];
+        assert!(!profiles[0].has_triggers());
+
+        let names = |list: &[&str]| list.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let flatpak = names(&["/user.slice/app.slice/app-flatpak-us.zoom.Zoom-4242.scope"]);
+        let found = |names: &[String], cgroups: &[String]| {
+            triggered(&profiles, names, cgroups).map(|(profile, app)| (profile.name.clone(), app))
+        };
+        assert_eq!(found(&names(&["bash", "cargo"]), &[]), Some(("compile".into(), "cargo".into())));
+        assert_eq!(found(&names(&["cargo", "teams"]), &[]), Some(("presenting".into(), "teams".into())));
+        assert_eq!(found(&names(&["bash"]), &flatpak), Some(("presenting".into(), "us.zoom.Zoom".into())));
+        assert_eq!(found(&names(&["bash"]), &[]), None);
+    }
+
+    /// Step with the proposed mode applied
+    fn applied(switch: &mut ProcessSwitch, wanted: Option<&str>, current: &Mode) -> Option<Mode> {
+        let mode = switch.step(wanted, current);
+        switch.commit(true);
+        mode
+    }
+
+    #[test]
+    fn test_process_switch() {
+        let mut switch = ProcessSwitch::default();
+        assert_eq!(applied(&mut switch, None, &Mode::Quiet), None);
+
+        // Applied on start, restored on exit
+        assert_eq!(applied(&mut switch, Some("performance"), &Mode::Quiet), Some(Mode::Performance));
+        assert_eq!(switch.active(), Some("performance"));
+        assert_eq!(applied(&mut switch, Some("performance"), &Mode::Performance), None);
+        assert_eq!(applied(&mut switch, None, &Mode::Performance), Some(Mode::Quiet));
+        assert_eq!(switch.active(), None);
+
+        // Moves on to another triggered mode, still restoring the first one
+        applied(&mut switch, Some("performance"), &Mode::Balanced);
+        assert_eq!(applied(&mut switch, Some("comfort"), &Mode::Performance), Some(Mode::Comfort));
+        assert_eq!(applied(&mut switch, None, &Mode::Comfort), Some(Mode::Balanced));
+
+        // A mode picked by hand meanwhile is kept
+        applied(&mut switch, Some("performance"), &Mode::Balanced);
+        assert_eq!(applied(&mut switch, Some("performance"), &Mode::Quiet), None);
+        assert_eq!(applied(&mut switch, Some("comfort"), &Mode::Quiet), None);
+        assert_eq!(applied(&mut switch, None, &Mode::Quiet), None);
+
+        // Already in the mode: nothing to apply, nothing to restore
+        assert_eq!(applied(&mut switch, Some("performance"), &Mode::Performance), None);
+        assert_eq!(applied(&mut switch, None, &Mode::Performance), None);
+    }
+
+    #[test]
+    fn test_process_switch_failed() {
+        let mut switch = ProcessSwitch::default();
+        assert_eq!(switch.step(Some("performance"), &Mode::Quiet), Some(Mode::Performance));
+        switch.commit(false);
+        // Nothing recorded, so the switch is tried again
+        assert_eq!(switch.active(), None);
+        assert_eq!(switch.step(Some("performance"), &Mode::Quiet), Some(Mode::Performance));
+        switch.commit(true);
+        assert_eq!(switch.active(), Some("performance"));
+
+        // A failed restore is retried too
+        assert_eq!(switch.step(None, &Mode::Performance), Some(Mode::Quiet));
+        switch.commit(false);
+        assert_eq!(switch.active(), Some("performance"));
+        assert_eq!(switch.step(None, &Mode::Performance), Some(Mode::Quiet));
+        switch.commit(true);
+        assert_eq!(switch.active(), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! ACPI platform profile selection
+//!
+//! Kernels since 6.14 expose one class device per profile handler under
+//! `/sys/class/platform-profile/platform-profile-N` with `name`, `profile`
+//! and `choices`. Older kernels only have the aggregated legacy files
+//! `/sys/firmware/acpi/platform_profile{,_choices}`. Values are always
+//! validated against the advertised choices before writing.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::cpufreq::SysfsWrite;
+use crate::i18n::tr;
+use crate::system::read_sysfs_value;
+
+/// Default sysfs mount point
+pub const SYSFS_ROOT: &str = "/sys";
+
+/// One platform profile handler (e.g. "lenovo-wmi-gamezone", or "acpi" for
+/// the legacy interface)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileHandler {
+    pub name: String,
+    /// Attribute holding the active profile
+    pub path: PathBuf,
+    pub choices: Vec<String>,
+}
+
+impl ProfileHandler {
+    pub fn current(&self) -> Option<String> {
+        read_sysfs_value(&self.path).ok()
+    }
+}
+
+/// Profile handlers found below `sysfs`, preferring the class interface
+pub fn handlers(sysfs: &Path) -> Vec<ProfileHandler> {
+    let mut found: Vec<ProfileHandler> = fs::read_dir(sysfs.join("class/platform-profile"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            Some(ProfileHandler {
+                name: read_sysfs_value(dir.join("name")).unwrap_or_else(|_| entry.file_name().to_string_lossy().into()),
+                choices: read_choices(dir.join("choices"))?,
+                path: dir.join("profile"),
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if found.is_empty() {
+        let legacy = sysfs.join("firmware/acpi");
+        if let Some(choices) = read_choices(legacy.join("platform_profile_choices")) {
+            found.push(ProfileHandler { name: "acpi".into(), path: legacy.join("platform_profile"), choices });
+        }
+    }
+    found
+}
+
+fn read_choices(path: PathBuf) -> Option<Vec<String>> {
+    let list = read_sysfs_value(path).ok()?;
+    Some(list.split_whitespace().map(String::from).collect())
+}
+
+/// Active profile of the first handler
+pub fn current(sysfs: &Path) -> Option<String> {
+    handlers(sysfs).first().and_then(ProfileHandler::current)
+}
+
+/// Profiles every handler accepts, in the first handler's order
+pub fn choices(sysfs: &Path) -> Vec<String> {
+    let found = handlers(sysfs);
+    let Some((first, rest)) = found.split_first() else {
+        return Vec::new();
+    };
+    first
+        .choices
+        .iter()
+        .filter(|choice| rest.iter().all(|h| h.choices.contains(choice)))
+        .cloned()
+        .collect()
+}
+
+/// Writes that switch every handler to `profile`, rejecting unadvertised values
+pub fn profile_writes(sysfs: &Path, profile: &str) -> io::Result<Vec<SysfsWrite>> {
+    let found = handlers(sysfs);
+    if found.is_empty() {
+        return Err(io::Error::new(ErrorKind::Unsupported, "Platform profiles not supported"));
+    }
+    if !choices(sysfs).iter().any(|choice| choice == profile) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Unsupported platform profile '{}'", profile),
+        ));
+    }
+    Ok(found.into_iter().map(|h| SysfsWrite::new(h.path, profile)).collect())
+}
+
+/// Localized profile name; profiles not known to us are shown as-is
+pub fn profile_label(profile: &str) -> &str {
+    match profile {
+        "low-power" => tr("profile-low-power"),
+        "cool" => tr("profile-cool"),
+        "quiet" => tr("profile-quiet"),
+        "balanced" => tr("profile-balanced"),
+        "balanced-performance" => tr("profile-balanced-performance"),
+        "performance" => tr("profile-performance"),
+        "max-power" => tr("profile-max-power"),
+        "custom" => tr("profile-custom"),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+
+    #[test]
+    fn test_legacy_interface() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(handlers(dir.path()).is_empty());
+        assert!(profile_writes(dir.path(), "balanced").is_err());
+
+        put(dir.path(), "firmware/acpi/platform_profile", "balanced\n");
+        put(dir.path(), "firmware/acpi/platform_profile_choices", "quiet balanced performance\n");
+        assert_eq!(current(dir.path()).as_deref(), Some("balanced"));
+        assert_eq!(choices(dir.path()), ["quiet", "balanced", "performance"]);
+
+        let writes = profile_writes(dir.path(), "quiet").unwrap();
+        assert_eq!(writes, vec![SysfsWrite::new(dir.path().join("firmware/acpi/platform_profile"), "quiet")]);
+        // "low-power" is not advertised on this machine
+        assert_eq!(profile_writes(dir.path(), "low-power").unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_class_interface_preferred() {
+        let dir = tempfile::tempdir().unwrap();
+        put(dir.path(), "firmware/acpi/platform_profile", "balanced");
+        put(dir.path(), "firmware/acpi/platform_profile_choices", "balanced");
+        put(dir.path(), "class/platform-profile/platform-profile-0/name", "ideapad");
+        put(dir.path(), "class/platform-profile/platform-profile-0/profile", "low-power");
+        put(dir.path(), "class/platform-profile/platform-profile-0/choices", "low-power balanced performance");
+        put(dir.path(), "class/platform-profile/platform-profile-1/name", "amd-pmf");
+        put(dir.path(), "class/platform-profile/platform-profile-1/profile", "low-power");
+        put(dir.path(), "class/platform-profile/platform-profile-1/choices", "low-power balanced");
+
+        let found = handlers(dir.path());
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].name, "ideapad");
+        assert_eq!(current(dir.path()).as_deref(), Some("low-power"));
+        // Only profiles every handler supports are offered
+        assert_eq!(choices(dir.path()), ["low-power", "balanced"]);
+        assert_eq!(profile_writes(dir.path(), "balanced").unwrap().len(), 2);
+        assert!(profile_writes(dir.path(), "performance").is_err());
+    }
+
+    #[test]
+    fn test_profile_label() {
+        assert_eq!(profile_label("low-power"), "Low power");
+        assert_eq!(profile_label("vendor-turbo"), "vendor-turbo");
+    }
+}
@@ -0,0 +1,606 @@
+//! CPU power readout
+//!
+//! Power is derived from energy counters read twice:
+//! - RAPL powercap zones (`/sys/class/powercap/intel-rapl:*`, also used by
+//!   AMD since Zen): `energy_uj` counts microjoules and wraps at
+//!   `max_energy_range_uj`; `name` tells the domain (`package-N`, `core`,
+//!   `uncore`)
+//! - the `amd_energy` hwmon driver: `energyN_input` in microjoules, labelled
+//!   `Esocket*` or `Ecore*`
+//!
+//! Without counters, `zenpower` hwmon sensors give instantaneous
+//! `powerN_input` in microwatts (core and SoC).
+//!
+//! Intel restricts `energy_uj` to root on current kernels; that case is
+//! reported as [`PowerReading::restricted`] rather than as missing.
+//!
+//! Package zones also carry the firmware power limits: constraint
+//! `long_term` (PL1, averaged over `constraint_N_time_window_us`) and
+//! `short_term` (PL2), each with `constraint_N_power_limit_uw` and an
+//! optional `constraint_N_max_power_uw`. Those are read here and written
+//! through the privileged helper.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use crate::cpufreq::SysfsWrite;
+use crate::i18n::tr_args;
+use crate::platform_profile::SYSFS_ROOT;
+use crate::system::read_sysfs_value;
+
+/// Shortest interval that gives a meaningful average
+const MIN_INTERVAL_SECS: f64 = 0.05;
+
+/// Lowest sustained limit accepted, so a typo cannot stall the machine
+pub const MIN_POWER_LIMIT_W: u32 = 3;
+
+/// RAPL expresses time windows in units of 1/1024 s on every current CPU
+const TIME_UNITS_PER_SEC: u64 = 1024;
+
+/// Previous counter sample, so every reading averages since the last one
+static LAST_SAMPLE: Mutex<Option<Sample>> = Mutex::new(None);
+
+/// Power domain a counter or sensor measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Domain {
+    /// Whole CPU package (all sockets summed)
+    Package,
+    /// CPU cores
+    Core,
+    /// Uncore: integrated GPU, caches, SoC
+    Uncore,
+}
+
+/// Power in watts per domain; `None` where the hardware has no source
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PowerReading {
+    pub package: Option<f32>,
+    pub core: Option<f32>,
+    pub uncore: Option<f32>,
+    /// Energy counters exist but only root may read them
+    pub restricted: bool,
+}
+
+impl PowerReading {
+    /// Headline figure: package power, or core power where that is all there is
+    pub fn total(&self) -> Option<f32> {
+        self.package.or(self.core)
+    }
+
+    /// Per-domain figures: "Package 12.3 W, Core 8.1 W, Uncore 0.4 W"
+    pub fn breakdown(&self) -> String {
+        let parts: Vec<String> = [("power-package", self.package), ("power-core", self.core), ("power-uncore", self.uncore)]
+            .into_iter()
+            .filter_map(|(key, watts)| Some(tr_args(key, &[("watts", &format!("{:.1}", watts?))])))
+            .collect();
+        parts.join(", ")
+    }
+
+    fn add(&mut self, domain: Domain, watts: f32) {
+        let slot = match domain {
+            Domain::Package => &mut self.package,
+            Domain::Core => &mut self.core,
+            Domain::Uncore => &mut self.uncore,
+        };
+        *slot = Some(slot.unwrap_or(0.0) + watts);
+    }
+}
+
+/// RAPL power limit constraint of a package zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// PL1: sustained limit, averaged over the time window
+    LongTerm,
+    /// PL2: turbo limit for short bursts
+    ShortTerm,
+}
+
+impl Constraint {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "long_term" => Some(Constraint::LongTerm),
+            "short_term" => Some(Constraint::ShortTerm),
+            _ => None,
+        }
+    }
+
+    /// Name Intel documentation uses
+    pub fn label(&self) -> &'static str {
+        match self {
+            Constraint::LongTerm => "PL1",
+            Constraint::ShortTerm => "PL2",
+        }
+    }
+}
+
+/// One power limit as the firmware currently has it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerLimit {
+    pub constraint: Constraint,
+    /// `constraint_N_power_limit_uw`
+    pub path: PathBuf,
+    pub power_uw: u64,
+    /// Averaging window; PL2 has none on most CPUs
+    pub window_us: Option<u64>,
+    /// Highest limit the firmware accepts, where it says so
+    pub max_uw: Option<u64>,
+}
+
+impl PowerLimit {
+    pub fn watts(&self) -> f32 {
+        (self.power_uw as f64 / 1e6) as f32
+    }
+
+    pub fn max_watts(&self) -> Option<f32> {
+        self.max_uw.map(|uw| (uw as f64 / 1e6) as f32)
+    }
+
+    fn window_path(&self) -> PathBuf {
+        let name = self.path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        self.path.with_file_name(name.replace("power_limit_uw", "time_window_us"))
+    }
+
+    /// "PL1 15 W (28 s)"
+    pub fn summary(&self) -> String {
+        let mut text = format!("{} {} W", self.constraint.label(), trim_watts(self.watts()));
+        if let Some(us) = self.window_us {
+            text.push_str(&format!(" ({} s)", trim_watts((us as f64 / 1e6) as f32)));
+        }
+        text
+    }
+}
+
+/// PL1 and PL2 of the first package; `None` where the CPU has no such constraint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PowerLimits {
+    pub long_term: Option<PowerLimit>,
+    pub short_term: Option<PowerLimit>,
+}
+
+impl PowerLimits {
+    pub fn is_empty(&self) -> bool {
+        self.long_term.is_none() && self.short_term.is_none()
+    }
+
+    /// "PL1 15 W (28 s), PL2 25 W"
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [&self.long_term, &self.short_term].into_iter().flatten().map(PowerLimit::summary).collect();
+        parts.join(", ")
+    }
+
+    /// PL1 that counts as 100% for thermal control: the firmware maximum,
+    /// else PL2
+    pub fn full_scale_watts(&self) -> Option<f32> {
+        let long_term = self.long_term.as_ref()?;
+        long_term.max_watts().or(self.short_term.as_ref().map(PowerLimit::watts)).filter(|&watts| watts > 0.0)
+    }
+
+    /// Current PL1 as a percentage of [`full_scale_watts`](Self::full_scale_watts)
+    pub fn long_term_pct(&self) -> Option<u8> {
+        let pct = self.long_term.as_ref()?.watts() / self.full_scale_watts()? * 100.0;
+        Some(pct.round().clamp(0.0, 100.0) as u8)
+    }
+
+    /// PL1 in whole watts for a percentage of full scale
+    pub fn long_term_watts_at(&self, pct: u8) -> Option<u32> {
+        let watts = self.full_scale_watts()? * pct.min(100) as f32 / 100.0;
+        Some((watts.round() as u32).max(MIN_POWER_LIMIT_W))
+    }
+}
+
+/// Limits to set; `None` leaves that value alone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LimitRequest {
+    pub long_term_w: Option<u32>,
+    pub short_term_w: Option<u32>,
+    /// PL1 averaging window in seconds
+    pub window_s: Option<u32>,
+}
+
+/// Energy counter reading
+#[derive(Debug, Clone, PartialEq)]
+struct Counter {
+    path: PathBuf,
+    domain: Domain,
+    energy_uj: u64,
+    /// Value at which the counter wraps to zero
+    range_uj: u64,
+}
+
+/// All counters and instantaneous sensors at one point in time
+#[derive(Debug, Clone)]
+struct Sample {
+    at: Instant,
+    counters: Vec<Counter>,
+    /// Instantaneous sensors, in watts
+    sensors: Vec<(Domain, f32)>,
+    restricted: bool,
+}
+
+/// Energy used between two counter values, allowing for one wraparound
+fn energy_delta(previous: u64, current: u64, range: u64) -> u64 {
+    if current >= previous {
+        current - previous
+    } else {
+        range.saturating_sub(previous) + current
+    }
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> =
+        fs::read_dir(dir).into_iter().flatten().flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+    entries
+}
+
+/// Domain of a RAPL zone from its `name`; dram and psys are not CPU power
+fn rapl_domain(name: &str) -> Option<Domain> {
+    match name {
+        "core" => Some(Domain::Core),
+        "uncore" => Some(Domain::Uncore),
+        name if name.starts_with("package") => Some(Domain::Package),
+        _ => None,
+    }
+}
+
+/// Numbered hwmon attributes such as `energy3_input`, with their label
+fn hwmon_inputs(hwmon: &Path, prefix: &str) -> Vec<(PathBuf, String)> {
+    sorted_entries(hwmon)
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let index = name.strip_prefix(prefix)?.strip_suffix("_input")?;
+            if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let label = read_sysfs_value(hwmon.join(format!("{}{}_label", prefix, index))).unwrap_or_default();
+            Some((path, label.to_lowercase()))
+        })
+        .collect()
+}
+
+/// Read every counter and sensor below `sysfs`
+fn sample(sysfs: &Path) -> Sample {
+    let mut counters = Vec::new();
+    let mut sensors = Vec::new();
+    let mut restricted = false;
+
+    for zone in sorted_entries(&sysfs.join("class/powercap")) {
+        let is_rapl = zone.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("intel-rapl:"));
+        let Some(domain) = read_sysfs_value(zone.join("name")).ok().filter(|_| is_rapl).and_then(|n| rapl_domain(&n))
+        else {
+            continue;
+        };
+        match read_sysfs_value(zone.join("energy_uj")) {
+            Ok(value) => {
+                let range_uj = read_sysfs_value(zone.join("max_energy_range_uj")).ok().and_then(|v| v.parse().ok());
+                if let (Ok(energy_uj), Some(range_uj)) = (value.parse(), range_uj) {
+                    counters.push(Counter { path: zone.join("energy_uj"), domain, energy_uj, range_uj });
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => restricted = true,
+            Err(_) => {}
+        }
+    }
+
+    for hwmon in sorted_entries(&sysfs.join("class/hwmon")) {
+        match read_sysfs_value(hwmon.join("name")).unwrap_or_default().as_str() {
+            // RAPL covers the same counters where it is readable
+            "amd_energy" if counters.is_empty() => {
+                for (path, label) in hwmon_inputs(&hwmon, "energy") {
+                    let domain = if label.starts_with("esocket") { Domain::Package } else { Domain::Core };
+                    match read_sysfs_value(&path) {
+                        Ok(value) => {
+                            if let Ok(energy_uj) = value.parse() {
+                                // Accumulated in 64 bits by the driver
+                                counters.push(Counter { path, domain, energy_uj, range_uj: u64::MAX });
+                            }
+                        }
+                        Err(e) if e.kind() == ErrorKind::PermissionDenied => restricted = true,
+                        Err(_) => {}
+                    }
+                }
+            }
+            "zenpower" => {
+                for (path, label) in hwmon_inputs(&hwmon, "power") {
+                    let domain = if label.contains("soc") { Domain::Uncore } else { Domain::Core };
+                    if let Some(microwatts) = read_sysfs_value(&path).ok().and_then(|v| v.parse::<f64>().ok()) {
+                        sensors.push((domain, (microwatts / 1e6) as f32));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Sample { at: Instant::now(), counters, sensors, restricted }
+}
+
+/// Average power between two samples of the same counters
+fn power_between(previous: &Sample, current: &Sample) -> PowerReading {
+    let mut reading = PowerReading { restricted: current.restricted, ..Default::default() };
+    let seconds = current.at.duration_since(previous.at).as_secs_f64();
+    if seconds >= MIN_INTERVAL_SECS {
+        for counter in &current.counters {
+            if let Some(before) = previous.counters.iter().find(|c| c.path == counter.path) {
+                let joules = energy_delta(before.energy_uj, counter.energy_uj, counter.range_uj) as f64 / 1e6;
+                reading.add(counter.domain, (joules / seconds) as f32);
+            }
+        }
+    }
+    for &(domain, watts) in &current.sensors {
+        reading.add(domain, watts);
+    }
+    reading
+}
+
+/// "12", or "12.5" for fractional watts
+fn trim_watts(watts: f32) -> String {
+    let text = format!("{:.1}", watts);
+    text.strip_suffix(".0").map(String::from).unwrap_or(text)
+}
+
+/// Package zones with power limits: `intel-rapl:N`, not the MMIO copy
+fn package_zones(sysfs: &Path) -> Vec<PathBuf> {
+    sorted_entries(&sysfs.join("class/powercap"))
+        .into_iter()
+        .filter(|zone| {
+            zone.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("intel-rapl:"))
+                .is_some_and(|index| !index.contains(':'))
+        })
+        .filter(|zone| read_sysfs_value(zone.join("name")).is_ok_and(|name| name.starts_with("package")))
+        .collect()
+}
+
+fn zone_limits(zone: &Path) -> PowerLimits {
+    let mut limits = PowerLimits::default();
+    let number = |index: u32, attribute: &str| -> Option<u64> {
+        read_sysfs_value(zone.join(format!("constraint_{}_{}", index, attribute))).ok()?.parse().ok()
+    };
+    for index in 0..4 {
+        let Some(constraint) =
+            read_sysfs_value(zone.join(format!("constraint_{}_name", index))).ok().and_then(|n| Constraint::from_name(&n))
+        else {
+            continue;
+        };
+        let Some(power_uw) = number(index, "power_limit_uw") else {
+            continue;
+        };
+        let limit = PowerLimit {
+            constraint,
+            path: zone.join(format!("constraint_{}_power_limit_uw", index)),
+            power_uw,
+            window_us: number(index, "time_window_us"),
+            // Firmware reports 0 when it sets no bound
+            max_uw: number(index, "max_power_uw").filter(|&uw| uw > 0),
+        };
+        match constraint {
+            Constraint::LongTerm => limits.long_term = Some(limit),
+            Constraint::ShortTerm => limits.short_term = Some(limit),
+        }
+    }
+    limits
+}
+
+/// Power limits of the first package below `sysfs` (normally `/sys`)
+pub fn read_limits(sysfs: &Path) -> PowerLimits {
+    package_zones(sysfs).first().map(|zone| zone_limits(zone)).unwrap_or_default()
+}
+
+/// Current power limits of this machine
+pub fn limits() -> PowerLimits {
+    read_limits(Path::new(SYSFS_ROOT))
+}
+
+/// Time window the hardware can hold closest to `seconds`, in microseconds:
+/// 2^y * (1 + x/4) time units
+fn window_us(seconds: u32) -> u64 {
+    let wanted = seconds as u64 * TIME_UNITS_PER_SEC * 4;
+    let units = (0..32u32)
+        .flat_map(|y| (4..8u64).map(move |x| (1u64 << y) * x))
+        .min_by_key(|&quarters| quarters.abs_diff(wanted))
+        .unwrap_or(wanted);
+    units * 1_000_000 / (TIME_UNITS_PER_SEC * 4)
+}
+
+/// Writes setting `request` on every package below `sysfs`
+/// Limits are capped to the firmware maximum and PL2 kept at or above PL1
+pub fn limit_writes(sysfs: &Path, request: &LimitRequest) -> io::Result<Vec<SysfsWrite>> {
+    let zones = package_zones(sysfs);
+    if zones.is_empty() {
+        return Err(io::Error::new(ErrorKind::NotFound, "No RAPL power limits found"));
+    }
+    let uw = |watts: u32, limit: &PowerLimit| {
+        let wanted = watts.max(MIN_POWER_LIMIT_W) as u64 * 1_000_000;
+        limit.max_uw.map_or(wanted, |max| wanted.min(max))
+    };
+    let mut writes = Vec::new();
+    for zone in zones {
+        let limits = zone_limits(&zone);
+        if let (Some(watts), Some(limit)) = (request.long_term_w, &limits.long_term) {
+            writes.push(SysfsWrite::new(&limit.path, uw(watts, limit)));
+            if let (Some(seconds), Some(_)) = (request.window_s, limit.window_us) {
+                writes.push(SysfsWrite::new(limit.window_path(), window_us(seconds.max(1))));
+            }
+        }
+        if let (Some(watts), Some(limit)) = (request.short_term_w, &limits.short_term) {
+            // PL2 never below PL1, the requested one or else the one in force
+            let long_term_w = request
+                .long_term_w
+                .or_else(|| limits.long_term.as_ref().map(|limit| limit.power_uw.div_ceil(1_000_000) as u32));
+            let watts = watts.max(long_term_w.unwrap_or(0));
+            writes.push(SysfsWrite::new(&limit.path, uw(watts, limit)));
+        }
+    }
+    Ok(writes)
+}
+
+/// Power since the previous call. The first call only primes the counters,
+/// so callers without a previous reading should call it twice, apart
+pub fn read_power() -> PowerReading {
+    let current = sample(Path::new(SYSFS_ROOT));
+    let mut last = LAST_SAMPLE.lock().unwrap_or_else(PoisonError::into_inner);
+    let reading = match last.as_ref() {
+        Some(previous) => power_between(previous, &current),
+        None => power_between(&current, &current),
+    };
+    *last = Some(current);
+    reading
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+    use std::time::Duration;
+
+    fn rapl(root: &Path, zone: &str, name: &str, energy: u64) {
+        put(root, &format!("class/powercap/{}/name", zone), name);
+        put(root, &format!("class/powercap/{}/energy_uj", zone), &energy.to_string());
+        put(root, &format!("class/powercap/{}/max_energy_range_uj", zone), "262143328850");
+    }
+
+    #[test]
+    fn test_energy_wraparound() {
+        assert_eq!(energy_delta(1_000, 4_000, 10_000), 3_000);
+        assert_eq!(energy_delta(9_000, 500, 10_000), 1_500);
+    }
+
+    #[test]
+    fn test_rapl_power() {
+        let root = tempfile::tempdir().unwrap();
+        rapl(root.path(), "intel-rapl:0", "package-0", 1_000_000);
+        rapl(root.path(), "intel-rapl:0:0", "core", 500_000);
+        rapl(root.path(), "intel-rapl:0:1", "uncore", 100_000);
+        rapl(root.path(), "intel-rapl:0:2", "dram", 0);
+        rapl(root.path(), "intel-rapl-mmio:0", "package-0", 0);
+        let mut before = sample(root.path());
+        assert_eq!(before.counters.len(), 3);
+
+        rapl(root.path(), "intel-rapl:0", "package-0", 13_000_000);
+        rapl(root.path(), "intel-rapl:0:0", "core", 8_500_000);
+        rapl(root.path(), "intel-rapl:0:1", "uncore", 1_100_000);
+        let after = sample(root.path());
+        before.at = after.at - Duration::from_secs(2);
+
+        let power = power_between(&before, &after);
+        assert_eq!(power.package, Some(6.0));
+        assert_eq!(power.core, Some(4.0));
+        assert_eq!(power.uncore, Some(0.5));
+        assert_eq!(power.total(), Some(6.0));
+        assert_eq!(power.breakdown(), "Package 6.0 W, Core 4.0 W, Uncore 0.5 W");
+        assert!(!power.restricted);
+
+        // Too short an interval gives no figure rather than a wild one
+        assert_eq!(power_between(&after, &after).total(), None);
+    }
+
+    #[test]
+    fn test_amd_hwmon_power() {
+        let root = tempfile::tempdir().unwrap();
+        put(root.path(), "class/hwmon/hwmon3/name", "amd_energy");
+        put(root.path(), "class/hwmon/hwmon3/energy1_input", "2000000");
+        put(root.path(), "class/hwmon/hwmon3/energy1_label", "Ecore000");
+        put(root.path(), "class/hwmon/hwmon3/energy2_input", "1000000");
+        put(root.path(), "class/hwmon/hwmon3/energy2_label", "Ecore001");
+        put(root.path(), "class/hwmon/hwmon3/energy3_input", "9000000");
+        put(root.path(), "class/hwmon/hwmon3/energy3_label", "Esocket0");
+        let mut before = sample(root.path());
+        put(root.path(), "class/hwmon/hwmon3/energy1_input", "3000000");
+        put(root.path(), "class/hwmon/hwmon3/energy2_input", "2000000");
+        put(root.path(), "class/hwmon/hwmon3/energy3_input", "14000000");
+        let after = sample(root.path());
+        before.at = after.at - Duration::from_secs(1);
+        let power = power_between(&before, &after);
+        assert_eq!(power.core, Some(2.0));
+        assert_eq!(power.package, Some(5.0));
+
+        // zenpower reports watts directly
+        let root = tempfile::tempdir().unwrap();
+        put(root.path(), "class/hwmon/hwmon1/name", "zenpower");
+        put(root.path(), "class/hwmon/hwmon1/power1_input", "12500000");
+        put(root.path(), "class/hwmon/hwmon1/power1_label", "SVI2_P_Core");
+        put(root.path(), "class/hwmon/hwmon1/power2_input", "3000000");
+        put(root.path(), "class/hwmon/hwmon1/power2_label", "SVI2_P_SoC");
+        let now = sample(root.path());
+        let power = power_between(&now, &now);
+        assert_eq!(power.core, Some(12.5));
+        assert_eq!(power.uncore, Some(3.0));
+        assert_eq!(power.total(), Some(12.5));
+    }
+
+    fn constraint(root: &Path, zone: &str, index: u32, name: &str, power_uw: u64, max_uw: u64) {
+        let base = format!("class/powercap/{}/constraint_{}", zone, index);
+        put(root, &format!("{}_name", base), name);
+        put(root, &format!("{}_power_limit_uw", base), &power_uw.to_string());
+        put(root, &format!("{}_max_power_uw", base), &max_uw.to_string());
+    }
+
+    #[test]
+    fn test_power_limits() {
+        let root = tempfile::tempdir().unwrap();
+        assert!(read_limits(root.path()).is_empty());
+        assert!(limit_writes(root.path(), &LimitRequest::default()).is_err());
+
+        rapl(root.path(), "intel-rapl:0", "package-0", 0);
+        constraint(root.path(), "intel-rapl:0", 0, "long_term", 28_000_000, 45_000_000);
+        put(root.path(), "class/powercap/intel-rapl:0/constraint_0_time_window_us", "27983872");
+        constraint(root.path(), "intel-rapl:0", 1, "short_term", 64_000_000, 0);
+        rapl(root.path(), "intel-rapl:0:0", "core", 0);
+        constraint(root.path(), "intel-rapl:0:0", 0, "long_term", 0, 0);
+        rapl(root.path(), "intel-rapl-mmio:0", "package-0", 0);
+        constraint(root.path(), "intel-rapl-mmio:0", 0, "long_term", 28_000_000, 0);
+
+        let limits = read_limits(root.path());
+        let long_term = limits.long_term.clone().unwrap();
+        assert_eq!(long_term.watts(), 28.0);
+        assert_eq!(long_term.max_watts(), Some(45.0));
+        assert_eq!(limits.short_term.as_ref().unwrap().max_uw, None);
+        assert_eq!(limits.summary(), "PL1 28 W (28 s), PL2 64 W");
+
+        let zone = root.path().join("class/powercap/intel-rapl:0");
+        let request = LimitRequest { long_term_w: Some(12), short_term_w: Some(10), window_s: Some(9) };
+        assert_eq!(
+            limit_writes(root.path(), &request).unwrap(),
+            vec![
+                SysfsWrite::new(zone.join("constraint_0_power_limit_uw"), "12000000"),
+                // 9 s is not representable; 8 s is the nearest window
+                SysfsWrite::new(zone.join("constraint_0_time_window_us"), "8000000"),
+                // PL2 never below PL1
+                SysfsWrite::new(zone.join("constraint_1_power_limit_uw"), "12000000"),
+            ]
+        );
+        // Without a new PL1, PL2 stays at or above the current one
+        let request = LimitRequest { short_term_w: Some(10), ..Default::default() };
+        assert_eq!(
+            limit_writes(root.path(), &request).unwrap(),
+            vec![SysfsWrite::new(zone.join("constraint_1_power_limit_uw"), "28000000")]
+        );
+
+        // Capped to the firmware maximum, floored to a usable minimum
+        let request = LimitRequest { long_term_w: Some(100), ..Default::default() };
+        assert_eq!(limit_writes(root.path(), &request).unwrap()[0].value, "45000000");
+        let request = LimitRequest { long_term_w: Some(0), ..Default::default() };
+        assert_eq!(limit_writes(root.path(), &request).unwrap()[0].value, "3000000");
+
+        // Thermal control scales PL1 against the firmware maximum
+        assert_eq!(limits.full_scale_watts(), Some(45.0));
+        assert_eq!(limits.long_term_pct(), Some(62));
+        assert_eq!(limits.long_term_watts_at(50), Some(23));
+        assert_eq!(limits.long_term_watts_at(1), Some(MIN_POWER_LIMIT_W));
+        let no_max = PowerLimits { long_term: Some(PowerLimit { max_uw: None, ..long_term }), ..limits };
+        assert_eq!(no_max.full_scale_watts(), Some(64.0));
+    }
+
+    #[test]
+    fn test_window_rounding() {
+        assert_eq!(window_us(28), 28_000_000);
+        assert_eq!(window_us(1), 1_000_000);
+        assert_eq!(window_us(10), 10_000_000);
+    }
+}
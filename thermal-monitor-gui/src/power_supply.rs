@@ -0,0 +1,301 @@
+//! AC adapter and battery status
+//!
+//! Read from `/sys/class/power_supply/*`:
+//! - `type = Mains` (or `USB` for USB-C chargers): `online` is 1 while
+//!   plugged in
+//! - `type = Battery`: `capacity` in percent, `status`, and either energy
+//!   (`energy_now`, `energy_full` in µWh, `power_now` in µW) or charge
+//!   (`charge_now`, `charge_full` in µAh, `current_now` in µA, with
+//!   `voltage_now` in µV) depending on the firmware
+//!
+//! Batteries of peripherals (`scope = Device`, e.g. a wireless mouse) are
+//! ignored. Several system batteries are combined.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::i18n::{tr, tr_args};
+use crate::platform_profile::SYSFS_ROOT;
+use crate::system::read_sysfs_value;
+
+/// Charging state as the battery reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    Full,
+    /// Plugged in but held below full, e.g. by a charge threshold
+    NotCharging,
+}
+
+impl BatteryStatus {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Charging" => Some(BatteryStatus::Charging),
+            "Discharging" => Some(BatteryStatus::Discharging),
+            "Full" => Some(BatteryStatus::Full),
+            "Not charging" => Some(BatteryStatus::NotCharging),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BatteryStatus::Charging => tr("battery-charging"),
+            BatteryStatus::Discharging => tr("battery-discharging"),
+            BatteryStatus::Full => tr("battery-full"),
+            BatteryStatus::NotCharging => tr("battery-not-charging"),
+        }
+    }
+}
+
+/// Where the machine draws power from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+impl PowerSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerSource::Ac => tr("source-ac"),
+            PowerSource::Battery => tr("source-battery"),
+        }
+    }
+}
+
+/// AC and battery snapshot; `None` where the hardware does not say
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PowerSupply {
+    /// `None` without an AC adapter entry (most desktops)
+    pub ac_online: Option<bool>,
+    /// `None` without a system battery
+    pub battery_pct: Option<u8>,
+    pub status: Option<BatteryStatus>,
+    /// Charge or discharge rate in watts, always positive
+    pub rate_w: Option<f32>,
+    /// Until empty while discharging, until full while charging
+    pub time_remaining: Option<Duration>,
+}
+
+impl PowerSupply {
+    pub fn has_battery(&self) -> bool {
+        self.battery_pct.is_some()
+    }
+
+    /// Current source; a discharging battery counts as unplugged where
+    /// there is no adapter entry
+    pub fn source(&self) -> Option<PowerSource> {
+        match (self.ac_online, self.status) {
+            (Some(true), _) => Some(PowerSource::Ac),
+            (Some(false), _) | (None, Some(BatteryStatus::Discharging)) => Some(PowerSource::Battery),
+            (None, Some(_)) => Some(PowerSource::Ac),
+            (None, None) => None,
+        }
+    }
+
+    /// "82%, Discharging, 9.5 W, 3 h 12 min left"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(pct) = self.battery_pct {
+            parts.push(format!("{}%", pct));
+        }
+        if let Some(status) = self.status {
+            parts.push(status.label().to_string());
+        }
+        if let Some(watts) = self.rate_w.filter(|&w| w > 0.0) {
+            parts.push(format!("{:.1} W", watts));
+        }
+        if let Some(left) = self.time_remaining {
+            let minutes = left.as_secs() / 60;
+            let time = format!("{} h {:02} min", minutes / 60, minutes % 60);
+            let key = match self.status {
+                Some(BatteryStatus::Charging) => "battery-until-full",
+                _ => "battery-left",
+            };
+            parts.push(tr_args(key, &[("time", &time)]));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Battery figures in µWh and µW, converted from charge where needed
+#[derive(Debug, Clone, Copy, Default)]
+struct Battery {
+    now_uwh: f64,
+    full_uwh: f64,
+    rate_uw: f64,
+}
+
+fn number(dir: &Path, attribute: &str) -> Option<f64> {
+    read_sysfs_value(dir.join(attribute)).ok()?.parse().ok()
+}
+
+/// Energy figures of one battery; charge figures are scaled by the voltage
+fn battery_energy(dir: &Path) -> Option<Battery> {
+    if let (Some(now_uwh), Some(full_uwh)) = (number(dir, "energy_now"), number(dir, "energy_full")) {
+        return Some(Battery { now_uwh, full_uwh, rate_uw: number(dir, "power_now").unwrap_or(0.0).abs() });
+    }
+    let volts = number(dir, "voltage_now").or_else(|| number(dir, "voltage_min_design"))? / 1e6;
+    Some(Battery {
+        now_uwh: number(dir, "charge_now")? * volts,
+        full_uwh: number(dir, "charge_full")? * volts,
+        // Some firmware reports a negative current while discharging
+        rate_uw: number(dir, "current_now").unwrap_or(0.0).abs() * volts,
+    })
+}
+
+/// AC and battery status below `sysfs` (normally `/sys`)
+pub fn read_supply(sysfs: &Path) -> PowerSupply {
+    let mut supply = PowerSupply::default();
+    let mut total = Battery::default();
+    let mut capacities = Vec::new();
+    let mut entries: Vec<_> = fs::read_dir(sysfs.join("class/power_supply"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    for dir in entries {
+        if read_sysfs_value(dir.join("scope")).is_ok_and(|scope| scope == "Device") {
+            continue;
+        }
+        match read_sysfs_value(dir.join("type")).unwrap_or_default().as_str() {
+            "Mains" | "USB" => {
+                if let Ok(online) = read_sysfs_value(dir.join("online")) {
+                    supply.ac_online = Some(supply.ac_online.unwrap_or(false) || online == "1");
+                }
+            }
+            "Battery" => {
+                if read_sysfs_value(dir.join("present")).is_ok_and(|present| present == "0") {
+                    continue;
+                }
+                let Some(capacity) = number(&dir, "capacity") else {
+                    continue;
+                };
+                capacities.push(capacity);
+                let status = read_sysfs_value(dir.join("status")).ok().and_then(|s| BatteryStatus::parse(&s));
+                // Any battery charging or discharging decides the combined state
+                if supply.status.is_none() || matches!(status, Some(BatteryStatus::Charging | BatteryStatus::Discharging)) {
+                    supply.status = status.or(supply.status);
+                }
+                if let Some(battery) = battery_energy(&dir) {
+                    total.now_uwh += battery.now_uwh;
+                    total.full_uwh += battery.full_uwh;
+                    total.rate_uw += battery.rate_uw;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if capacities.is_empty() {
+        return supply;
+    }
+    // Several batteries are weighted by their size
+    let pct = match capacities.as_slice() {
+        [only] => *only,
+        _ if total.full_uwh > 0.0 => total.now_uwh / total.full_uwh * 100.0,
+        _ => capacities.iter().sum::<f64>() / capacities.len() as f64,
+    };
+    supply.battery_pct = Some(pct.round().clamp(0.0, 100.0) as u8);
+    if total.rate_uw > 0.0 {
+        supply.rate_w = Some((total.rate_uw / 1e6) as f32);
+        let hours = match supply.status {
+            Some(BatteryStatus::Discharging) => Some(total.now_uwh / total.rate_uw),
+            Some(BatteryStatus::Charging) => Some((total.full_uwh - total.now_uwh).max(0.0) / total.rate_uw),
+            _ => None,
+        };
+        supply.time_remaining = hours.map(|h| Duration::from_secs((h * 3600.0) as u64));
+    }
+    supply
+}
+
+/// AC and battery status of this machine
+pub fn read() -> PowerSupply {
+    read_supply(Path::new(SYSFS_ROOT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+
+    fn supply(root: &Path, name: &str, attributes: &[(&str, &str)]) {
+        for (attribute, value) in attributes {
+            put(root, &format!("class/power_supply/{}/{}", name, attribute), value);
+        }
+    }
+
+    #[test]
+    fn test_energy_battery_discharging() {
+        let root = tempfile::tempdir().unwrap();
+        assert_eq!(read_supply(root.path()), PowerSupply::default());
+        assert_eq!(PowerSupply::default().source(), None);
+
+        supply(root.path(), "AC", &[("type", "Mains"), ("online", "0")]);
+        supply(
+            root.path(),
+            "BAT0",
+            &[
+                ("type", "Battery"),
+                ("present", "1"),
+                ("status", "Discharging"),
+                ("capacity", "81"),
+                ("energy_now", "40000000"),
+                ("energy_full", "50000000"),
+                ("power_now", "10000000"),
+            ],
+        );
+        // A mouse battery does not count
+        supply(root.path(), "hidpp_battery_0", &[("type", "Battery"), ("scope", "Device"), ("capacity", "5")]);
+
+        let status = read_supply(root.path());
+        assert_eq!(status.ac_online, Some(false));
+        assert_eq!(status.battery_pct, Some(81));
+        assert_eq!(status.rate_w, Some(10.0));
+        assert_eq!(status.time_remaining, Some(Duration::from_secs(4 * 3600)));
+        assert_eq!(status.source(), Some(PowerSource::Battery));
+        assert_eq!(status.summary(), "81%, Discharging, 10.0 W, 4 h 00 min left");
+
+        supply(root.path(), "AC", &[("online", "1")]);
+        supply(root.path(), "BAT0", &[("status", "Charging")]);
+        let status = read_supply(root.path());
+        assert_eq!(status.source(), Some(PowerSource::Ac));
+        assert_eq!(status.time_remaining, Some(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_charge_battery_without_adapter() {
+        let root = tempfile::tempdir().unwrap();
+        supply(
+            root.path(),
+            "BAT1",
+            &[
+                ("type", "Battery"),
+                ("status", "Discharging"),
+                ("capacity", "50"),
+                ("charge_now", "2000000"),
+                ("charge_full", "4000000"),
+                ("current_now", "-1000000"),
+                ("voltage_now", "12000000"),
+            ],
+        );
+        let status = read_supply(root.path());
+        assert_eq!(status.ac_online, None);
+        assert_eq!(status.battery_pct, Some(50));
+        assert_eq!(status.rate_w, Some(12.0));
+        assert_eq!(status.time_remaining, Some(Duration::from_secs(2 * 3600)));
+        assert_eq!(status.source(), Some(PowerSource::Battery));
+
+        supply(root.path(), "BAT1", &[("status", "Full"), ("current_now", "0")]);
+        let status = read_supply(root.path());
+        assert_eq!(status.source(), Some(PowerSource::Ac));
+        assert_eq!(status.time_remaining, None);
+        assert_eq!(status.summary(), "50%, Full");
+    }
+}
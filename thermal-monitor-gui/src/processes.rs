@@ -0,0 +1,374 @@
+//! Running processes, read from `/proc/<pid>/comm` and `/proc/<pid>/cgroup`
+//!
+//! The kernel truncates `comm` to 15 bytes, so longer names given by the
+//! user match on that prefix. Cgroup paths identify apps whose processes
+//! have generic names, e.g. Flatpaks and desktop-launched apps
+//! (`app-flatpak-us.zoom.Zoom-1234.scope`).
+//!
+//! CPU time comes from `/proc/<pid>/stat` (`utime` + `stime`, in clock
+//! ticks) and `/proc/stat` (all CPUs). [`HeatTracker`] turns two snapshots
+//! into the processes that used the most CPU in between; their share of the
+//! busy time, times the package power, gives a rough per-process power.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Command;
+
+use crate::fan::PROCFS_ROOT;
+
+/// Longest name the kernel keeps in `comm`
+const COMM_LEN: usize = 15;
+
+/// Sorted, deduplicated values of a per-process file below `procfs`
+fn collect(procfs: &Path, file: &str, value: fn(&str) -> Option<&str>) -> Vec<String> {
+    let mut values: Vec<String> = fs::read_dir(procfs)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_str().is_some_and(|pid| pid.chars().all(|c| c.is_ascii_digit())))
+        .filter_map(|entry| fs::read(entry.path().join(file)).ok())
+        .filter_map(|contents| value(&String::from_utf8_lossy(&contents)).map(String::from))
+        .filter(|value| !value.is_empty())
+        .collect();
+    values.sort();
+    values.dedup();
+    values
+}
+
+/// Sorted, deduplicated names of the processes below `procfs`; the kernel
+/// truncates bytes, so a character it cut in half is dropped
+pub fn names(procfs: &Path) -> Vec<String> {
+    collect(procfs, "comm", |comm| Some(comm.trim_end().trim_end_matches(char::REPLACEMENT_CHARACTER)))
+}
+
+/// Sorted, deduplicated cgroup paths of the processes below `procfs`;
+/// the unified hierarchy, or systemd's on cgroup v1
+pub fn cgroups(procfs: &Path) -> Vec<String> {
+    collect(procfs, "cgroup", |contents| {
+        contents.lines().find_map(|line| line.strip_prefix("0::").or_else(|| line.split_once(":name=systemd:").map(|(_, path)| path)))
+    })
+}
+
+/// Names of the processes running on this machine
+pub fn running() -> Vec<String> {
+    names(Path::new(PROCFS_ROOT))
+}
+
+/// Cgroup paths of the processes running on this machine
+pub fn running_cgroups() -> Vec<String> {
+    cgroups(Path::new(PROCFS_ROOT))
+}
+
+/// Whether `wanted` is among `names`, allowing for `comm` truncation
+pub fn is_running(names: &[String], wanted: &str) -> bool {
+    let end = (0..=COMM_LEN.min(wanted.len())).rev().find(|&i| wanted.is_char_boundary(i)).unwrap_or(0);
+    let wanted = &wanted[..end];
+    names.binary_search_by(|name| name.as_str().cmp(wanted)).is_ok()
+}
+
+/// CPU time and scheduling state of one process
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessStat {
+    pub pid: u32,
+    pub name: String,
+    /// User plus system time in clock ticks
+    pub ticks: u64,
+    pub nice: i32,
+    /// Stopped by a signal (state `T`)
+    pub stopped: bool,
+    /// Clock ticks after boot the process started at; with the pid it
+    /// tells a process from a later one that reuses the pid
+    pub start_time: u64,
+}
+
+impl ProcessStat {
+    /// Parse `/proc/<pid>/stat`; the name is in parentheses and may itself
+    /// contain spaces and parentheses
+    fn parse(pid: u32, line: &str) -> Option<Self> {
+        let (head, tail) = line.rsplit_once(')')?;
+        let name = head.split_once('(')?.1.to_string();
+        // Fields from the state on: state is 0, utime 11, stime 12, nice 16,
+        // starttime 19
+        let fields: Vec<&str> = tail.split_whitespace().collect();
+        let number = |index: usize| fields.get(index)?.parse::<u64>().ok();
+        Some(Self {
+            pid,
+            name,
+            ticks: number(11)? + number(12)?,
+            nice: fields.get(16)?.parse().ok()?,
+            stopped: *fields.first()? == "T",
+            start_time: number(19)?,
+        })
+    }
+
+    /// Read `/proc/<pid>/stat` below `procfs`
+    fn read(procfs: &Path, pid: u32) -> Option<Self> {
+        Self::parse(pid, &fs::read_to_string(procfs.join(pid.to_string()).join("stat")).ok()?)
+    }
+
+    /// Whether `other` is the same process, not a later one with its pid
+    fn same_process(&self, other: &ProcessStat) -> bool {
+        self.pid == other.pid && self.start_time == other.start_time && self.name == other.name
+    }
+}
+
+/// CPU time of the whole machine and of every process at one moment
+#[derive(Debug, Clone, Default)]
+pub struct CpuSnapshot {
+    /// All CPU time in clock ticks, summed over CPUs
+    total_ticks: u64,
+    /// Time not idle or waiting for I/O
+    busy_ticks: u64,
+    cpus: usize,
+    processes: HashMap<u32, ProcessStat>,
+}
+
+/// Current CPU times below `procfs`; `None` without `/proc/stat`
+pub fn snapshot(procfs: &Path) -> Option<CpuSnapshot> {
+    let stat = fs::read_to_string(procfs.join("stat")).ok()?;
+    let totals: Vec<u64> = stat.lines().next()?.strip_prefix("cpu ")?.split_whitespace().filter_map(|v| v.parse().ok()).collect();
+    // user nice system idle iowait irq softirq steal; guest time is already in user
+    let total_ticks: u64 = totals.iter().take(8).sum();
+    let idle_ticks = totals.get(3).copied().unwrap_or(0) + totals.get(4).copied().unwrap_or(0);
+    let cpus = stat.lines().filter(|line| line.starts_with("cpu") && !line.starts_with("cpu ")).count();
+
+    let processes = fs::read_dir(procfs)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| ProcessStat::read(procfs, entry.file_name().to_str()?.parse().ok()?))
+        .map(|process| (process.pid, process))
+        .collect();
+    Some(CpuSnapshot { total_ticks, busy_ticks: total_ticks.saturating_sub(idle_ticks), cpus: cpus.max(1), processes })
+}
+
+/// A process ranked by its recent CPU use
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contributor {
+    pub process: ProcessStat,
+    /// Percent of one CPU, so several busy threads exceed 100
+    pub cpu_pct: f32,
+    /// Share of the package power, estimated from the share of busy time
+    pub watts: Option<f32>,
+}
+
+/// Ranks processes by CPU time used between successive snapshots
+#[derive(Debug, Default)]
+pub struct HeatTracker {
+    previous: Option<CpuSnapshot>,
+}
+
+impl HeatTracker {
+    /// Record `next` and return the `count` processes that used the most
+    /// CPU since the previous snapshot; empty on the first call
+    pub fn update(&mut self, next: CpuSnapshot, package_w: Option<f32>, count: usize) -> Vec<Contributor> {
+        let Some(previous) = self.previous.replace(next) else {
+            return Vec::new();
+        };
+        let next = self.previous.as_ref().expect("just stored");
+        let elapsed = next.total_ticks.saturating_sub(previous.total_ticks);
+        let busy = next.busy_ticks.saturating_sub(previous.busy_ticks);
+        if elapsed == 0 {
+            return Vec::new();
+        }
+        let per_cpu = elapsed as f32 / next.cpus as f32;
+
+        let mut ranked: Vec<(u64, &ProcessStat)> = next
+            .processes
+            .values()
+            .map(|process| {
+                // A new process, or a reused pid, used all of its time since
+                let before = previous.processes.get(&process.pid).filter(|p| p.same_process(process)).map_or(0, |p| p.ticks);
+                (process.ticks.saturating_sub(before), process)
+            })
+            .filter(|(used, _)| *used > 0)
+            .collect();
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.pid.cmp(&b.1.pid)));
+        ranked
+            .into_iter()
+            .take(count)
+            .map(|(used, process)| Contributor {
+                process: process.clone(),
+                cpu_pct: used as f32 / per_cpu * 100.0,
+                watts: package_w.filter(|_| busy > 0).map(|w| w * (used as f32 / busy as f32).min(1.0)),
+            })
+            .collect()
+    }
+
+    /// Forget the last snapshot, e.g. while the panel is hidden
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+/// Current CPU times of this machine
+pub fn current_snapshot() -> Option<CpuSnapshot> {
+    snapshot(Path::new(PROCFS_ROOT))
+}
+
+/// One-click actions on a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessAction {
+    /// Lower the priority by [`RENICE_STEP`]
+    Renice,
+    Stop,
+    Continue,
+}
+
+/// Nice increment applied by [`ProcessAction::Renice`]
+pub const RENICE_STEP: i32 = 5;
+
+/// Lowest priority a process can have
+pub const MAX_NICE: i32 = 19;
+
+/// Nice value [`ProcessAction::Renice`] sets; `None` at the lowest priority
+pub fn reniced(nice: i32) -> Option<i32> {
+    (nice < MAX_NICE).then(|| (nice + RENICE_STEP).min(MAX_NICE))
+}
+
+/// Whether `pid` below `procfs` belongs to the same user as this program,
+/// leaving out init and this program itself
+fn owned_by_user(procfs: &Path, pid: u32) -> bool {
+    let owner = |dir: &str| fs::metadata(procfs.join(dir)).map(|meta| meta.uid()).ok();
+    pid > 1 && pid != std::process::id() && owner(&pid.to_string()).is_some_and(|uid| Some(uid) == owner("self"))
+}
+
+/// Whether this user may renice, stop or continue `pid`
+pub fn can_control(pid: u32) -> bool {
+    owned_by_user(Path::new(PROCFS_ROOT), pid)
+}
+
+/// Whether `process` is still running, and not replaced by a new one with
+/// its pid
+pub fn exists(process: &ProcessStat) -> bool {
+    ProcessStat::read(Path::new(PROCFS_ROOT), process.pid).is_some_and(|current| current.same_process(process))
+}
+
+/// `process` as it is now below `procfs`, if it is still the same one and
+/// belongs to this user
+fn current(procfs: &Path, process: &ProcessStat) -> io::Result<ProcessStat> {
+    let current = ProcessStat::read(procfs, process.pid)
+        .filter(|current| current.same_process(process))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Process {} ({}) has exited", process.name, process.pid)))?;
+    if !owned_by_user(procfs, process.pid) {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("Process {} belongs to another user", process.pid)));
+    }
+    Ok(current)
+}
+
+/// Renice, stop or continue `process`; refuses processes of other users and
+/// pids taken over by another process since `process` was read
+pub fn perform(process: &ProcessStat, action: ProcessAction) -> io::Result<()> {
+    let current = current(Path::new(PROCFS_ROOT), process)?;
+    let pid = current.pid.to_string();
+    let output = match action {
+        ProcessAction::Renice => {
+            let Some(nice) = reniced(current.nice) else {
+                return Ok(());
+            };
+            // A bare number is an absolute priority in every renice version
+            Command::new("renice").args([&nice.to_string(), "-p", &pid]).output()?
+        }
+        ProcessAction::Stop => Command::new("kill").args(["-STOP", &pid]).output()?,
+        ProcessAction::Continue => Command::new("kill").args(["-CONT", &pid]).output()?,
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("Process {}: {}", pid, stderr.trim())));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+
+    #[test]
+    fn test_process_names() {
+        let root = tempfile::tempdir().unwrap();
+        put(root.path(), "1/comm", "systemd\n");
+        put(root.path(), "812/comm", "rustc\n");
+        put(root.path(), "813/comm", "rustc\n");
+        put(root.path(), "900/comm", "gnome-control-c\n");
+        put(root.path(), "self/comm", "ignored\n");
+        let names = names(root.path());
+        assert_eq!(names, ["gnome-control-c", "rustc", "systemd"]);
+        assert!(is_running(&names, "rustc"));
+        assert!(is_running(&names, "gnome-control-center"));
+        assert!(!is_running(&names, "cc1plus"));
+
+        // Byte 15 falls inside "é", which the kernel cuts in half
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(root.path().join("7")).unwrap();
+        fs::write(root.path().join("7/comm"), &"Convertisseur-édition".as_bytes()[..COMM_LEN]).unwrap();
+        let names = super::names(root.path());
+        assert_eq!(names, ["Convertisseur-"]);
+        assert!(is_running(&names, "Convertisseur-édition"));
+    }
+
+    #[test]
+    fn test_process_cgroups() {
+        let root = tempfile::tempdir().unwrap();
+        let zoom = "/user.slice/user-1000.slice/user@1000.service/app.slice/app-flatpak-us.zoom.Zoom-4242.scope";
+        put(root.path(), "4242/cgroup", &format!("0::{}\n", zoom));
+        put(root.path(), "4243/cgroup", &format!("0::{}\n", zoom));
+        put(root.path(), "77/cgroup", "12:cpu:/\n1:name=systemd:/system.slice/cron.service\n");
+        assert_eq!(cgroups(root.path()), ["/system.slice/cron.service", zoom]);
+    }
+
+    #[test]
+    fn test_heat_contributors() {
+        let root = tempfile::tempdir().unwrap();
+        let stat = |total: [u64; 3]| format!("cpu  {} 0 {} {} 0 0 0 0 0 0\ncpu0 0\ncpu1 0\n", total[0], total[1], total[2]);
+        let process = |pid: u32, name: &str, state: &str, utime: u64| {
+            put(root.path(), &format!("{}/stat", pid), &format!("{} ({}) {} 1 1 1 0 -1 0 0 0 0 0 {} 0 0 0 20 0 1 0 100\n", pid, name, state, utime));
+        };
+        put(root.path(), "stat", &stat([100, 100, 100]));
+        process(10, "cc1plus", "R", 50);
+        process(11, "Web Content (x)", "S", 10);
+        process(12, "idle", "S", 5);
+
+        let mut tracker = HeatTracker::default();
+        assert!(tracker.update(snapshot(root.path()).unwrap(), Some(20.0), 5).is_empty());
+
+        // 200 ticks over two CPUs pass, 100 of them busy
+        put(root.path(), "stat", &stat([190, 110, 200]));
+        process(10, "cc1plus", "R", 130);
+        process(11, "Web Content (x)", "T", 30);
+        process(13, "rustc", "R", 0);
+        let top = tracker.update(snapshot(root.path()).unwrap(), Some(20.0), 5);
+        let summary: Vec<(u32, &str, f32, Option<f32>)> =
+            top.iter().map(|c| (c.process.pid, c.process.name.as_str(), c.cpu_pct, c.watts)).collect();
+        assert_eq!(summary, [(10, "cc1plus", 80.0, Some(16.0)), (11, "Web Content (x)", 20.0, Some(4.0))]);
+        assert!(top[1].process.stopped);
+        assert_eq!(top[0].process.nice, 0);
+        assert_eq!(top[0].process.start_time, 100);
+
+        tracker.reset();
+        assert!(tracker.update(snapshot(root.path()).unwrap(), None, 5).is_empty());
+
+        put(root.path(), "self/stat", "");
+        assert!(owned_by_user(root.path(), 10));
+        assert!(!owned_by_user(root.path(), 14));
+        assert!(!owned_by_user(root.path(), std::process::id()));
+
+        // The pid was reused by a later process
+        let cc1plus = top[0].process.clone();
+        assert_eq!(current(root.path(), &cc1plus).unwrap().ticks, 130);
+        put(root.path(), "10/stat", "10 (cc1plus) R 1 1 1 0 -1 0 0 0 0 0 1 0 0 0 20 0 1 0 900\n");
+        assert_eq!(current(root.path(), &cc1plus).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert!(current(root.path(), &ProcessStat { pid: 14, ..cc1plus }).is_err());
+    }
+
+    #[test]
+    fn test_reniced() {
+        assert_eq!(reniced(0), Some(5));
+        assert_eq!(reniced(-5), Some(0));
+        assert_eq!(reniced(17), Some(19));
+        assert_eq!(reniced(19), None);
+    }
+}
@@ -0,0 +1,688 @@
+//! Rules for automatic mode switching
+//!
+//! Rules are read from `rules.ini` in `/etc/thermal-monitor` and in the
+//! user config directory, user rules replacing system rules of the same
+//! name. Each `[rule NAME]` section lists conditions, all of which must
+//! hold, and actions:
+//!
+//! ```ini
+//! [settings]
+//! dry_run = on
+//!
+//! [rule hot-keyboard]
+//! priority = 10
+//! cooldown = 300
+//! keyboard_above = 40
+//! ac = off
+//! battery_below = 50
+//! time = 08:00-18:00
+//! process = rustc, cc1plus
+//! idle_below = 60
+//! mode = quiet
+//! target = 50
+//! fan_boost = on
+//! notify = Keyboard is getting hot, switching to Quiet
+//! ```
+//!
+//! Conditions: `cpu_above`/`cpu_below` and `keyboard_above`/`keyboard_below`
+//! (Celsius), `ac` (on/off), `battery_above`/`battery_below` (percent),
+//! `time` (local `HH:MM-HH:MM`, may wrap past midnight), `process` (any of
+//! the listed ones running) and `idle_above`/`idle_below` (seconds since the
+//! last input, as reported by logind). A condition whose input is unknown
+//! does not hold.
+//!
+//! Actions: `mode`, `target` (Celsius), `fan_boost` (on/off) and `notify`.
+//! A rule fires once when its conditions start to hold, then not again
+//! until they have stopped holding and `cooldown` seconds (default 60) have
+//! passed. When rules firing together set the same thing, the one with the
+//! highest `priority` wins. Actions are not undone when a rule stops
+//! holding; a second rule does that. With `dry_run` the actions are only
+//! logged. Edited files are picked up on the next sample.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::config::{self, Ini};
+use crate::i18n::{tr, tr_args};
+use crate::modes::{parse_bool, valid_name};
+use crate::power_supply::PowerSource;
+use crate::processes;
+use crate::session;
+use crate::system::ThermalState;
+
+/// System-wide rules file
+const SYSTEM_RULES_FILE: &str = "/etc/thermal-monitor/rules.ini";
+
+/// Rules file name inside the user config directory
+const RULES_FILE: &str = "rules.ini";
+
+/// Section prefix of a rule
+const RULE_PREFIX: &str = "rule ";
+
+/// Section holding engine settings
+const SETTINGS: &str = "settings";
+
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Entries kept in the log
+const LOG_CAPACITY: usize = 50;
+
+/// How long the local UTC offset is trusted, so DST changes are noticed
+const UTC_OFFSET_TTL: Duration = Duration::from_secs(600);
+
+/// Cached UTC offset in seconds and when it was read
+static UTC_OFFSET: Mutex<Option<(Instant, i64)>> = Mutex::new(None);
+
+/// Conditions of a rule; `None` and empty lists always hold
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conditions {
+    pub cpu_above: Option<f32>,
+    pub cpu_below: Option<f32>,
+    pub keyboard_above: Option<f32>,
+    pub keyboard_below: Option<f32>,
+    pub ac: Option<bool>,
+    pub battery_above: Option<u8>,
+    pub battery_below: Option<u8>,
+    /// Local time window in minutes after midnight, start to end
+    pub time: Option<(u16, u16)>,
+    /// Holds while any of these runs
+    pub processes: Vec<String>,
+    pub idle_above: Option<Duration>,
+    pub idle_below: Option<Duration>,
+}
+
+/// Inputs the conditions are checked against
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sample {
+    pub cpu_temp: f32,
+    pub keyboard_temp: f32,
+    pub source: Option<PowerSource>,
+    pub battery_pct: Option<u8>,
+    /// Local minutes after midnight
+    pub minute_of_day: Option<u16>,
+    /// Running process names, only read when a rule asks
+    pub processes: Vec<String>,
+    /// Time since the last input, only read when a rule asks
+    pub idle: Option<Duration>,
+}
+
+impl Conditions {
+    pub fn matches(&self, sample: &Sample) -> bool {
+        let above = |limit: Option<f32>, value: f32| limit.is_none_or(|limit| value > limit);
+        let below = |limit: Option<f32>, value: f32| limit.is_none_or(|limit| value < limit);
+        let battery = |limit: Option<u8>, check: fn(u8, u8) -> bool| {
+            limit.is_none_or(|limit| sample.battery_pct.is_some_and(|pct| check(pct, limit)))
+        };
+        let idle = |limit: Option<Duration>, check: fn(Duration, Duration) -> bool| {
+            limit.is_none_or(|limit| sample.idle.is_some_and(|idle| check(idle, limit)))
+        };
+        above(self.cpu_above, sample.cpu_temp)
+            && below(self.cpu_below, sample.cpu_temp)
+            && above(self.keyboard_above, sample.keyboard_temp)
+            && below(self.keyboard_below, sample.keyboard_temp)
+            && self.ac.is_none_or(|ac| sample.source == Some(if ac { PowerSource::Ac } else { PowerSource::Battery }))
+            && battery(self.battery_above, |pct, limit| pct > limit)
+            && battery(self.battery_below, |pct, limit| pct < limit)
+            && self.time.is_none_or(|(start, end)| sample.minute_of_day.is_some_and(|now| in_window(now, start, end)))
+            && (self.processes.is_empty() || self.processes.iter().any(|p| processes::is_running(&sample.processes, p)))
+            && idle(self.idle_above, |idle, limit| idle > limit)
+            && idle(self.idle_below, |idle, limit| idle < limit)
+    }
+}
+
+/// Whether `now` lies in `start..end`, wrapping past midnight
+fn in_window(now: u16, start: u16, end: u16) -> bool {
+    if start <= end {
+        (start..end).contains(&now)
+    } else {
+        now >= start || now < end
+    }
+}
+
+/// Something a rule does when it fires
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Mode(String),
+    Target(f32),
+    FanBoost(bool),
+    Notify(String),
+}
+
+impl Action {
+    /// Actions of the same kind override each other
+    fn same_kind(&self, other: &Action) -> bool {
+        !matches!(self, Action::Notify(_)) && std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// As written in the rules file: "mode = quiet"
+    pub fn describe(&self) -> String {
+        match self {
+            Action::Mode(name) => format!("mode = {}", name),
+            Action::Target(celsius) => format!("target = {}", celsius),
+            Action::FanBoost(on) => format!("fan_boost = {}", if *on { "on" } else { "off" }),
+            Action::Notify(text) => format!("notify = {}", text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub name: String,
+    /// Higher wins when rules firing together set the same thing
+    pub priority: i32,
+    pub cooldown: Duration,
+    pub conditions: Conditions,
+    pub actions: Vec<Action>,
+}
+
+impl Rule {
+    /// Parse a `[rule NAME]` section
+    pub fn from_ini(name: &str, ini: &Ini) -> Result<Self, String> {
+        if !valid_name(name) {
+            return Err(format!("Invalid rule name '{}'", name));
+        }
+        let section = format!("{}{}", RULE_PREFIX, name);
+        let get = |key: &str| ini.get(&section, key).filter(|v| !v.is_empty());
+        let number = |key: &str| -> Result<Option<f32>, String> {
+            get(key)
+                .map(|v| v.trim_end_matches(['C', '%', 's']).trim().parse::<f32>().map_err(|_| format!("{} must be a number, got '{}'", key, v)))
+                .transpose()
+        };
+        let pct = |key: &str| -> Result<Option<u8>, String> {
+            number(key)?.map(|v| if (0.0..=100.0).contains(&v) { Ok(v as u8) } else { Err(format!("{} must be 0-100", key)) }).transpose()
+        };
+        let seconds = |key: &str| -> Result<Option<Duration>, String> {
+            number(key)?.map(|v| Duration::try_from_secs_f32(v).map_err(|_| format!("{} must not be negative", key))).transpose()
+        };
+        let flag = |key: &str| -> Result<Option<bool>, String> {
+            get(key).map(|v| parse_bool(v).ok_or(format!("{} must be on or off", key))).transpose()
+        };
+
+        let conditions = Conditions {
+            cpu_above: number("cpu_above")?,
+            cpu_below: number("cpu_below")?,
+            keyboard_above: number("keyboard_above")?,
+            keyboard_below: number("keyboard_below")?,
+            ac: flag("ac")?,
+            battery_above: pct("battery_above")?,
+            battery_below: pct("battery_below")?,
+            time: get("time").map(|v| parse_window(v).ok_or(format!("time must be HH:MM-HH:MM, got '{}'", v))).transpose()?,
+            processes: get("process")
+                .map(|list| list.split(',').map(str::trim).filter(|p| !p.is_empty()).map(String::from).collect())
+                .unwrap_or_default(),
+            idle_above: seconds("idle_above")?,
+            idle_below: seconds("idle_below")?,
+        };
+
+        let mut actions = Vec::new();
+        if let Some(mode) = get("mode") {
+            if !valid_name(mode) {
+                return Err(format!("Invalid mode name '{}'", mode));
+            }
+            actions.push(Action::Mode(mode.to_string()));
+        }
+        if let Some(target) = number("target")? {
+            actions.push(Action::Target(target));
+        }
+        if let Some(boost) = flag("fan_boost")? {
+            actions.push(Action::FanBoost(boost));
+        }
+        if let Some(text) = get("notify") {
+            actions.push(Action::Notify(text.to_string()));
+        }
+        if actions.is_empty() {
+            return Err("Rule has no action".into());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            priority: get("priority").map(|v| v.parse().map_err(|_| format!("priority must be a whole number, got '{}'", v))).transpose()?.unwrap_or(0),
+            cooldown: seconds("cooldown")?.unwrap_or(DEFAULT_COOLDOWN),
+            conditions,
+            actions,
+        })
+    }
+
+    /// Actions in file syntax: "mode = quiet, fan_boost = on"
+    pub fn actions_text(&self) -> String {
+        let parts: Vec<String> = self.actions.iter().map(Action::describe).collect();
+        parts.join(", ")
+    }
+}
+
+/// "22:00-07:00" as minutes after midnight
+fn parse_window(value: &str) -> Option<(u16, u16)> {
+    let minutes = |text: &str| -> Option<u16> {
+        let (hours, minutes) = text.trim().split_once(':')?;
+        let (hours, minutes): (u16, u16) = (hours.parse().ok()?, minutes.parse().ok()?);
+        (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+    };
+    let (start, end) = value.split_once('-')?;
+    Some((minutes(start)?, minutes(end)?))
+}
+
+/// All rules, highest priority first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+    /// Log actions instead of performing them
+    pub dry_run: bool,
+}
+
+impl RuleSet {
+    /// Parse a rules file; broken rules are reported on stderr and skipped
+    pub fn from_ini(ini: &Ini) -> Self {
+        let mut set = RuleSet { dry_run: ini.get(SETTINGS, "dry_run").and_then(parse_bool).unwrap_or(false), ..Default::default() };
+        for name in ini.sections().filter_map(|section| section.strip_prefix(RULE_PREFIX)) {
+            match Rule::from_ini(name.trim(), ini) {
+                Ok(rule) => set.add(rule),
+                Err(e) => eprintln!("Ignoring rule '{}': {}", name, e),
+            }
+        }
+        set
+    }
+
+    /// Add or replace a rule by name, keeping priority order
+    fn add(&mut self, rule: Rule) {
+        self.rules.retain(|r| r.name != rule.name);
+        let index = self.rules.partition_point(|r| r.priority >= rule.priority);
+        self.rules.insert(index, rule);
+    }
+
+    /// Merge `other` into this set; its rules and settings win
+    fn extend(&mut self, other: RuleSet, other_sets_dry_run: bool) {
+        for rule in other.rules {
+            self.add(rule);
+        }
+        if other_sets_dry_run {
+            self.dry_run = other.dry_run;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    fn needs(&self, check: fn(&Conditions) -> bool) -> bool {
+        self.rules.iter().any(|rule| check(&rule.conditions))
+    }
+
+    /// Inputs for the rules from a state snapshot; process, idle and clock
+    /// readings are only taken when some rule needs them
+    pub fn sample(&self, state: &ThermalState) -> Sample {
+        Sample {
+            cpu_temp: state.cpu_temp,
+            keyboard_temp: state.keyboard_temp,
+            source: state.power_supply.source(),
+            battery_pct: state.power_supply.battery_pct,
+            minute_of_day: if self.needs(|c| c.time.is_some()) { local_minute_of_day() } else { None },
+            processes: if self.needs(|c| !c.processes.is_empty()) { processes::running() } else { Vec::new() },
+            idle: if self.needs(|c| c.idle_above.is_some() || c.idle_below.is_some()) { idle_time() } else { None },
+        }
+    }
+}
+
+/// User rules file location
+pub fn user_rules_file() -> Option<PathBuf> {
+    config::Config::path().and_then(|path| path.parent().map(|dir| dir.join(RULES_FILE)))
+}
+
+/// Rules from the given files, later files overriding earlier ones
+pub fn load_files(paths: &[PathBuf]) -> RuleSet {
+    let mut set = RuleSet::default();
+    for path in paths {
+        if let Ok(source) = fs::read_to_string(path) {
+            let ini = Ini::parse(&source);
+            set.extend(RuleSet::from_ini(&ini), ini.get(SETTINGS, "dry_run").is_some());
+        }
+    }
+    set
+}
+
+/// System rules file, then the user one
+pub fn rule_files() -> Vec<PathBuf> {
+    // Tests see no rules
+    if cfg!(test) {
+        return Vec::new();
+    }
+    std::iter::once(PathBuf::from(SYSTEM_RULES_FILE)).chain(user_rules_file()).collect()
+}
+
+/// System and user rules
+pub fn load() -> RuleSet {
+    load_files(&rule_files())
+}
+
+/// Modification times of `paths`, `None` for missing files
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths.iter().map(|path| fs::metadata(path).and_then(|m| m.modified()).ok()).collect()
+}
+
+/// An action of a rule that fired
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fired {
+    pub rule: String,
+    pub action: Action,
+}
+
+impl Fired {
+    /// "hot-keyboard: mode = quiet"
+    pub fn describe(&self) -> String {
+        format!("{}: {}", self.rule, self.action.describe())
+    }
+}
+
+/// Firing state of one rule
+#[derive(Debug, Clone, Copy)]
+struct RuleState {
+    /// Conditions have not held since the last firing
+    armed: bool,
+    last_fired: Option<Instant>,
+}
+
+/// Evaluates rules on every sample and remembers what fired
+#[derive(Debug, Default)]
+pub struct RuleEngine {
+    pub rules: RuleSet,
+    states: Vec<RuleState>,
+    /// Recent firings, newest last
+    log: VecDeque<String>,
+    /// Files the rules came from and their modification times
+    files: Vec<PathBuf>,
+    modified: Vec<Option<SystemTime>>,
+}
+
+impl RuleEngine {
+    pub fn new(rules: RuleSet) -> Self {
+        let states = vec![RuleState { armed: true, last_fired: None }; rules.rules.len()];
+        Self { rules, states, log: VecDeque::new(), files: Vec::new(), modified: Vec::new() }
+    }
+
+    /// Engine for the rules in `files`, followed by `reload_if_changed`
+    pub fn from_files(files: Vec<PathBuf>) -> Self {
+        let modified = modified(&files);
+        let rules = load_files(&files);
+        Self { files, modified, ..Self::new(rules) }
+    }
+
+    /// Re-read the files when one was edited, created or removed; the log is
+    /// kept, the firing state starts over
+    pub fn reload_if_changed(&mut self) -> bool {
+        let modified = modified(&self.files);
+        if modified == self.modified {
+            return false;
+        }
+        let log = std::mem::take(&mut self.log);
+        let files = std::mem::take(&mut self.files);
+        *self = Self { log, ..Self::from_files(files) };
+        true
+    }
+
+    /// Actions to perform for this sample; in dry run they are logged and
+    /// still returned, and the caller only reports them
+    pub fn evaluate(&mut self, sample: &Sample, now: Instant) -> Vec<Fired> {
+        let mut fired: Vec<Fired> = Vec::new();
+        for (rule, state) in self.rules.rules.iter().zip(self.states.iter_mut()) {
+            if !rule.conditions.matches(sample) {
+                state.armed = true;
+                continue;
+            }
+            let cooling = state.last_fired.is_some_and(|at| now.duration_since(at) < rule.cooldown);
+            if !state.armed || cooling {
+                continue;
+            }
+            state.armed = false;
+            state.last_fired = Some(now);
+            // Rules are in priority order, so an earlier action of the same kind wins
+            for action in &rule.actions {
+                if !fired.iter().any(|f| f.action.same_kind(action)) {
+                    fired.push(Fired { rule: rule.name.clone(), action: action.clone() });
+                }
+            }
+        }
+        for entry in &fired {
+            let mut line = match local_minute_of_day() {
+                Some(m) => format!("{:02}:{:02} {}", m / 60, m % 60, entry.describe()),
+                None => entry.describe(),
+            };
+            if self.rules.dry_run {
+                line = tr_args("rules-dry-run", &[("action", &line)]);
+            }
+            eprintln!("rules: {}", line);
+            if self.log.len() == LOG_CAPACITY {
+                self.log.pop_front();
+            }
+            self.log.push_back(line);
+        }
+        fired
+    }
+
+    /// Recent firings, newest last
+    pub fn log(&self) -> impl Iterator<Item = &str> {
+        self.log.iter().map(String::as_str)
+    }
+}
+
+/// Desktop notification for the `notify` action; best effort
+pub fn notify(text: &str) {
+    let spawned = Command::new("notify-send").args(["--app-name", tr("app-title"), tr("app-title"), text]).spawn();
+    if let Err(e) = spawned {
+        eprintln!("notify-send: {}", e);
+    }
+}
+
+/// Local UTC offset in seconds from `date +%z` ("+0130"), cached
+fn utc_offset() -> Option<i64> {
+    let mut cached = UTC_OFFSET.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((at, offset)) = *cached {
+        if at.elapsed() < UTC_OFFSET_TTL {
+            return Some(offset);
+        }
+    }
+    let output = Command::new("date").arg("+%z").output().ok().filter(|o| o.status.success())?;
+    let offset = parse_utc_offset(String::from_utf8_lossy(&output.stdout).trim())?;
+    *cached = Some((Instant::now(), offset));
+    Some(offset)
+}
+
+fn parse_utc_offset(text: &str) -> Option<i64> {
+    let (sign, digits) = match text.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Local minutes after midnight
+fn local_minute_of_day() -> Option<u16> {
+    let unix = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    Some(((unix + utc_offset()?).rem_euclid(86_400) / 60) as u16)
+}
+
+/// Time since the last input from logind's idle hint for this session
+fn idle_time() -> Option<Duration> {
+    let output = session::login1_properties(
+        "/org/freedesktop/login1/session/auto",
+        "org.freedesktop.login1.Session",
+        &["IdleHint", "IdleSinceHint"],
+    )?;
+    let now_us = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_micros() as u64;
+    parse_idle(&output, now_us)
+}
+
+/// Idle time from busctl output ("b true\nt 1700000000000000")
+fn parse_idle(output: &str, now_us: u64) -> Option<Duration> {
+    let mut lines = output.lines();
+    if !session::parse_bool_value(lines.next()?)? {
+        return Some(Duration::ZERO);
+    }
+    let since_us: u64 = lines.next()?.trim().strip_prefix("t ")?.parse().ok()?;
+    Some(Duration::from_micros(now_us.saturating_sub(since_us)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+
+    const RULES: &str = "\
+[settings]
+dry_run = on
+
+[rule hot]
+priority = 10
+cooldown = 120
+cpu_above = 80
+mode = quiet
+fan_boost = on
+notify = Hot
+
+[rule on-battery]
+ac = off
+battery_below = 30%
+mode = comfort
+target = 50
+
+[rule night]
+time = 22:00-07:00
+process = rustc, cc1plus
+idle_above = 300
+mode = performance
+
+[rule broken]
+cpu_above = warm
+mode = quiet
+";
+
+    fn sample(cpu_temp: f32) -> Sample {
+        Sample { cpu_temp, keyboard_temp: 35.0, source: Some(PowerSource::Battery), battery_pct: Some(20), ..Default::default() }
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let set = RuleSet::from_ini(&Ini::parse(RULES));
+        assert!(set.dry_run);
+        let names: Vec<&str> = set.rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["hot", "on-battery", "night"]);
+        let hot = &set.rules[0];
+        assert_eq!(hot.cooldown, Duration::from_secs(120));
+        assert_eq!(hot.actions_text(), "mode = quiet, fan_boost = on, notify = Hot");
+        assert_eq!(set.rules[1].conditions.battery_below, Some(30));
+        assert_eq!(set.rules[1].cooldown, DEFAULT_COOLDOWN);
+        let night = &set.rules[2].conditions;
+        assert_eq!(night.time, Some((22 * 60, 7 * 60)));
+        assert_eq!(night.processes, ["rustc", "cc1plus"]);
+        assert_eq!(night.idle_above, Some(Duration::from_secs(300)));
+
+        assert!(Rule::from_ini("x", &Ini::parse("[rule x]\ncpu_above = 80\n")).is_err());
+        assert!(Rule::from_ini("x", &Ini::parse("[rule x]\ntime = 25:00-07:00\nmode = quiet\n")).is_err());
+        assert!(Rule::from_ini("x", &Ini::parse("[rule x]\nmode = ../etc\n")).is_err());
+    }
+
+    #[test]
+    fn test_conditions() {
+        let set = RuleSet::from_ini(&Ini::parse(RULES));
+        let night = &set.rules[2].conditions;
+        let mut at_night = Sample {
+            minute_of_day: Some(23 * 60),
+            processes: vec!["bash".into(), "rustc".into()],
+            idle: Some(Duration::from_secs(600)),
+            ..sample(50.0)
+        };
+        assert!(night.matches(&at_night));
+        at_night.minute_of_day = Some(6 * 60 + 59);
+        assert!(night.matches(&at_night));
+        at_night.minute_of_day = Some(12 * 60);
+        assert!(!night.matches(&at_night));
+        // Unknown inputs never hold
+        at_night.minute_of_day = None;
+        assert!(!night.matches(&at_night));
+
+        let on_battery = &set.rules[1].conditions;
+        assert!(on_battery.matches(&sample(50.0)));
+        assert!(!on_battery.matches(&Sample { source: Some(PowerSource::Ac), ..sample(50.0) }));
+        assert!(!on_battery.matches(&Sample { battery_pct: None, ..sample(50.0) }));
+        assert!(Conditions::default().matches(&Sample::default()));
+    }
+
+    #[test]
+    fn test_engine_priority_and_cooldown() {
+        let mut engine = RuleEngine::new(RuleSet::from_ini(&Ini::parse(RULES)));
+        let start = Instant::now();
+
+        // Both fire; the higher priority mode wins, the target still applies
+        let fired = engine.evaluate(&sample(85.0), start);
+        let actions: Vec<String> = fired.iter().map(Fired::describe).collect();
+        assert_eq!(actions, ["hot: mode = quiet", "hot: fan_boost = on", "hot: notify = Hot", "on-battery: target = 50"]);
+        assert_eq!(engine.log().count(), 4);
+        assert!(engine.log().all(|line| line.starts_with("Dry run")));
+
+        // Still holding: nothing fires again
+        assert!(engine.evaluate(&sample(85.0), start + Duration::from_secs(10)).is_empty());
+
+        // Stops and starts holding within the cooldown: held back
+        engine.evaluate(&sample(60.0), start + Duration::from_secs(20));
+        assert!(engine.evaluate(&sample(85.0), start + Duration::from_secs(30)).is_empty());
+        // After the cooldown it fires once more
+        let fired = engine.evaluate(&sample(85.0), start + Duration::from_secs(130));
+        assert_eq!(fired.first().map(|f| f.rule.as_str()), Some("hot"));
+    }
+
+    #[test]
+    fn test_user_rules_override() {
+        let dir = tempfile::tempdir().unwrap();
+        put(dir.path(), "system.ini", RULES);
+        put(dir.path(), "user.ini", "[rule hot]\ncpu_above = 90\nmode = comfort\n[rule extra]\nac = on\nmode = balanced\n");
+        let set = load_files(&[dir.path().join("system.ini"), dir.path().join("user.ini"), dir.path().join("missing.ini")]);
+        let hot = set.rules.iter().find(|r| r.name == "hot").unwrap();
+        assert_eq!(hot.conditions.cpu_above, Some(90.0));
+        assert_eq!(hot.priority, 0);
+        assert_eq!(set.rules.len(), 4);
+        // The user file does not mention dry_run, so the system setting stays
+        assert!(set.dry_run);
+    }
+
+    #[test]
+    fn test_reload_edited_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.ini");
+        let mut engine = RuleEngine::from_files(vec![path.clone()]);
+        assert!(engine.rules.is_empty());
+        assert!(!engine.reload_if_changed());
+
+        put(dir.path(), "rules.ini", RULES);
+        assert!(engine.reload_if_changed());
+        assert_eq!(engine.rules.len(), 3);
+        assert!(!engine.reload_if_changed());
+
+        put(dir.path(), "rules.ini", "[rule extra]\nac = on\nmode = balanced\n");
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert!(engine.reload_if_changed());
+        assert_eq!(engine.rules.len(), 1);
+    }
+
+    #[test]
+    fn test_clock_and_idle_parsing() {
+        assert_eq!(parse_utc_offset("+0130"), Some(5400));
+        assert_eq!(parse_utc_offset("-0500"), Some(-18000));
+        assert_eq!(parse_utc_offset("UTC"), None);
+        assert_eq!(parse_idle("b false\nt 0\n", 10_000_000), Some(Duration::ZERO));
+        assert_eq!(parse_idle("b true\nt 4000000\n", 10_000_000), Some(Duration::from_secs(6)));
+        assert_eq!(parse_idle("", 0), None);
+    }
+}
@@ -0,0 +1,328 @@
+//! Session state from systemd-logind: screen lock, lid and suspend
+//!
+//! - `LockedHint` of the caller's session (`session/auto`), read with
+//!   `busctl get-property` and kept for a few seconds
+//! - The lid switch from `/proc/acpi/button/lid/*/state`, or logind's
+//!   `LidClosed` where ACPI does not expose it
+//! - `PrepareForSleep(true)` before suspend and `(false)` after resume,
+//!   followed with `gdbus monitor` on a background thread, which also
+//!   reports `LockedHint` changes so a lock is noticed at once. Without gdbus
+//!   a resume still shows as a jump of the boot time clock, which counts
+//!   suspend, against the monotonic one, which does not
+//!
+//! The GUI pauses automatic control and everything that may ask for a
+//! password while the session is locked or asleep.
+
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::fan::PROCFS_ROOT;
+
+const LOGIN1: &str = "org.freedesktop.login1";
+
+/// How long a logind property read with busctl is trusted
+const PROPERTY_TTL: Duration = Duration::from_secs(10);
+
+/// Boot time gap beyond the monotonic one that counts as a resume
+const RESUME_GAP: Duration = Duration::from_secs(30);
+
+/// Properties of a logind object, one `busctl` value per line
+pub fn login1_properties(object: &str, interface: &str, properties: &[&str]) -> Option<String> {
+    let output = Command::new("busctl")
+        .args(["get-property", LOGIN1, object, interface])
+        .args(properties)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Boolean from a busctl value ("b true")
+pub fn parse_bool_value(value: &str) -> Option<bool> {
+    match value.trim() {
+        "b true" => Some(true),
+        "b false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether this session's screen is locked; `None` when logind does not say
+fn read_locked() -> Option<bool> {
+    let value = login1_properties("/org/freedesktop/login1/session/auto", "org.freedesktop.login1.Session", &["LockedHint"])?;
+    parse_bool_value(&value)
+}
+
+/// Lid state from ACPI below `procfs`: `state:      closed`
+pub fn read_lid(procfs: &Path) -> Option<bool> {
+    let mut dirs: Vec<_> = fs::read_dir(procfs.join("acpi/button/lid")).ok()?.flatten().map(|e| e.path()).collect();
+    dirs.sort();
+    dirs.iter().find_map(|dir| {
+        let state = fs::read_to_string(dir.join("state")).ok()?;
+        match state.split_once(':')?.1.trim() {
+            "closed" => Some(true),
+            "open" => Some(false),
+            _ => None,
+        }
+    })
+}
+
+/// Lid state from logind; `None` on machines without a lid
+fn read_lid_closed() -> Option<bool> {
+    let value = login1_properties("/org/freedesktop/login1", "org.freedesktop.login1.Manager", &["LidClosed"])?;
+    parse_bool_value(&value)
+}
+
+/// Time since boot including suspend, from `/proc/uptime` below `procfs`
+pub fn read_uptime(procfs: &Path) -> Option<Duration> {
+    let uptime = fs::read_to_string(procfs.join("uptime")).ok()?;
+    Duration::try_from_secs_f64(uptime.split_whitespace().next()?.parse().ok()?).ok()
+}
+
+/// Whether boot time ran ahead of monotonic time between two polls, which
+/// only happens while suspended
+fn clock_jumped(before: (Instant, Duration), after: (Instant, Duration)) -> bool {
+    after.1.saturating_sub(before.1) > after.0.saturating_duration_since(before.0) + RESUME_GAP
+}
+
+/// `PrepareForSleep` argument from a `gdbus monitor` line:
+/// "/org/freedesktop/login1: org.freedesktop.login1.Manager.PrepareForSleep (true,)"
+pub fn parse_sleep_signal(line: &str) -> Option<bool> {
+    let (_, args) = line.split_once(".PrepareForSleep ")?;
+    match args.trim().trim_start_matches('(').split([',', ')']).next()? {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether a `gdbus monitor` line reports a changed `LockedHint`:
+/// "/org/freedesktop/login1/session/_32: org.freedesktop.DBus.Properties.PropertiesChanged
+/// ('org.freedesktop.login1.Session', {'LockedHint': <true>}, @as [])"
+pub fn is_lock_change(line: &str) -> bool {
+    line.contains(".PropertiesChanged ") && line.contains("'LockedHint'")
+}
+
+/// What happened since the last poll
+#[derive(Debug, Default)]
+struct SleepEvents {
+    /// Between `PrepareForSleep(true)` and `(false)`
+    sleeping: bool,
+    /// A resume not yet reported
+    resumed: bool,
+    /// `LockedHint` changed since it was last read
+    lock_changed: bool,
+    /// The monitor is running and reports suspend and resume
+    watching: bool,
+}
+
+impl SleepEvents {
+    fn record(&mut self, sleeping: bool) {
+        self.resumed |= self.sleeping && !sleeping;
+        self.sleeping = sleeping;
+    }
+}
+
+/// A logind value re-read once older than [`PROPERTY_TTL`]
+#[derive(Debug, Default)]
+struct Cached {
+    value: Option<bool>,
+    read_at: Option<Instant>,
+}
+
+impl Cached {
+    fn get(&mut self, read: fn() -> Option<bool>) -> Option<bool> {
+        if self.read_at.is_none_or(|at| at.elapsed() >= PROPERTY_TTL) {
+            self.value = read();
+            self.read_at = Some(Instant::now());
+        }
+        self.value
+    }
+}
+
+/// Follows suspend, resume, the screen lock and the lid
+#[derive(Debug)]
+pub struct SleepWatcher {
+    events: Arc<Mutex<SleepEvents>>,
+    /// `gdbus monitor`, stopped with the watcher
+    monitor: Option<Child>,
+    /// Why the monitor could not be started
+    error: Option<String>,
+    /// Monotonic and boot time at the last poll, to notice a resume
+    /// without the monitor
+    last_poll: (Instant, Option<Duration>),
+    locked: Cached,
+    lid: Cached,
+}
+
+impl Default for SleepWatcher {
+    fn default() -> Self {
+        Self {
+            events: Arc::default(),
+            monitor: None,
+            error: None,
+            last_poll: (Instant::now(), read_uptime(Path::new(PROCFS_ROOT))),
+            locked: Cached::default(),
+            lid: Cached::default(),
+        }
+    }
+}
+
+impl Drop for SleepWatcher {
+    fn drop(&mut self) {
+        if let Some(mut monitor) = self.monitor.take() {
+            let _ = monitor.kill();
+            let _ = monitor.wait();
+        }
+    }
+}
+
+impl SleepWatcher {
+    /// Start following logind signals; without gdbus only the clock check
+    /// and the timed property reads remain
+    pub fn start() -> Self {
+        let mut watcher = Self::default();
+        let spawned = Command::new("gdbus")
+            .args(["monitor", "--system", "--dest", LOGIN1])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut monitor = match spawned {
+            Ok(monitor) => monitor,
+            Err(e) => {
+                watcher.error = Some(format!("gdbus: {}", e));
+                return watcher;
+            }
+        };
+        if let Some(stdout) = monitor.stdout.take() {
+            let events = Arc::clone(&watcher.events);
+            watcher.events().watching = true;
+            std::thread::spawn(move || {
+                let lock = || events.lock().unwrap_or_else(PoisonError::into_inner);
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if let Some(sleeping) = parse_sleep_signal(&line) {
+                        lock().record(sleeping);
+                    } else if is_lock_change(&line) {
+                        lock().lock_changed = true;
+                    }
+                }
+                // The monitor exited: the clock check takes over
+                lock().watching = false;
+            });
+        }
+        watcher.monitor = Some(monitor);
+        watcher
+    }
+
+    fn events(&self) -> MutexGuard<'_, SleepEvents> {
+        self.events.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Why suspend and resume cannot be followed, if they cannot
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Whether the system is about to suspend
+    pub fn sleeping(&self) -> bool {
+        self.events().sleeping
+    }
+
+    /// Whether the system resumed since the last call
+    pub fn take_resumed(&mut self) -> bool {
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut resumed = std::mem::take(&mut events.resumed);
+        if !events.watching {
+            let now = (Instant::now(), read_uptime(Path::new(PROCFS_ROOT)));
+            if let ((before, Some(booted_before)), (after, Some(booted_after))) = (self.last_poll, now) {
+                resumed |= clock_jumped((before, booted_before), (after, booted_after));
+            }
+            self.last_poll = now;
+        }
+        if resumed {
+            events.sleeping = false;
+        }
+        resumed
+    }
+
+    /// Whether this session's screen is locked; `None` when logind does not say
+    pub fn locked(&mut self) -> Option<bool> {
+        if std::mem::take(&mut self.events().lock_changed) {
+            self.locked.read_at = None;
+        }
+        self.locked.get(read_locked)
+    }
+
+    /// Whether the lid is closed; `None` on machines without one
+    pub fn lid_closed(&mut self) -> Option<bool> {
+        read_lid(Path::new(PROCFS_ROOT)).or_else(|| self.lid.get(read_lid_closed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpufreq::tests::put;
+
+    #[test]
+    fn test_parse_logind_values() {
+        assert_eq!(parse_bool_value("b true\n"), Some(true));
+        assert_eq!(parse_bool_value("b false"), Some(false));
+        assert_eq!(parse_bool_value(""), None);
+
+        let signal = "/org/freedesktop/login1: org.freedesktop.login1.Manager.PrepareForSleep (true,)";
+        assert_eq!(parse_sleep_signal(signal), Some(true));
+        assert_eq!(parse_sleep_signal(&signal.replace("true", "false")), Some(false));
+        assert_eq!(parse_sleep_signal("/org/freedesktop/login1: org.freedesktop.login1.Manager.SessionNew ('3', objectpath '/x')"), None);
+
+        let lock = "/org/freedesktop/login1/session/_32: org.freedesktop.DBus.Properties.PropertiesChanged \
+                    ('org.freedesktop.login1.Session', {'LockedHint': <true>}, @as [])";
+        assert!(is_lock_change(lock));
+        assert!(!is_lock_change(&lock.replace("LockedHint", "IdleHint")));
+        assert!(!is_lock_change(signal));
+    }
+
+    #[test]
+    fn test_read_lid() {
+        let root = tempfile::tempdir().unwrap();
+        assert_eq!(read_lid(root.path()), None);
+        put(root.path(), "acpi/button/lid/LID0/state", "state:      open\n");
+        assert_eq!(read_lid(root.path()), Some(false));
+        put(root.path(), "acpi/button/lid/LID0/state", "state:      closed\n");
+        assert_eq!(read_lid(root.path()), Some(true));
+    }
+
+    #[test]
+    fn test_resume_from_signal() {
+        let mut watcher = SleepWatcher::default();
+        assert!(!watcher.take_resumed());
+        // A stray resume signal without the suspend one is ignored
+        watcher.events.lock().unwrap().record(false);
+        assert!(!watcher.take_resumed());
+
+        watcher.events.lock().unwrap().record(true);
+        assert!(watcher.sleeping());
+        watcher.events.lock().unwrap().record(false);
+        assert!(!watcher.sleeping());
+        assert!(watcher.take_resumed());
+        assert!(!watcher.take_resumed());
+    }
+
+    #[test]
+    fn test_resume_from_clocks() {
+        let root = tempfile::tempdir().unwrap();
+        assert_eq!(read_uptime(root.path()), None);
+        put(root.path(), "uptime", "3605.42 14000.10\n");
+        assert_eq!(read_uptime(root.path()), Some(Duration::from_millis(3_605_420)));
+
+        let start = Instant::now();
+        let later = start + Duration::from_secs(2);
+        let booted = Duration::from_secs(3600);
+        assert!(!clock_jumped((start, booted), (later, booted + Duration::from_secs(2))));
+        // An hour asleep between two polls
+        assert!(clock_jumped((start, booted), (later, booted + Duration::from_secs(3602))));
+    }
+}
@@ -0,0 +1,117 @@
+//! Keyboard shortcuts
+//!
+//! Maps key presses to GUI actions. Single-key shortcuts only fire without
+//! Ctrl/Alt/Cmd so they never shadow system or text-editing bindings.
+
+use eframe::egui::{Key, Modifiers};
+
+use crate::i18n::tr;
+
+/// Target temperature step for +/- (Celsius)
+pub const TARGET_STEP: f32 = 1.0;
+
+/// Action triggered by a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    /// Select mode by index into `Mode::available()`
+    SetMode(usize),
+    ToggleFanBoost,
+    ToggleAutoControl,
+    TargetUp,
+    TargetDown,
+    Export,
+    ToggleHelp,
+    CloseHelp,
+}
+
+/// Resolve a key press into a shortcut
+pub fn shortcut_for(key: Key, modifiers: Modifiers) -> Option<Shortcut> {
+    if modifiers.command || modifiers.ctrl {
+        return match key {
+            Key::E if !modifiers.alt => Some(Shortcut::Export),
+            _ => None,
+        };
+    }
+    if modifiers.alt {
+        return None;
+    }
+
+    match key {
+        Key::Num1 => Some(Shortcut::SetMode(0)),
+        Key::Num2 => Some(Shortcut::SetMode(1)),
+        Key::Num3 => Some(Shortcut::SetMode(2)),
+        Key::Num4 => Some(Shortcut::SetMode(3)),
+        Key::Num5 => Some(Shortcut::SetMode(4)),
+        Key::Num6 => Some(Shortcut::SetMode(5)),
+        Key::Num7 => Some(Shortcut::SetMode(6)),
+        Key::Num8 => Some(Shortcut::SetMode(7)),
+        Key::Num9 => Some(Shortcut::SetMode(8)),
+        Key::F => Some(Shortcut::ToggleFanBoost),
+        Key::A => Some(Shortcut::ToggleAutoControl),
+        Key::Plus | Key::Equals => Some(Shortcut::TargetUp),
+        Key::Minus => Some(Shortcut::TargetDown),
+        Key::F1 | Key::Questionmark => Some(Shortcut::ToggleHelp),
+        Key::Slash if modifiers.shift => Some(Shortcut::ToggleHelp),
+        Key::Escape => Some(Shortcut::CloseHelp),
+        _ => None,
+    }
+}
+
+/// Key bindings shown in the help overlay: (keys, description)
+pub fn help_entries() -> [(&'static str, &'static str); 7] {
+    [
+        ("1 – 9", tr("shortcut-modes")),
+        ("F", tr("shortcut-fan")),
+        ("A", tr("shortcut-auto")),
+        ("+ / −", tr("shortcut-target")),
+        ("Ctrl+E", tr("shortcut-export")),
+        ("Tab / Shift+Tab", tr("shortcut-focus")),
+        ("F1 / ?", tr("shortcut-help")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_keys() {
+        assert_eq!(shortcut_for(Key::Num1, Modifiers::NONE), Some(Shortcut::SetMode(0)));
+        assert_eq!(shortcut_for(Key::Num5, Modifiers::NONE), Some(Shortcut::SetMode(4)));
+        // Custom modes follow the built-in ones
+        assert_eq!(shortcut_for(Key::Num6, Modifiers::NONE), Some(Shortcut::SetMode(5)));
+        assert_eq!(shortcut_for(Key::Num9, Modifiers::NONE), Some(Shortcut::SetMode(8)));
+        assert_eq!(shortcut_for(Key::Num0, Modifiers::NONE), None);
+    }
+
+    #[test]
+    fn test_letter_keys() {
+        assert_eq!(shortcut_for(Key::F, Modifiers::NONE), Some(Shortcut::ToggleFanBoost));
+        assert_eq!(shortcut_for(Key::A, Modifiers::NONE), Some(Shortcut::ToggleAutoControl));
+        assert_eq!(shortcut_for(Key::E, Modifiers::NONE), None);
+    }
+
+    #[test]
+    fn test_target_keys() {
+        assert_eq!(shortcut_for(Key::Plus, Modifiers::NONE), Some(Shortcut::TargetUp));
+        assert_eq!(shortcut_for(Key::Equals, Modifiers::SHIFT), Some(Shortcut::TargetUp));
+        assert_eq!(shortcut_for(Key::Minus, Modifiers::NONE), Some(Shortcut::TargetDown));
+    }
+
+    #[test]
+    fn test_ctrl_shortcuts() {
+        assert_eq!(shortcut_for(Key::E, Modifiers::CTRL), Some(Shortcut::Export));
+        assert_eq!(shortcut_for(Key::E, Modifiers::COMMAND), Some(Shortcut::Export));
+        // Ctrl must not trigger single-key actions (e.g. Ctrl+A select all)
+        assert_eq!(shortcut_for(Key::A, Modifiers::CTRL), None);
+        assert_eq!(shortcut_for(Key::F, Modifiers::ALT), None);
+    }
+
+    #[test]
+    fn test_help_keys() {
+        assert_eq!(shortcut_for(Key::F1, Modifiers::NONE), Some(Shortcut::ToggleHelp));
+        assert_eq!(shortcut_for(Key::Slash, Modifiers::SHIFT), Some(Shortcut::ToggleHelp));
+        assert_eq!(shortcut_for(Key::Slash, Modifiers::NONE), None);
+        assert_eq!(shortcut_for(Key::Escape, Modifiers::NONE), Some(Shortcut::CloseHelp));
+    }
+}
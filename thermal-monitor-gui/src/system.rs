@@ -5,7 +5,21 @@
 
 use std::fs;
 use std::io::{self, ErrorKind};
-use std::process::Command;
+use std::path::{Path, PathBuf};
+
+use crate::cgroup;
+use crate::conflicts;
+use crate::cpufreq::{
+    detect_driver, energy_preference, energy_preference_choices, energy_preference_writes, CPU_ROOT,
+    MIN_PERF_PCT,
+};
+use crate::fan;
+use crate::power::{self, LimitRequest, PowerLimits, PowerReading};
+use crate::power_supply::{self, PowerSupply};
+use crate::helper::{self, apply_mode_privileged, write_privileged, WriteReport, WriteResult};
+use crate::i18n::{tr, tr_args};
+use crate::modes::{self, ModeProfile};
+use crate::platform_profile::{self, SYSFS_ROOT};
 
 /// Thermal attenuation factor for keyboard temperature estimation
 /// Based on physical model: T_kbd = T_amb + (T_cpu - T_amb) * ATTENUATION
@@ -15,7 +29,7 @@ const THERMAL_ATTENUATION: f32 = 0.45;
 const DEFAULT_AMBIENT: f32 = 28.0;
 
 /// CPU mode enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum Mode {
     Performance,
     Comfort,
@@ -23,46 +37,80 @@ pub enum Mode {
     Quiet,
     #[default]
     Auto,
+    /// User-defined mode, by profile name
+    Custom(String),
     Unknown,
 }
 
 impl Mode {
-    pub fn label(&self) -> &'static str {
+    pub fn label(&self) -> String {
         match self {
-            Mode::Performance => "PERFORMANCE",
-            Mode::Comfort => "COMFORT",
-            Mode::Balanced => "BALANCED",
-            Mode::Quiet => "QUIET",
-            Mode::Auto => "AUTO",
-            Mode::Unknown => "UNKNOWN",
+            Mode::Performance => tr("mode-performance").into(),
+            Mode::Comfort => tr("mode-comfort").into(),
+            Mode::Balanced => tr("mode-balanced").into(),
+            Mode::Quiet => tr("mode-quiet").into(),
+            Mode::Auto => tr("mode-auto").into(),
+            Mode::Custom(name) => modes::find(name).and_then(|p| p.label).unwrap_or_else(|| name.clone()),
+            Mode::Unknown => tr("mode-unknown").into(),
         }
     }
 
-    pub fn command(&self) -> &'static str {
+    pub fn command(&self) -> &str {
         match self {
             Mode::Performance => "performance",
             Mode::Comfort => "comfort",
             Mode::Balanced => "balanced",
             Mode::Quiet => "quiet",
             Mode::Auto => "auto",
+            Mode::Custom(name) => name,
             Mode::Unknown => "auto",
         }
     }
 
-    pub fn description(&self) -> &'static str {
-        match self {
-            Mode::Performance => "100% - Video calls",
-            Mode::Comfort => "60% - Cool keyboard",
-            Mode::Balanced => "75% - General use",
-            Mode::Quiet => "40% - Silent",
-            Mode::Auto => "Automatic",
-            Mode::Unknown => "Unknown",
+    /// Description derived from the mode's profile (including user overrides)
+    pub fn description(&self) -> String {
+        match (self, modes::find(self.command())) {
+            (Mode::Unknown, _) | (Mode::Custom(_), None) => tr("mode-unknown-desc").to_string(),
+            (_, Some(profile)) => profile.description(),
+            (_, None) => ModeProfile::builtin(self).description(),
         }
     }
 
+    /// Predefined modes
     pub fn all() -> &'static [Mode] {
         &[Mode::Performance, Mode::Comfort, Mode::Balanced, Mode::Quiet, Mode::Auto]
     }
+
+    /// Predefined modes followed by user-defined ones
+    pub fn available() -> Vec<Mode> {
+        let custom = modes::profiles()
+            .into_iter()
+            .filter(|p| !modes::is_builtin(&p.name))
+            .map(|p| Mode::Custom(p.name));
+        Mode::all().iter().cloned().chain(custom).collect()
+    }
+
+    /// Mode for a profile name, `Unknown` if no such profile exists
+    pub fn from_name(name: &str) -> Mode {
+        match Mode::all().iter().find(|m| m.command() == name) {
+            Some(mode) => mode.clone(),
+            None if modes::find(name).is_some() => Mode::Custom(name.to_string()),
+            None => Mode::Unknown,
+        }
+    }
+}
+
+/// Localized name of an Energy Performance Preference value
+/// Values not known to us are shown as the kernel reports them
+pub fn energy_preference_label(value: &str) -> &str {
+    match value {
+        "default" => tr("epp-default"),
+        "performance" => tr("epp-performance"),
+        "balance_performance" => tr("epp-balance-performance"),
+        "balance_power" => tr("epp-balance-power"),
+        "power" => tr("epp-power"),
+        other => other,
+    }
 }
 
 /// Thermal zone classification
@@ -90,12 +138,12 @@ impl ThermalZone {
 
     pub fn label(&self) -> &'static str {
         match self {
-            ThermalZone::Cool => "COOL",
-            ThermalZone::Comfort => "COMFORT",
-            ThermalZone::Optimal => "OPTIMAL",
-            ThermalZone::Warm => "WARM",
-            ThermalZone::Hot => "HOT",
-            ThermalZone::Critical => "CRITICAL",
+            ThermalZone::Cool => tr("zone-cool"),
+            ThermalZone::Comfort => tr("zone-comfort"),
+            ThermalZone::Optimal => tr("zone-optimal"),
+            ThermalZone::Warm => tr("zone-warm"),
+            ThermalZone::Hot => tr("zone-hot"),
+            ThermalZone::Critical => tr("zone-critical"),
         }
     }
 
@@ -113,7 +161,7 @@ impl ThermalZone {
 }
 
 /// Read a single value from a sysfs file
-fn read_sysfs_value(path: &str) -> io::Result<String> {
+pub(crate) fn read_sysfs_value(path: impl AsRef<Path>) -> io::Result<String> {
     fs::read_to_string(path).map(|s| s.trim().to_string())
 }
 
@@ -177,10 +225,30 @@ pub fn calculate_keyboard_temp(cpu_temp: f32, ambient_temp: f32) -> f32 {
     ambient_temp + (cpu_temp - ambient_temp) * THERMAL_ATTENUATION
 }
 
-/// Read current performance percentage from intel_pstate
+/// Read effective maximum performance percentage from the active cpufreq driver
 pub fn read_perf_pct() -> io::Result<u8> {
-    let content = read_sysfs_value("/sys/devices/system/cpu/intel_pstate/max_perf_pct")?;
-    content.parse::<u8>().map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    detect_driver(Path::new(CPU_ROOT)).max_perf_pct()
+}
+
+/// Read the minimum performance floor percentage
+pub fn read_min_perf_pct() -> io::Result<u8> {
+    detect_driver(Path::new(CPU_ROOT)).min_perf_pct()
+}
+
+/// Read whether turbo is enabled (`None` when the driver has no switch)
+pub fn read_turbo() -> Option<bool> {
+    detect_driver(Path::new(CPU_ROOT)).turbo_enabled()
+}
+
+/// Read the Energy Performance Preference and the values the driver accepts
+pub fn read_energy_preference() -> (Option<String>, Vec<String>) {
+    let root = Path::new(CPU_ROOT);
+    (energy_preference(root), energy_preference_choices(root))
+}
+
+/// Name of the active cpufreq driver
+pub fn read_cpufreq_driver() -> String {
+    detect_driver(Path::new(CPU_ROOT)).name()
 }
 
 /// Read current CPU frequency in MHz
@@ -197,82 +265,77 @@ pub fn read_max_freq() -> io::Result<u32> {
     Ok(khz / 1000)
 }
 
-/// Read current mode from cpu-mode status file
+/// Mode recorded by the privileged helper
 pub fn read_mode() -> Mode {
-    if let Ok(content) = read_sysfs_value("/tmp/cpu-mode.current") {
-        let lower = content.to_lowercase();
-        if lower.contains("performance") {
-            Mode::Performance
-        } else if lower.contains("comfort") {
-            if lower.contains("auto") || lower.contains("-") {
-                Mode::Auto // comfort-OPTIMAL, etc.
-            } else {
-                Mode::Comfort
-            }
-        } else if lower.contains("balanced") {
-            Mode::Balanced
-        } else if lower.contains("quiet") {
-            Mode::Quiet
-        } else if lower.contains("auto") {
-            Mode::Auto
-        } else {
-            Mode::Unknown
-        }
-    } else {
-        Mode::Unknown
+    helper::mode_state().map_or(Mode::Unknown, |state| Mode::from_name(&state.name))
+}
+
+/// Knobs that no longer match the current mode
+pub fn read_mode_drift(mode: &Mode) -> Vec<WriteResult> {
+    match mode {
+        Mode::Unknown => Vec::new(),
+        _ => modes::find(mode.command())
+            .map(|profile| helper::check_drift(&profile, Path::new(SYSFS_ROOT)))
+            .unwrap_or_default(),
     }
 }
 
 /// Read platform profile
 pub fn read_platform_profile() -> String {
-    read_sysfs_value("/sys/firmware/acpi/platform_profile").unwrap_or_else(|_| "unknown".into())
+    platform_profile::current(Path::new(SYSFS_ROOT)).unwrap_or_else(|| "unknown".into())
 }
 
-/// Read fan mode (0=auto, 1=boost)
-pub fn read_fan_mode() -> u8 {
-    read_sysfs_value("/sys/devices/pci0000:00/0000:00:1f.0/PNP0C09:00/VPC2004:00/fan_mode")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0)
+/// Platform profiles advertised by the firmware
+pub fn read_platform_profile_choices() -> Vec<String> {
+    platform_profile::choices(Path::new(SYSFS_ROOT))
 }
 
-/// Activate fan boost (max speed) - Lenovo IdeaPad specific
-pub fn set_fan_boost(enable: bool) -> io::Result<()> {
-    let value = if enable { "1" } else { "0" };
-    let output = Command::new("pkexec")
-        .args(["bash", "-c", &format!(
-            "echo {} > /sys/devices/pci0000:00/0000:00:1f.0/PNP0C09:00/VPC2004:00/fan_mode",
-            value
-        )])
-        .output()?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(io::Error::new(ErrorKind::Other, "Failed to set fan mode"))
-    }
-}
-
-/// Set performance percentage directly
-pub fn set_perf_pct(pct: u8) -> io::Result<()> {
-    let pct = pct.clamp(20, 100);
-    let output = Command::new("pkexec")
-        .args(["bash", "-c", &format!(
-            "echo {} > /sys/devices/system/cpu/intel_pstate/max_perf_pct 2>/dev/null || \
-             echo {} > /sys/devices/system/cpu/amd_pstate/max_perf_pct 2>/dev/null || \
-             for cpu in /sys/devices/system/cpu/cpu*/cpufreq/scaling_max_freq; do \
-               max=$(cat /sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq); \
-               echo $((max * {} / 100)) > $cpu; \
-             done",
-            pct, pct, pct
-        )])
-        .output()?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(io::Error::new(ErrorKind::Other, "Failed to set performance"))
+/// Switch the platform profile; only advertised choices are accepted
+/// With `via_ppd` the closest power-profiles-daemon profile is requested instead
+pub fn set_platform_profile(profile: &str, via_ppd: bool) -> io::Result<WriteReport> {
+    if via_ppd {
+        return conflicts::set_ppd_profile(conflicts::ppd_profile(profile)).map(|_| WriteReport::default());
     }
+    write_privileged(&platform_profile::profile_writes(Path::new(SYSFS_ROOT), profile)?)
+}
+
+/// Switch fan boost (max speed) through the detected fan backend
+pub fn set_fan_boost(enable: bool) -> io::Result<()> {
+    write_privileged(&fan::backend().boost_writes(enable)?).map(|_| ())
+}
+
+/// Set performance percentage on every policy through the active cpufreq driver
+/// Each attribute is verified after writing; any failure is reported per path
+pub fn set_perf_pct(pct: u8) -> io::Result<WriteReport> {
+    let pct = pct.clamp(MIN_PERF_PCT, 100);
+    write_privileged(&detect_driver(Path::new(CPU_ROOT)).max_perf_writes(pct)?)
+}
+
+/// Set the performance percentage of a single cpufreq policy
+pub fn set_policy_perf_pct(policy: u32, pct: u8) -> io::Result<WriteReport> {
+    let pct = pct.clamp(MIN_PERF_PCT, 100);
+    let root = Path::new(CPU_ROOT);
+    write_privileged(&detect_driver(root).policy_limit_writes(root, &[(policy, pct)])?)
+}
+
+/// Set the minimum performance floor through the active cpufreq driver
+pub fn set_min_perf_pct(pct: u8) -> io::Result<WriteReport> {
+    write_privileged(&detect_driver(Path::new(CPU_ROOT)).min_perf_writes(pct.min(100))?)
+}
+
+/// Allow or forbid turbo/boost frequencies
+pub fn set_turbo(enabled: bool) -> io::Result<WriteReport> {
+    write_privileged(&detect_driver(Path::new(CPU_ROOT)).turbo_writes(enabled)?)
+}
+
+/// Set RAPL package power limits on every package
+pub fn set_power_limits(request: &LimitRequest) -> io::Result<WriteReport> {
+    write_privileged(&power::limit_writes(Path::new(SYSFS_ROOT), request)?)
+}
+
+/// Set the Energy Performance Preference on every policy
+pub fn set_energy_preference(value: &str) -> io::Result<WriteReport> {
+    write_privileged(&energy_preference_writes(Path::new(CPU_ROOT), value)?)
 }
 
 /// Calculate required performance percentage to reach target temperature
@@ -287,48 +350,151 @@ pub fn calc_perf_for_target(current_temp: f32, target_temp: f32, current_perf: u
     }
 }
 
-/// Apply thermal control to reach target temperature
-pub fn apply_thermal_control(current_temp: f32, target_temp: f32) -> io::Result<String> {
-    let current_perf = read_perf_pct().unwrap_or(75);
-    let diff = current_temp - target_temp;
+/// Overshoot (Celsius) above which automatic control wants fan boost
+const FAN_BOOST_OVERSHOOT: f32 = 5.0;
+
+/// Whether automatic control wants fan boost at this temperature; the
+/// caller requests or releases it, see [`fan::BoostController`]
+pub fn control_wants_fan_boost(current_temp: f32, target_temp: f32) -> bool {
+    current_temp - target_temp > FAN_BOOST_OVERSHOOT
+}
+
+/// Knob automatic thermal control turns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Actuator {
+    /// Performance ceiling (`max_perf_pct` or frequency limits)
+    #[default]
+    Performance,
+    /// Sustained RAPL package power limit (PL1)
+    PowerLimit,
+    /// CPU quota of selected apps' cgroups, then the performance ceiling
+    Cgroups,
+}
+
+impl Actuator {
+    pub fn all() -> &'static [Actuator] {
+        &[Actuator::Performance, Actuator::PowerLimit, Actuator::Cgroups]
+    }
 
-    if diff > 10.0 {
-        // Critical: fan boost + aggressive throttle
-        let _ = set_fan_boost(true);
-        set_perf_pct(30)?;
-        Ok("CRITICAL: Fan boost + 30%".into())
-    } else if diff > 5.0 {
-        // High: fan boost + moderate throttle
-        let _ = set_fan_boost(true);
-        set_perf_pct(50)?;
-        Ok("HIGH: Fan boost + 50%".into())
+    pub fn label(&self) -> &'static str {
+        match self {
+            Actuator::Performance => tr("actuator-performance"),
+            Actuator::PowerLimit => tr("actuator-power-limit"),
+            Actuator::Cgroups => tr("actuator-cgroups"),
+        }
+    }
+
+    /// Config file key
+    pub fn key(&self) -> &'static str {
+        match self {
+            Actuator::Performance => "performance",
+            Actuator::PowerLimit => "power_limit",
+            Actuator::Cgroups => "cgroups",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Actuator::all().iter().copied().find(|actuator| actuator.key() == value.trim().to_lowercase())
+    }
+}
+
+/// Apply thermal control to reach target temperature, turning `actuator`;
+/// `throttle_groups` are the cgroups the cgroup actuator caps.
+/// Fan boost is left to the caller through [`control_wants_fan_boost`]
+pub fn apply_thermal_control(
+    current_temp: f32,
+    target_temp: f32,
+    actuator: Actuator,
+    throttle_groups: &[PathBuf],
+) -> io::Result<String> {
+    let diff = current_temp - target_temp;
+    let limits = match actuator {
+        Actuator::PowerLimit => power::limits(),
+        Actuator::Performance | Actuator::Cgroups => PowerLimits::default(),
+    };
+    let groups = match actuator {
+        Actuator::Cgroups => throttle_groups,
+        Actuator::Performance | Actuator::PowerLimit => &[],
+    };
+    let cpus = cgroup::cpu_count();
+    let current_pct = match actuator {
+        Actuator::Performance => read_perf_pct().unwrap_or(75),
+        Actuator::PowerLimit => limits
+            .long_term_pct()
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "No adjustable RAPL power limit found"))?,
+        Actuator::Cgroups => {
+            let current = cgroup::current_pct(groups, cpus)
+                .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "No matching cgroups to throttle"))?;
+            // The offenders go first: the ceiling drops only once they are
+            // at the floor, and is raised again before they are
+            let ceiling_lowered = read_perf_pct().is_ok_and(|pct| pct < 100);
+            if (diff > 0.0 && current <= cgroup::MIN_THROTTLE_PCT) || (diff < -5.0 && ceiling_lowered) {
+                return apply_thermal_control(current_temp, target_temp, Actuator::Performance, &[]);
+            }
+            current
+        }
+    };
+
+    // Status messages for the performance, power limit and cgroup actuators
+    let (pct, keys) = if diff > 10.0 {
+        // Critical: aggressive throttle
+        (30, ["control-critical", "control-limit-critical", "control-cgroup-critical"])
+    } else if diff > FAN_BOOST_OVERSHOOT {
+        // High: moderate throttle
+        (50, ["control-high", "control-limit-high", "control-cgroup-high"])
     } else if diff > 0.0 {
         // Slight overshoot: gradual reduction
-        let new_perf = calc_perf_for_target(current_temp, target_temp, current_perf);
-        set_perf_pct(new_perf)?;
-        Ok(format!("Adjusting to {}%", new_perf))
+        let pct = calc_perf_for_target(current_temp, target_temp, current_pct);
+        (pct, ["control-adjusting", "control-limit-adjusting", "control-cgroup-adjusting"])
     } else if diff < -5.0 {
         // Well below target: can increase
-        let new_perf = (current_perf + 10).min(100);
-        set_perf_pct(new_perf)?;
-        Ok(format!("Increasing to {}%", new_perf))
+        ((current_pct + 10).min(100), ["control-increasing", "control-limit-increasing", "control-cgroup-increasing"])
     } else {
-        Ok("On target".into())
+        return Ok(tr("control-on-target").into());
+    };
+
+    match actuator {
+        Actuator::Performance => {
+            set_perf_pct(pct)?;
+            Ok(tr_args(keys[0], &[("pct", &pct.to_string())]))
+        }
+        Actuator::PowerLimit => {
+            let watts = limits.long_term_watts_at(pct).unwrap_or(power::MIN_POWER_LIMIT_W);
+            set_power_limits(&LimitRequest { long_term_w: Some(watts), ..Default::default() })?;
+            Ok(tr_args(keys[1], &[("watts", &watts.to_string())]))
+        }
+        Actuator::Cgroups => {
+            // Keep tightening while hot, so the floor and the ceiling are reached
+            let pct = if diff > 0.0 { pct.min(current_pct.saturating_sub(10)) } else { pct };
+            let pct = pct.max(cgroup::MIN_THROTTLE_PCT);
+            cgroup::set_throttle(groups, pct)?;
+            Ok(tr_args(keys[2], &[("pct", &pct.to_string()), ("count", &groups.len().to_string())]))
+        }
     }
 }
 
-/// Change CPU mode using pkexec
-pub fn set_mode(mode: Mode) -> io::Result<()> {
-    let output = Command::new("pkexec")
-        .args(["/usr/local/bin/cpu-mode", mode.command()])
-        .output()?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(io::Error::new(ErrorKind::Other, format!("Failed to change mode: {}", stderr)))
+/// Apply a mode profile by name through the privileged helper
+/// With `via_ppd` the platform profile and EPP are requested from
+/// power-profiles-daemon and only the remaining knobs are written
+pub fn apply_mode(name: &str, via_ppd: bool) -> io::Result<WriteReport> {
+    let profile = modes::find(name)
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("Unknown mode '{}'", name)))?;
+    let mut writes = profile.writes(Path::new(SYSFS_ROOT))?;
+    if via_ppd {
+        if let Some(target) = conflicts::ppd_profile_for(&profile) {
+            conflicts::set_ppd_profile(target)?;
+        }
+        writes = conflicts::without_ppd_knobs(writes);
     }
+    apply_mode_privileged(&profile.name, profile.automatic, &writes)
+}
+
+/// Change CPU mode
+pub fn set_mode(mode: &Mode, via_ppd: bool) -> io::Result<WriteReport> {
+    let report = apply_mode(mode.command(), via_ppd);
+    // A mode may switch fan drivers between firmware and manual control
+    fan::redetect();
+    report
 }
 
 /// Complete thermal state snapshot
@@ -338,11 +504,30 @@ pub struct ThermalState {
     pub keyboard_temp: f32,
     pub ambient_temp: f32,
     pub perf_pct: u8,
+    pub min_perf_pct: u8,
+    pub turbo: Option<bool>,
+    pub energy_preference: Option<String>,
+    pub energy_preferences: Vec<String>,
+    pub cpufreq_driver: String,
     pub current_freq_mhz: u32,
     pub max_freq_mhz: u32,
     pub mode: Mode,
+    /// Knobs changed since the mode was applied
+    pub mode_drift: Vec<WriteResult>,
     pub platform_profile: String,
-    pub fan_boost: bool,
+    pub platform_profile_choices: Vec<String>,
+    /// Fan boost state, `None` when there is no fan backend
+    pub fan_boost: Option<bool>,
+    /// Fan backend driver name, "none" without one
+    pub fan_backend: &'static str,
+    /// Fan speed in RPM, `None` when no driver reports it
+    pub fan_rpm: Option<u32>,
+    /// CPU power since the previous read
+    pub power: PowerReading,
+    /// RAPL package power limits; empty where there are none
+    pub power_limits: PowerLimits,
+    /// AC adapter and battery
+    pub power_supply: PowerSupply,
 }
 
 impl ThermalState {
@@ -351,17 +536,32 @@ impl ThermalState {
         let cpu_temp = read_cpu_temp().unwrap_or(50.0);
         let ambient_temp = read_ambient_temp();
         let keyboard_temp = calculate_keyboard_temp(cpu_temp, ambient_temp);
+        let (energy_preference, energy_preferences) = read_energy_preference();
+        let mode = read_mode();
+        let fan = fan::backend();
 
         Self {
             cpu_temp,
             keyboard_temp,
             ambient_temp,
             perf_pct: read_perf_pct().unwrap_or(50),
+            min_perf_pct: read_min_perf_pct().unwrap_or(0),
+            turbo: read_turbo(),
+            energy_preference,
+            energy_preferences,
+            cpufreq_driver: read_cpufreq_driver(),
             current_freq_mhz: read_current_freq().unwrap_or(1000),
             max_freq_mhz: read_max_freq().unwrap_or(4400),
-            mode: read_mode(),
+            mode_drift: read_mode_drift(&mode),
+            mode,
             platform_profile: read_platform_profile(),
-            fan_boost: read_fan_mode() == 1,
+            platform_profile_choices: read_platform_profile_choices(),
+            fan_boost: fan.boost_enabled(),
+            fan_backend: fan.name(),
+            fan_rpm: fan::rpm(),
+            power: power::read_power(),
+            power_limits: power::limits(),
+            power_supply: power_supply::read(),
         }
     }
 
@@ -376,6 +576,7 @@ impl ThermalState {
     }
 
     /// Get max frequency in GHz
+    #[allow(dead_code)]
     pub fn max_freq_ghz(&self) -> f32 {
         self.max_freq_mhz as f32 / 1000.0
     }
@@ -385,6 +586,12 @@ impl ThermalState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_energy_preference_label() {
+        assert_eq!(energy_preference_label("balance_power"), "Balance power");
+        assert_eq!(energy_preference_label("custom_hint"), "custom_hint");
+    }
+
     #[test]
     fn test_thermal_zone_classification() {
         assert_eq!(ThermalZone::from_cpu_temp(35.0), ThermalZone::Cool);
@@ -470,9 +677,22 @@ mod tests {
         assert!(Mode::Balanced.description().contains("75%"));
         assert!(Mode::Quiet.description().contains("40%"));
         assert!(Mode::Auto.description().contains("Automatic"));
+        assert_eq!(Mode::Custom("missing".into()).description(), "Unknown");
+    }
+
+    #[test]
+    fn test_mode_from_name() {
+        assert_eq!(Mode::from_name("quiet"), Mode::Quiet);
+        assert_eq!(Mode::from_name("auto"), Mode::Auto);
+        // Tests only load the built-in profiles
+        assert_eq!(Mode::from_name("compile"), Mode::Unknown);
+        assert_eq!(Mode::available(), Mode::all());
+        assert_eq!(Mode::Custom("compile".into()).label(), "compile");
+        assert_eq!(Mode::Custom("compile".into()).command(), "compile");
     }
 
     #[test]
+    #[allow(unused_variables)]
     fn test_thermal_zone_colors() {
         let (r, g, b) = ThermalZone::Cool.color_rgb();
         assert!(b > r); // Blue should be dominant for cool
@@ -514,6 +734,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_calc_perf_for_target_at_target() {
         // At target - minimal change
         let perf = calc_perf_for_target(55.0, 55.0, 75);
@@ -562,6 +783,7 @@ mod tests {
         let state = ThermalState::default();
         assert_eq!(state.cpu_temp, 0.0);
         assert_eq!(state.mode, Mode::Auto);
-        assert!(!state.fan_boost);
+        assert_eq!(state.fan_boost, None);
+        assert_eq!(state.fan_rpm, None);
     }
 }
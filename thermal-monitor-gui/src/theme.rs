@@ -0,0 +1,361 @@
+//! Color themes and status palettes
+//!
+//! Supports light, dark and high-contrast themes. The "system" preference
+//! follows the freedesktop appearance settings exposed by the desktop
+//! portal (`org.freedesktop.appearance` color-scheme and contrast),
+//! queried through `busctl` to avoid a D-Bus library dependency.
+
+use std::process::Command;
+
+use eframe::egui::{self, Color32, Stroke};
+
+use crate::i18n::tr;
+use crate::modes;
+use crate::system::{Mode, ThermalZone};
+
+/// Resolved theme used for rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    Light,
+    #[default]
+    Dark,
+    HighContrast,
+}
+
+/// User theme preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemePreference {
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl ThemePreference {
+    pub fn all() -> &'static [ThemePreference] {
+        &[
+            ThemePreference::System,
+            ThemePreference::Light,
+            ThemePreference::Dark,
+            ThemePreference::HighContrast,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemePreference::System => tr("theme-system"),
+            ThemePreference::Light => tr("theme-light"),
+            ThemePreference::Dark => tr("theme-dark"),
+            ThemePreference::HighContrast => tr("theme-high-contrast"),
+        }
+    }
+
+    /// Config file key
+    pub fn key(&self) -> &'static str {
+        match self {
+            ThemePreference::System => "system",
+            ThemePreference::Light => "light",
+            ThemePreference::Dark => "dark",
+            ThemePreference::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        ThemePreference::all()
+            .iter()
+            .copied()
+            .find(|pref| pref.key() == value.trim().to_lowercase())
+    }
+
+    /// Resolve to a concrete theme, given the desktop setting (if known)
+    pub fn resolve(&self, system: Option<Theme>) -> Theme {
+        match self {
+            ThemePreference::System => system.unwrap_or_default(),
+            ThemePreference::Light => Theme::Light,
+            ThemePreference::Dark => Theme::Dark,
+            ThemePreference::HighContrast => Theme::HighContrast,
+        }
+    }
+}
+
+impl Theme {
+    /// egui visuals for this theme
+    pub fn visuals(&self) -> egui::Visuals {
+        match self {
+            Theme::Light => egui::Visuals::light(),
+            Theme::Dark => egui::Visuals::dark(),
+            Theme::HighContrast => {
+                let mut visuals = egui::Visuals::dark();
+                visuals.override_text_color = Some(Color32::WHITE);
+                visuals.panel_fill = Color32::BLACK;
+                visuals.window_fill = Color32::BLACK;
+                visuals.extreme_bg_color = Color32::BLACK;
+                visuals.faint_bg_color = Color32::from_gray(20);
+                visuals.selection.stroke = Stroke::new(2.0, Color32::YELLOW);
+                for widget in [
+                    &mut visuals.widgets.noninteractive,
+                    &mut visuals.widgets.inactive,
+                    &mut visuals.widgets.hovered,
+                    &mut visuals.widgets.active,
+                    &mut visuals.widgets.open,
+                ] {
+                    widget.bg_stroke = Stroke::new(1.5, Color32::WHITE);
+                    widget.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+                }
+                visuals.widgets.hovered.bg_stroke = Stroke::new(2.0, Color32::YELLOW);
+                visuals
+            }
+        }
+    }
+
+    /// Thermal zone color readable on this theme's background
+    pub fn zone_color(&self, zone: ThermalZone) -> Color32 {
+        match self {
+            Theme::Dark => {
+                let (r, g, b) = zone.color_rgb();
+                Color32::from_rgb(r, g, b)
+            }
+            Theme::Light => match zone {
+                ThermalZone::Cool => Color32::from_rgb(0, 110, 190),
+                ThermalZone::Comfort => Color32::from_rgb(20, 130, 40),
+                ThermalZone::Optimal => Color32::from_rgb(80, 130, 0),
+                ThermalZone::Warm => Color32::from_rgb(170, 110, 0),
+                ThermalZone::Hot => Color32::from_rgb(200, 80, 0),
+                ThermalZone::Critical => Color32::from_rgb(190, 0, 0),
+            },
+            Theme::HighContrast => match zone {
+                ThermalZone::Cool => Color32::from_rgb(0, 220, 255),
+                ThermalZone::Comfort => Color32::from_rgb(0, 255, 0),
+                ThermalZone::Optimal => Color32::from_rgb(190, 255, 0),
+                ThermalZone::Warm => Color32::from_rgb(255, 255, 0),
+                ThermalZone::Hot => Color32::from_rgb(255, 160, 0),
+                ThermalZone::Critical => Color32::from_rgb(255, 110, 110),
+            },
+        }
+    }
+
+    /// Mode accent color readable on this theme's background
+    /// User-defined modes use their own color, if they set one
+    pub fn mode_color(&self, mode: &Mode) -> Color32 {
+        match (self, mode) {
+            (_, Mode::Unknown) => Color32::GRAY,
+            (_, Mode::Custom(name)) => match modes::find(name).and_then(|p| p.color) {
+                Some([r, g, b]) => Color32::from_rgb(r, g, b),
+                None => self.custom_mode_color(),
+            },
+            (Theme::Dark, Mode::Performance) => Color32::from_rgb(255, 100, 100),
+            (Theme::Dark, Mode::Comfort) => Color32::from_rgb(100, 200, 255),
+            (Theme::Dark, Mode::Balanced) => Color32::from_rgb(150, 220, 100),
+            (Theme::Dark, Mode::Quiet) => Color32::from_rgb(180, 180, 220),
+            (Theme::Dark, Mode::Auto) => Color32::from_rgb(255, 200, 100),
+            (Theme::Light, Mode::Performance) => Color32::from_rgb(190, 30, 30),
+            (Theme::Light, Mode::Comfort) => Color32::from_rgb(0, 110, 190),
+            (Theme::Light, Mode::Balanced) => Color32::from_rgb(50, 130, 20),
+            (Theme::Light, Mode::Quiet) => Color32::from_rgb(90, 80, 170),
+            (Theme::Light, Mode::Auto) => Color32::from_rgb(170, 110, 0),
+            (Theme::HighContrast, Mode::Performance) => Color32::from_rgb(255, 110, 110),
+            (Theme::HighContrast, Mode::Comfort) => Color32::from_rgb(0, 220, 255),
+            (Theme::HighContrast, Mode::Balanced) => Color32::from_rgb(0, 255, 0),
+            (Theme::HighContrast, Mode::Quiet) => Color32::from_rgb(200, 170, 255),
+            (Theme::HighContrast, Mode::Auto) => Color32::from_rgb(255, 255, 0),
+        }
+    }
+
+    /// Accent for user-defined modes without a color of their own
+    fn custom_mode_color(&self) -> Color32 {
+        match self {
+            Theme::Dark => Color32::from_rgb(100, 220, 200),
+            Theme::Light => Color32::from_rgb(0, 120, 110),
+            Theme::HighContrast => Color32::from_rgb(0, 255, 200),
+        }
+    }
+
+    /// Text drawn on top of a filled accent (selected buttons)
+    pub fn on_accent(&self) -> Color32 {
+        match self {
+            Theme::Light => Color32::WHITE,
+            Theme::Dark | Theme::HighContrast => Color32::BLACK,
+        }
+    }
+
+    /// Secondary text (reading labels, hints)
+    pub fn muted(&self) -> Color32 {
+        match self {
+            Theme::Light => Color32::from_gray(90),
+            Theme::Dark => Color32::GRAY,
+            Theme::HighContrast => Color32::from_gray(220),
+        }
+    }
+
+    /// Least important text (version, footnotes)
+    pub fn faint(&self) -> Color32 {
+        match self {
+            Theme::Light => Color32::from_gray(120),
+            Theme::Dark => Color32::DARK_GRAY,
+            Theme::HighContrast => Color32::from_gray(200),
+        }
+    }
+
+    /// "On target" / enabled indicator
+    pub fn good(&self) -> Color32 {
+        self.zone_color(ThermalZone::Comfort)
+    }
+
+    /// Over-target / boost indicator
+    pub fn warn(&self) -> Color32 {
+        self.zone_color(ThermalZone::Hot)
+    }
+
+    /// Status bar messages
+    pub fn status(&self) -> Color32 {
+        match self {
+            Theme::Light => Color32::from_rgb(140, 90, 0),
+            Theme::Dark | Theme::HighContrast => Color32::YELLOW,
+        }
+    }
+}
+
+/// Parse the portal reply for a `u` setting from `busctl` or `gdbus` output
+/// e.g. "v u 1" (busctl) or "(<<uint32 1>>,)" (gdbus)
+pub fn parse_portal_uint(output: &str) -> Option<u32> {
+    output
+        .split(|c: char| !c.is_ascii_digit())
+        .rfind(|s| !s.is_empty())
+        .and_then(|s| s.parse().ok())
+}
+
+/// Read one `org.freedesktop.appearance` key from the desktop portal
+fn read_portal_setting(key: &str) -> Option<u32> {
+    let output = Command::new("busctl")
+        .args([
+            "--user",
+            "call",
+            "org.freedesktop.portal.Desktop",
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Settings",
+            "ReadOne",
+            "ss",
+            "org.freedesktop.appearance",
+            key,
+        ])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .or_else(|| {
+            Command::new("gdbus")
+                .args([
+                    "call",
+                    "--session",
+                    "--dest",
+                    "org.freedesktop.portal.Desktop",
+                    "--object-path",
+                    "/org/freedesktop/portal/desktop",
+                    "--method",
+                    "org.freedesktop.portal.Settings.ReadOne",
+                    "org.freedesktop.appearance",
+                    key,
+                ])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+        })?;
+    parse_portal_uint(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Map portal values to a theme
+/// color-scheme: 0 = no preference, 1 = prefer dark, 2 = prefer light
+/// contrast: 0 = normal, 1 = high
+pub fn theme_from_portal(color_scheme: Option<u32>, contrast: Option<u32>) -> Option<Theme> {
+    if contrast == Some(1) {
+        return Some(Theme::HighContrast);
+    }
+    match color_scheme {
+        Some(1) => Some(Theme::Dark),
+        Some(2) => Some(Theme::Light),
+        _ => None,
+    }
+}
+
+/// Query the desktop's preferred theme, if the portal is available
+pub fn read_system_theme() -> Option<Theme> {
+    theme_from_portal(read_portal_setting("color-scheme"), read_portal_setting("contrast"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZONES: [ThermalZone; 6] = [
+        ThermalZone::Cool,
+        ThermalZone::Comfort,
+        ThermalZone::Optimal,
+        ThermalZone::Warm,
+        ThermalZone::Hot,
+        ThermalZone::Critical,
+    ];
+
+    /// Relative luminance per WCAG 2.x
+    fn luminance(c: Color32) -> f32 {
+        let lin = |v: u8| {
+            let v = v as f32 / 255.0;
+            if v <= 0.03928 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * lin(c.r()) + 0.7152 * lin(c.g()) + 0.0722 * lin(c.b())
+    }
+
+    fn contrast(a: Color32, b: Color32) -> f32 {
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+
+    #[test]
+    fn test_palettes_readable_on_background() {
+        for theme in [Theme::Light, Theme::Dark, Theme::HighContrast] {
+            let bg = theme.visuals().panel_fill;
+            let min = if theme == Theme::HighContrast { 7.0 } else { 3.0 };
+            for zone in ZONES {
+                let c = contrast(theme.zone_color(zone), bg);
+                assert!(c >= min, "{:?} {:?} contrast {:.1}", theme, zone, c);
+            }
+            // A custom mode without its own color gets the fallback accent
+            let custom = Mode::Custom("unsaved".into());
+            for mode in Mode::all().iter().chain([&custom]) {
+                let c = contrast(theme.mode_color(mode), bg);
+                assert!(c >= min, "{:?} {:?} contrast {:.1}", theme, mode, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_preference_resolution() {
+        assert_eq!(ThemePreference::System.resolve(None), Theme::Dark);
+        assert_eq!(ThemePreference::System.resolve(Some(Theme::Light)), Theme::Light);
+        assert_eq!(ThemePreference::HighContrast.resolve(Some(Theme::Light)), Theme::HighContrast);
+        assert_eq!(ThemePreference::Light.resolve(Some(Theme::Dark)), Theme::Light);
+    }
+
+    #[test]
+    fn test_preference_keys_roundtrip() {
+        for pref in ThemePreference::all() {
+            assert_eq!(ThemePreference::parse(pref.key()), Some(*pref));
+        }
+        assert_eq!(ThemePreference::parse("solarized"), None);
+    }
+
+    #[test]
+    fn test_parse_portal_output() {
+        assert_eq!(parse_portal_uint("v u 1\n"), Some(1));
+        assert_eq!(parse_portal_uint("(<<uint32 2>>,)\n"), Some(2));
+        assert_eq!(parse_portal_uint(""), None);
+    }
+
+    #[test]
+    fn test_theme_from_portal() {
+        assert_eq!(theme_from_portal(Some(1), Some(0)), Some(Theme::Dark));
+        assert_eq!(theme_from_portal(Some(2), None), Some(Theme::Light));
+        assert_eq!(theme_from_portal(Some(2), Some(1)), Some(Theme::HighContrast));
+        assert_eq!(theme_from_portal(Some(0), None), None);
+        assert_eq!(theme_from_portal(None, None), None);
+    }
+}
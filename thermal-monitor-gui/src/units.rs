@@ -0,0 +1,229 @@
+//! Display units for temperatures and frequencies
+//!
+//! Control logic always works in Celsius and MHz; these types only
+//! convert values at the edges (GUI, CLI output, exported data).
+
+use crate::i18n::{tr, tr_args};
+
+/// Temperature display unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TempUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TempUnit {
+    /// Convert a Celsius reading to this unit
+    pub fn convert(self, celsius: f32) -> f32 {
+        match self {
+            TempUnit::Celsius => celsius,
+            TempUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    /// Convert a value in this unit back to Celsius
+    pub fn to_celsius(self, value: f32) -> f32 {
+        match self {
+            TempUnit::Celsius => value,
+            TempUnit::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+        }
+    }
+
+    /// Convert a Celsius difference (no offset) to this unit
+    pub fn convert_delta(self, delta: f32) -> f32 {
+        match self {
+            TempUnit::Celsius => delta,
+            TempUnit::Fahrenheit => delta * 9.0 / 5.0,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TempUnit::Celsius => "°C",
+            TempUnit::Fahrenheit => "°F",
+        }
+    }
+
+    /// Unit name as read by screen readers
+    pub fn spoken_name(&self) -> &'static str {
+        match self {
+            TempUnit::Celsius => tr("unit-celsius"),
+            TempUnit::Fahrenheit => tr("unit-fahrenheit"),
+        }
+    }
+
+    /// Config file key
+    pub fn key(&self) -> &'static str {
+        match self {
+            TempUnit::Celsius => "celsius",
+            TempUnit::Fahrenheit => "fahrenheit",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "c" | "celsius" => Some(TempUnit::Celsius),
+            "f" | "fahrenheit" => Some(TempUnit::Fahrenheit),
+            _ => None,
+        }
+    }
+
+    pub fn toggled(&self) -> Self {
+        match self {
+            TempUnit::Celsius => TempUnit::Fahrenheit,
+            TempUnit::Fahrenheit => TempUnit::Celsius,
+        }
+    }
+}
+
+/// Frequency display unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FreqUnit {
+    #[default]
+    GHz,
+    MHz,
+}
+
+impl FreqUnit {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            FreqUnit::GHz => "GHz",
+            FreqUnit::MHz => "MHz",
+        }
+    }
+
+    /// Unit name as read by screen readers
+    pub fn spoken_name(&self) -> &'static str {
+        match self {
+            FreqUnit::GHz => tr("unit-gigahertz"),
+            FreqUnit::MHz => tr("unit-megahertz"),
+        }
+    }
+
+    /// Config file key
+    pub fn key(&self) -> &'static str {
+        match self {
+            FreqUnit::GHz => "ghz",
+            FreqUnit::MHz => "mhz",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "ghz" | "g" => Some(FreqUnit::GHz),
+            "mhz" | "m" => Some(FreqUnit::MHz),
+            _ => None,
+        }
+    }
+
+    pub fn toggled(&self) -> Self {
+        match self {
+            FreqUnit::GHz => FreqUnit::MHz,
+            FreqUnit::MHz => FreqUnit::GHz,
+        }
+    }
+}
+
+/// Units preference applied across GUI, CLI and exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Units {
+    pub temp: TempUnit,
+    pub freq: FreqUnit,
+}
+
+impl Units {
+    /// Temperature value in the display unit
+    pub fn temp_value(&self, celsius: f32) -> f32 {
+        self.temp.convert(celsius)
+    }
+
+    /// Format a temperature reading ("52°C", "126°F")
+    pub fn temp(&self, celsius: f32) -> String {
+        format!("{:.0}{}", self.temp_value(celsius), self.temp.symbol())
+    }
+
+    /// Format a signed temperature difference ("+3°C")
+    pub fn temp_delta(&self, delta_celsius: f32) -> String {
+        format!("{:+.0}{}", self.temp.convert_delta(delta_celsius), self.temp.symbol())
+    }
+
+    /// Format a frequency ("2.5 GHz", "2500 MHz")
+    pub fn freq(&self, mhz: u32) -> String {
+        format!("{} {}", self.freq_number(mhz), self.freq.symbol())
+    }
+
+    fn freq_number(&self, mhz: u32) -> String {
+        match self.freq {
+            FreqUnit::GHz => format!("{:.1}", mhz as f32 / 1000.0),
+            FreqUnit::MHz => mhz.to_string(),
+        }
+    }
+
+    /// Temperature with the unit spelled out ("52 degrees Celsius")
+    pub fn temp_spoken(&self, celsius: f32) -> String {
+        let value = format!("{:.0}", self.temp_value(celsius));
+        tr_args("a11y-quantity", &[("value", &value), ("unit", self.temp.spoken_name())])
+    }
+
+    /// Frequency with the unit spelled out ("2.5 gigahertz")
+    pub fn freq_spoken(&self, mhz: u32) -> String {
+        let value = self.freq_number(mhz);
+        tr_args("a11y-quantity", &[("value", &value), ("unit", self.freq.spoken_name())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_celsius_fahrenheit_conversion() {
+        let f = TempUnit::Fahrenheit;
+        assert!((f.convert(0.0) - 32.0).abs() < 0.01);
+        assert!((f.convert(100.0) - 212.0).abs() < 0.01);
+        assert!((f.to_celsius(212.0) - 100.0).abs() < 0.01);
+        assert!((f.to_celsius(f.convert(55.0)) - 55.0).abs() < 0.01);
+        assert!((TempUnit::Celsius.convert(55.0) - 55.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_delta_has_no_offset() {
+        assert!((TempUnit::Fahrenheit.convert_delta(5.0) - 9.0).abs() < 0.01);
+        assert!((TempUnit::Celsius.convert_delta(5.0) - 5.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_unit_parsing() {
+        assert_eq!(TempUnit::parse("F"), Some(TempUnit::Fahrenheit));
+        assert_eq!(TempUnit::parse("celsius"), Some(TempUnit::Celsius));
+        assert_eq!(TempUnit::parse("kelvin"), None);
+        assert_eq!(FreqUnit::parse("MHz"), Some(FreqUnit::MHz));
+        assert_eq!(FreqUnit::parse(TempUnit::Celsius.key()), None);
+        assert_eq!(TempUnit::parse(TempUnit::Fahrenheit.key()), Some(TempUnit::Fahrenheit));
+    }
+
+    #[test]
+    fn test_formatting() {
+        let metric = Units::default();
+        assert_eq!(metric.temp(52.4), "52°C");
+        assert_eq!(metric.temp_delta(3.0), "+3°C");
+        assert_eq!(metric.freq(2500), "2.5 GHz");
+
+        let imperial = Units { temp: TempUnit::Fahrenheit, freq: FreqUnit::MHz };
+        assert_eq!(imperial.temp(50.0), "122°F");
+        assert_eq!(imperial.temp_delta(5.0), "+9°F");
+        assert_eq!(imperial.freq(2500), "2500 MHz");
+    }
+
+    #[test]
+    fn test_spoken_units() {
+        let metric = Units::default();
+        assert_eq!(metric.temp_spoken(52.4), "52 degrees Celsius");
+        assert_eq!(metric.freq_spoken(2500), "2.5 gigahertz");
+
+        let imperial = Units { temp: TempUnit::Fahrenheit, freq: FreqUnit::MHz };
+        assert_eq!(imperial.temp_spoken(50.0), "122 degrees Fahrenheit");
+        assert_eq!(imperial.freq_spoken(2500), "2500 megahertz");
+    }
+}